@@ -0,0 +1,539 @@
+use crate::{
+    protocol::{Options, Range, TextEdit, Uri, WorkspaceEdit},
+    syntax::{bibtex, latex, CharStream, SyntaxNode},
+    workspace::Document,
+};
+use once_cell::sync::Lazy;
+use petgraph::graph::NodeIndex;
+use regex::Regex;
+use serde_json::Value;
+use std::{
+    collections::{HashMap, HashSet},
+    fmt::Write,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+pub const TRIM_BIBLIOGRAPHY_COMMAND: &str = "texlab.trimBibliography";
+
+pub const IMPORT_CITATION_COMMAND: &str = "texlab.importCitation";
+
+/// The citation keys that are used by a group of related documents.
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub struct CitationUsage {
+    keys: HashSet<String>,
+    cites_all: bool,
+}
+
+impl CitationUsage {
+    /// Collects the citations of the related LaTeX documents.
+    /// Returns `None` if the bibliography is not used by any LaTeX document.
+    pub fn find(related: &[Arc<Document>]) -> Option<Self> {
+        let tables: Vec<_> = related
+            .iter()
+            .filter_map(|doc| doc.content.as_latex())
+            .collect();
+
+        if tables.is_empty() {
+            return None;
+        }
+
+        let mut usage = Self::default();
+        for table in tables {
+            for key in table
+                .citations
+                .iter()
+                .flat_map(|citation| citation.keys(&table.tree))
+            {
+                if key.text() == "*" {
+                    usage.cites_all = true;
+                } else {
+                    usage.keys.insert(key.text().to_owned());
+                }
+            }
+        }
+        Some(usage)
+    }
+
+    pub fn is_cited(&self, key: &str) -> bool {
+        self.cites_all || self.keys.contains(key)
+    }
+
+    /// Returns the entries that end up in the bibliography,
+    /// including the `crossref` parents of the cited entries.
+    pub fn used_entries(&self, tree: &bibtex::Tree) -> HashSet<NodeIndex> {
+        let mut entries = HashSet::new();
+        let mut queue: Vec<_> = tree
+            .children(tree.root)
+            .filter(|node| {
+                tree.as_entry(*node)
+                    .filter(|entry| !entry.is_comment())
+                    .and_then(|entry| entry.key.as_ref())
+                    .map_or(false, |key| self.is_cited(key.text()))
+            })
+            .collect();
+
+        while let Some(entry) = queue.pop() {
+            if entries.insert(entry) {
+                queue.extend(tree.crossref(entry));
+            }
+        }
+        entries
+    }
+}
+
+/// Creates a copy of the bibliography that contains only the used entries
+/// and the `@preamble`s and `@string`s they depend on.
+pub fn trim(text: &str, tree: &bibtex::Tree, usage: &CitationUsage) -> String {
+    let entries = usage.used_entries(tree);
+    let mut strings = HashSet::new();
+    let mut queue: Vec<_> = entries
+        .iter()
+        .flat_map(|entry| string_references(tree, *entry))
+        .collect();
+
+    while let Some(name) = queue.pop() {
+        if !strings.insert(name) {
+            continue;
+        }
+
+        for node in tree.children(tree.root) {
+            let is_definition = tree
+                .as_string(node)
+                .and_then(|string| string.name.as_ref())
+                .map_or(false, |string| string.text() == name);
+            if is_definition {
+                queue.extend(string_references(tree, node));
+            }
+        }
+    }
+
+    let nodes: Vec<_> = tree
+        .children(tree.root)
+        .filter(|node| match &tree.graph[*node] {
+            bibtex::Node::Preamble(_) => true,
+            bibtex::Node::String(string) => string
+                .name
+                .as_ref()
+                .map_or(false, |name| strings.contains(name.text())),
+            bibtex::Node::Entry(_) => entries.contains(node),
+            _ => false,
+        })
+        .collect();
+
+    let mut result = String::new();
+    for node in nodes {
+        result.push_str(&CharStream::extract(text, tree.graph[node].range()));
+        result.push_str("\n\n");
+    }
+    result.truncate(result.trim_end().len());
+    result.push('\n');
+    result
+}
+
+/// Returns the names of the `@string`s that are referenced inside of the given node.
+fn string_references(tree: &bibtex::Tree, parent: NodeIndex) -> Vec<&str> {
+    let mut names = Vec::new();
+    for node in tree.children(parent) {
+        match &tree.graph[node] {
+            bibtex::Node::Word(word) => match &tree.graph[parent] {
+                bibtex::Node::Field(_) | bibtex::Node::Concat(_) | bibtex::Node::String(_) => {
+                    names.push(word.token.text())
+                }
+                _ => (),
+            },
+            bibtex::Node::QuotedContent(_) | bibtex::Node::BracedContent(_) => (),
+            _ => names.append(&mut string_references(tree, node)),
+        }
+    }
+    names
+}
+
+/// Returns the text of a `\bibitem` without line breaks and `\newblock`s.
+pub fn bib_item_text(text: &str, item: latex::BibItem) -> String {
+    CharStream::extract(text, item.body)
+        .replace("\\newblock", "")
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+static NAME_SEPARATOR_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"\s+and\s+").unwrap());
+
+/// Splits a list of names like the value of an `author` field at the `and`s between them.
+/// The whitespace inside of every name is normalized.
+pub fn split_names(names: &str) -> impl Iterator<Item = String> + '_ {
+    NAME_SEPARATOR_REGEX
+        .split(names)
+        .map(|name| name.split_whitespace().collect::<Vec<_>>().join(" "))
+}
+
+/// Returns the offset of the last name in a list of names,
+/// which is the end of the last `and` or zero if there is only one name.
+pub fn last_name_offset(names: &str) -> usize {
+    NAME_SEPARATOR_REGEX
+        .find_iter(names)
+        .last()
+        .map_or(0, |separator| separator.end())
+}
+
+/// Returns the global bibliographies that are configured with the `bibtex.libraries` option.
+pub fn library_uris(options: &Options, current_dir: &Path) -> Vec<Uri> {
+    options
+        .bibtex
+        .as_ref()
+        .and_then(|opts| opts.libraries.as_ref())
+        .into_iter()
+        .flatten()
+        .filter_map(|path| Uri::from_file_path(current_dir.join(path)).ok())
+        .collect()
+}
+
+/// Creates an edit that copies an entry from a library to the end of a bibliography.
+/// Returns `None` if the bibliography already contains an entry with the same key.
+pub fn import_entry(library: &Document, key: &str, target: &Document) -> Option<WorkspaceEdit> {
+    let tree = library.content.as_bibtex()?;
+    let entry = tree.entry_by_key(key)?;
+    if target.content.as_bibtex()?.entry_by_key(key).is_some() {
+        return None;
+    }
+
+    let entry_text = CharStream::extract(&library.text, tree.graph[entry].range());
+    let separator = if target.text.trim().is_empty() {
+        ""
+    } else if target.text.ends_with('\n') {
+        "\n"
+    } else {
+        "\n\n"
+    };
+
+    let mut stream = CharStream::new(&target.text);
+    while stream.next().is_some() {}
+    let end = stream.current_position;
+
+    let edit = TextEdit::new(
+        Range::new(end, end),
+        format!("{}{}\n", separator, entry_text),
+    );
+    let mut changes = HashMap::new();
+    changes.insert(target.uri.clone().into(), vec![edit]);
+    Some(WorkspaceEdit::new(changes))
+}
+
+/// Converts a CSL-JSON library, e.g. exported by Zotero, into BibTeX entries.
+pub fn convert_csl_json(text: &str) -> Option<String> {
+    let items: Vec<Value> = serde_json::from_str(text).ok()?;
+    let mut result = String::new();
+    for item in &items {
+        let key = match csl_text(&item["id"]) {
+            Some(key) if is_valid_key(&key) => key,
+            _ => continue,
+        };
+
+        let ty = match item["type"].as_str().unwrap_or_default() {
+            "article" | "article-journal" | "article-magazine" | "article-newspaper" => "article",
+            "book" => "book",
+            "chapter" => "incollection",
+            "paper-conference" => "inproceedings",
+            "report" => "techreport",
+            "thesis" => "phdthesis",
+            "webpage" | "post-weblog" => "online",
+            _ => "misc",
+        };
+
+        let container = if ty == "article" {
+            "journal"
+        } else {
+            "booktitle"
+        };
+
+        let fields = vec![
+            ("author", csl_names(&item["author"])),
+            ("editor", csl_names(&item["editor"])),
+            ("title", csl_text(&item["title"])),
+            (container, csl_text(&item["container-title"])),
+            ("year", csl_text(&item["issued"]["date-parts"][0][0])),
+            ("volume", csl_text(&item["volume"])),
+            ("number", csl_text(&item["issue"])),
+            ("pages", csl_text(&item["page"])),
+            ("publisher", csl_text(&item["publisher"])),
+            ("doi", csl_text(&item["DOI"])),
+            ("url", csl_text(&item["URL"])),
+        ];
+
+        writeln!(result, "@{}{{{},", ty, key).ok()?;
+        for (name, value) in fields {
+            if let Some(value) = value {
+                let is_verbatim = name == "doi" || name == "url";
+                writeln!(
+                    result,
+                    "    {} = {{{}}},",
+                    name,
+                    csl_escape(&value, is_verbatim)
+                )
+                .ok()?;
+            }
+        }
+        result.push_str("}\n\n");
+    }
+    Some(result)
+}
+
+fn csl_text(value: &Value) -> Option<String> {
+    match value {
+        Value::String(text) => Some(text.clone()),
+        Value::Number(number) => Some(number.to_string()),
+        _ => None,
+    }
+}
+
+fn csl_names(value: &Value) -> Option<String> {
+    let names: Vec<_> = value
+        .as_array()?
+        .iter()
+        .filter_map(
+            |name| match (csl_text(&name["family"]), csl_text(&name["given"])) {
+                (Some(family), Some(given)) => Some(format!("{}, {}", family, given)),
+                (Some(family), None) => Some(family),
+                _ => csl_text(&name["literal"])
+                    .map(|literal| format!("{{{}}}", csl_escape(&literal, false))),
+            },
+        )
+        .collect();
+
+    if names.is_empty() {
+        None
+    } else {
+        Some(names.join(" and "))
+    }
+}
+
+/// Checks whether the given text can be used as the key of a BibTeX entry.
+fn is_valid_key(key: &str) -> bool {
+    !key.is_empty()
+        && key
+            .chars()
+            .all(|c| !c.is_whitespace() && !",{}()\"#%'=@\\".contains(c))
+}
+
+/// Escapes the value of a CSL-JSON item so that it can be used inside of a braced BibTeX field.
+/// Unbalanced braces are removed or closed and special characters are escaped.
+/// Verbatim fields like URLs only escape the characters that would end the field.
+fn csl_escape(text: &str, is_verbatim: bool) -> String {
+    let mut result = String::new();
+    let mut depth = 0;
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => {
+                if let Some(next) = chars.next() {
+                    result.push(c);
+                    result.push(next);
+                }
+            }
+            '{' => {
+                depth += 1;
+                result.push(c);
+            }
+            '}' if depth > 0 => {
+                depth -= 1;
+                result.push(c);
+            }
+            '}' => {}
+            '#' => result.push_str("\\#"),
+            '%' | '&' if !is_verbatim => {
+                result.push('\\');
+                result.push(c);
+            }
+            _ => result.push(c),
+        }
+    }
+
+    for _ in 0..depth {
+        result.push('}');
+    }
+    result
+}
+
+/// Returns the path of the trimmed copy of a bibliography.
+pub fn trimmed_path(uri: &Uri) -> Option<PathBuf> {
+    let path = uri.to_file_path().ok()?;
+    let stem = path.file_stem()?.to_str()?;
+    Some(path.with_file_name(format!("{}-trimmed.bib", stem)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        protocol::RangeExt,
+        tex::{Language, Resolver},
+        workspace::DocumentParams,
+    };
+    use indoc::indoc;
+    use std::env;
+
+    fn create_document(name: &str, text: &str, language: Language) -> Arc<Document> {
+        Arc::new(Document::open(DocumentParams {
+            uri: Uri::parse(&format!("http://www.example.com/{}", name)).unwrap(),
+            text: text.into(),
+            language,
+            resolver: &Resolver::default(),
+            options: &Options::default(),
+            current_dir: &env::current_dir().unwrap(),
+        }))
+    }
+
+    #[test]
+    fn usage_without_latex() {
+        let bib = create_document("main.bib", "", Language::Bibtex);
+        assert_eq!(CitationUsage::find(&[bib]), None);
+    }
+
+    #[test]
+    fn names() {
+        let names = "Foo,  Bar and Baz\nQux and others";
+        let actual_names: Vec<_> = split_names(names).collect();
+        assert_eq!(actual_names, vec!["Foo, Bar", "Baz Qux", "others"]);
+        assert_eq!(last_name_offset(names), 26);
+        assert_eq!(last_name_offset("Foo"), 0);
+    }
+
+    #[test]
+    fn usage_nocite_all() {
+        let tex = create_document("main.tex", "\\nocite{*}", Language::Latex);
+        let usage = CitationUsage::find(&[tex]).unwrap();
+        assert!(usage.is_cited("foo"));
+    }
+
+    #[test]
+    fn trim_entries() {
+        let tex = create_document("main.tex", "\\cite{foo}", Language::Latex);
+        let bib = create_document(
+            "main.bib",
+            indoc!(
+                r#"
+                    @string{pub = "Publisher"}
+                    @string{pubaddr = pub # " Inc."}
+                    @string{unused = "Unused"}
+                    @book{bar, publisher = pubaddr}
+                    @inproceedings{foo, crossref = {bar}}
+                    @article{baz, journal = unused}
+                "#
+            ),
+            Language::Bibtex,
+        );
+
+        let usage = CitationUsage::find(&[tex]).unwrap();
+        let actual = trim(&bib.text, bib.content.as_bibtex().unwrap(), &usage);
+        let expected = indoc!(
+            r#"
+                @string{pub = "Publisher"}
+
+                @string{pubaddr = pub # " Inc."}
+
+                @book{bar, publisher = pubaddr}
+
+                @inproceedings{foo, crossref = {bar}}
+            "#
+        );
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn import_entry_appends() {
+        let library = create_document(
+            "library.bib",
+            "@article{foo, title = {Foo}}\n@article{bar,}",
+            Language::Bibtex,
+        );
+        let target = create_document("main.bib", "@article{baz,}", Language::Bibtex);
+
+        let actual = import_entry(&library, "foo", &target).unwrap();
+
+        let mut changes = HashMap::new();
+        changes.insert(
+            target.uri.clone().into(),
+            vec![TextEdit::new(
+                Range::new_simple(0, 14, 0, 14),
+                "\n\n@article{foo, title = {Foo}}\n".into(),
+            )],
+        );
+        assert_eq!(actual, WorkspaceEdit::new(changes));
+    }
+
+    #[test]
+    fn import_entry_existing_key() {
+        let library = create_document("library.bib", "@article{foo,}", Language::Bibtex);
+        let target = create_document("main.bib", "@book{foo,}", Language::Bibtex);
+        assert_eq!(import_entry(&library, "foo", &target), None);
+    }
+
+    #[test]
+    fn csl_json() {
+        let actual = convert_csl_json(indoc!(
+            r#"
+                [
+                    {
+                        "id": "smith2019",
+                        "type": "article-journal",
+                        "title": "Graphs",
+                        "container-title": "Journal of Graphs",
+                        "author": [
+                            { "family": "Smith", "given": "John" },
+                            { "literal": "ACME" }
+                        ],
+                        "issued": { "date-parts": [[2019, 5]] },
+                        "volume": 12
+                    }
+                ]
+            "#
+        ));
+
+        let expected = indoc!(
+            r#"
+                @article{smith2019,
+                    author = {Smith, John and {ACME}},
+                    title = {Graphs},
+                    journal = {Journal of Graphs},
+                    year = {2019},
+                    volume = {12},
+                }
+
+            "#
+        );
+        assert_eq!(actual.as_deref(), Some(expected));
+    }
+
+    #[test]
+    fn csl_json_escape() {
+        let actual = convert_csl_json(indoc!(
+            r#"
+                [
+                    {
+                        "id": "smith2019",
+                        "type": "book",
+                        "title": "100% {Graphs} } & {Trees",
+                        "URL": "https://example.com/a%20b#c"
+                    },
+                    {
+                        "id": "foo, bar",
+                        "type": "book",
+                        "title": "Invalid"
+                    }
+                ]
+            "#
+        ));
+
+        let expected = indoc!(
+            r#"
+                @book{smith2019,
+                    title = {100\% {Graphs}  \& {Trees}},
+                    url = {https://example.com/a%20b\#c},
+                }
+
+            "#
+        );
+        assert_eq!(actual.as_deref(), Some(expected));
+    }
+}
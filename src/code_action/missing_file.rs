@@ -0,0 +1,165 @@
+use crate::{
+    diagnostics::MISSING_FILE_CODE,
+    feature::{FeatureProvider, FeatureRequest},
+    protocol::{
+        CodeAction, CodeActionKind, CodeActionParams, CreateFile, CreateFileOptions, Diagnostic,
+        DocumentChangeOperation, DocumentChanges, NumberOrString, ResourceOp,
+        ResourceOperationKind, Uri, WorkspaceEdit,
+    },
+    syntax::{LatexIncludeKind, SyntaxNode},
+};
+use async_trait::async_trait;
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub struct MissingFileCodeActionProvider;
+
+#[async_trait]
+impl FeatureProvider for MissingFileCodeActionProvider {
+    type Params = CodeActionParams;
+    type Output = Vec<CodeAction>;
+
+    async fn execute<'a>(&'a self, req: &'a FeatureRequest<Self::Params>) -> Self::Output {
+        let mut actions = Vec::new();
+        if !supports_create_file(req) {
+            return actions;
+        }
+
+        for diagnostic in &req.params.context.diagnostics {
+            if diagnostic.code != Some(NumberOrString::String(MISSING_FILE_CODE.into())) {
+                continue;
+            }
+
+            if let Some(uri) = find_target(req, diagnostic) {
+                let name = uri
+                    .path_segments()
+                    .and_then(|segments| segments.last())
+                    .unwrap_or_default()
+                    .to_owned();
+
+                let operation = ResourceOp::Create(CreateFile {
+                    uri: uri.into(),
+                    options: Some(CreateFileOptions {
+                        overwrite: Some(false),
+                        ignore_if_exists: Some(true),
+                    }),
+                });
+
+                actions.push(CodeAction {
+                    title: format!("Create `{}`", name),
+                    kind: Some(CodeActionKind::QUICKFIX),
+                    diagnostics: Some(vec![diagnostic.clone()]),
+                    edit: Some(WorkspaceEdit {
+                        changes: None,
+                        document_changes: Some(DocumentChanges::Operations(vec![
+                            DocumentChangeOperation::Op(operation),
+                        ])),
+                    }),
+                    ..CodeAction::default()
+                });
+            }
+        }
+        actions
+    }
+}
+
+fn supports_create_file(req: &FeatureRequest<CodeActionParams>) -> bool {
+    req.client_capabilities
+        .workspace
+        .as_ref()
+        .and_then(|cap| cap.workspace_edit.as_ref())
+        .and_then(|cap| cap.resource_operations.as_ref())
+        .map_or(false, |ops| ops.contains(&ResourceOperationKind::Create))
+}
+
+fn find_target(req: &FeatureRequest<CodeActionParams>, diagnostic: &Diagnostic) -> Option<Uri> {
+    let table = req.current().content.as_latex()?;
+    table
+        .includes
+        .iter()
+        .filter(|include| include.kind == LatexIncludeKind::Latex)
+        .find_map(|include| {
+            include
+                .paths(&table.tree)
+                .into_iter()
+                .zip(&include.all_targets)
+                .find(|(path, _)| path.range() == diagnostic.range)
+                .and_then(|(_, targets)| {
+                    targets
+                        .iter()
+                        .find(|target| target.path().ends_with(".tex"))
+                        .cloned()
+                })
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        feature::FeatureTester,
+        protocol::{
+            ClientCapabilities, Range, RangeExt, WorkspaceClientCapabilities,
+            WorkspaceEditCapability,
+        },
+    };
+
+    fn create_diagnostic() -> Diagnostic {
+        Diagnostic {
+            source: Some("latex".into()),
+            code: Some(NumberOrString::String(MISSING_FILE_CODE.into())),
+            ..Diagnostic::new_simple(Range::new_simple(0, 7, 0, 10), String::new())
+        }
+    }
+
+    fn create_capabilities() -> ClientCapabilities {
+        ClientCapabilities {
+            workspace: Some(WorkspaceClientCapabilities {
+                workspace_edit: Some(WorkspaceEditCapability {
+                    resource_operations: Some(vec![ResourceOperationKind::Create]),
+                    ..WorkspaceEditCapability::default()
+                }),
+                ..WorkspaceClientCapabilities::default()
+            }),
+            ..ClientCapabilities::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn create_file() {
+        let actions = FeatureTester::new()
+            .file("main.tex", "\\input{foo}")
+            .main("main.tex")
+            .diagnostic(create_diagnostic())
+            .client_capabilities(create_capabilities())
+            .test_code_action(MissingFileCodeActionProvider)
+            .await;
+
+        assert_eq!(actions.len(), 1);
+        assert_eq!(actions[0].title, "Create `foo.tex`");
+        let expected_edit = WorkspaceEdit {
+            changes: None,
+            document_changes: Some(DocumentChanges::Operations(vec![
+                DocumentChangeOperation::Op(ResourceOp::Create(CreateFile {
+                    uri: FeatureTester::uri("foo.tex").into(),
+                    options: Some(CreateFileOptions {
+                        overwrite: Some(false),
+                        ignore_if_exists: Some(true),
+                    }),
+                })),
+            ])),
+        };
+        assert_eq!(actions[0].edit, Some(expected_edit));
+    }
+
+    #[tokio::test]
+    async fn unsupported() {
+        let actions = FeatureTester::new()
+            .file("main.tex", "\\input{foo}")
+            .main("main.tex")
+            .diagnostic(create_diagnostic())
+            .test_code_action(MissingFileCodeActionProvider)
+            .await;
+
+        assert!(actions.is_empty());
+    }
+}
@@ -0,0 +1,47 @@
+mod missing_file;
+mod spelling;
+mod suppression;
+mod unicode;
+
+use self::{
+    missing_file::MissingFileCodeActionProvider, spelling::SpellingCodeActionProvider,
+    suppression::SuppressionCodeActionProvider, unicode::UnicodeCodeActionProvider,
+};
+use crate::{
+    feature::{ConcatProvider, FeatureProvider, FeatureRequest},
+    protocol::{CodeAction, CodeActionParams},
+};
+use async_trait::async_trait;
+
+pub struct CodeActionProvider {
+    provider: ConcatProvider<CodeActionParams, CodeAction>,
+}
+
+impl CodeActionProvider {
+    pub fn new() -> Self {
+        Self {
+            provider: ConcatProvider::new(vec![
+                Box::new(MissingFileCodeActionProvider),
+                Box::new(SpellingCodeActionProvider),
+                Box::new(SuppressionCodeActionProvider),
+                Box::new(UnicodeCodeActionProvider),
+            ]),
+        }
+    }
+}
+
+impl Default for CodeActionProvider {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl FeatureProvider for CodeActionProvider {
+    type Params = CodeActionParams;
+    type Output = Vec<CodeAction>;
+
+    async fn execute<'a>(&'a self, req: &'a FeatureRequest<Self::Params>) -> Self::Output {
+        self.provider.execute(req).await
+    }
+}
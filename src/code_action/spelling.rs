@@ -0,0 +1,128 @@
+use crate::{
+    feature::{FeatureProvider, FeatureRequest},
+    protocol::{
+        CodeAction, CodeActionKind, CodeActionParams, Command, Diagnostic, TextEdit, WorkspaceEdit,
+    },
+    spelling::{self, ADD_TO_DICTIONARY_COMMAND},
+    syntax::CharStream,
+};
+use async_trait::async_trait;
+use serde_json::json;
+use std::collections::HashMap;
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub struct SpellingCodeActionProvider;
+
+#[async_trait]
+impl FeatureProvider for SpellingCodeActionProvider {
+    type Params = CodeActionParams;
+    type Output = Vec<CodeAction>;
+
+    async fn execute<'a>(&'a self, req: &'a FeatureRequest<Self::Params>) -> Self::Output {
+        let diagnostics: Vec<_> = req
+            .params
+            .context
+            .diagnostics
+            .iter()
+            .filter(|diagnostic| diagnostic.source.as_deref() == Some("spelling"))
+            .collect();
+
+        let mut actions = Vec::new();
+        if diagnostics.is_empty() {
+            return actions;
+        }
+
+        let dictionary = spelling::find_dictionary(req.related(), &req.options).await;
+        for diagnostic in diagnostics {
+            let word = CharStream::extract(&req.current().text, diagnostic.range);
+            if let Some(dictionary) = &dictionary {
+                for suggestion in dictionary.suggest(&word) {
+                    actions.push(create_replace_action(req, diagnostic, suggestion));
+                }
+            }
+
+            actions.push(CodeAction {
+                title: format!("Add `{}` to the project dictionary", word),
+                kind: Some(CodeActionKind::QUICKFIX),
+                diagnostics: Some(vec![diagnostic.clone()]),
+                command: Some(Command {
+                    title: "Add to dictionary".into(),
+                    command: ADD_TO_DICTIONARY_COMMAND.into(),
+                    arguments: Some(vec![json!(word), json!(req.current().uri.as_str())]),
+                }),
+                ..CodeAction::default()
+            });
+        }
+        actions
+    }
+}
+
+fn create_replace_action(
+    req: &FeatureRequest<CodeActionParams>,
+    diagnostic: &Diagnostic,
+    suggestion: String,
+) -> CodeAction {
+    let title = format!("Change to `{}`", suggestion);
+    let mut changes = HashMap::new();
+    changes.insert(
+        req.current().uri.clone().into(),
+        vec![TextEdit::new(diagnostic.range, suggestion)],
+    );
+    CodeAction {
+        title,
+        kind: Some(CodeActionKind::QUICKFIX),
+        diagnostics: Some(vec![diagnostic.clone()]),
+        edit: Some(WorkspaceEdit::new(changes)),
+        ..CodeAction::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        feature::FeatureTester,
+        protocol::{NumberOrString, Range, RangeExt},
+    };
+
+    #[tokio::test]
+    async fn add_to_dictionary() {
+        let actions = FeatureTester::new()
+            .file("main.tex", "foo wrod bar")
+            .main("main.tex")
+            .position(0, 5)
+            .diagnostic(Diagnostic {
+                source: Some("spelling".into()),
+                code: Some(NumberOrString::String("misspelled-word".into())),
+                ..Diagnostic::new_simple(Range::new_simple(0, 4, 0, 8), "Unknown word: wrod".into())
+            })
+            .test_code_action(SpellingCodeActionProvider)
+            .await;
+
+        let action = actions.last().unwrap();
+        assert_eq!(action.title, "Add `wrod` to the project dictionary");
+        assert_eq!(
+            action.command.as_ref().unwrap().arguments,
+            Some(vec![
+                json!("wrod"),
+                json!(FeatureTester::uri("main.tex").as_str())
+            ])
+        );
+    }
+
+    #[tokio::test]
+    async fn other_source() {
+        let actions = FeatureTester::new()
+            .file("main.tex", "foo")
+            .main("main.tex")
+            .position(0, 0)
+            .diagnostic(Diagnostic {
+                source: Some("chktex".into()),
+                ..Diagnostic::new_simple(Range::new_simple(0, 0, 0, 3), String::new())
+            })
+            .test_code_action(SpellingCodeActionProvider)
+            .await;
+
+        assert!(actions.is_empty());
+    }
+}
@@ -0,0 +1,165 @@
+use crate::{
+    diagnostics::diagnostic_name,
+    feature::{FeatureProvider, FeatureRequest},
+    protocol::{
+        CodeAction, CodeActionKind, CodeActionParams, Diagnostic, Position, Range, TextEdit,
+        WorkspaceEdit,
+    },
+    workspace::DocumentContent,
+};
+use async_trait::async_trait;
+use std::collections::HashMap;
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub struct SuppressionCodeActionProvider;
+
+#[async_trait]
+impl FeatureProvider for SuppressionCodeActionProvider {
+    type Params = CodeActionParams;
+    type Output = Vec<CodeAction>;
+
+    async fn execute<'a>(&'a self, req: &'a FeatureRequest<Self::Params>) -> Self::Output {
+        let mut actions = Vec::new();
+        for diagnostic in &req.params.context.diagnostics {
+            let name = match diagnostic_name(diagnostic) {
+                Some(name) => name,
+                None => continue,
+            };
+
+            if let DocumentContent::Latex(_) = &req.current().content {
+                let line = diagnostic.range.start.line;
+                let indent: String = req
+                    .current()
+                    .text
+                    .lines()
+                    .nth(line as usize)
+                    .unwrap_or_default()
+                    .chars()
+                    .take_while(|c| c.is_whitespace())
+                    .collect();
+
+                actions.push(create_action(
+                    req,
+                    format!("Suppress `{}` for this line", name),
+                    diagnostic,
+                    Position::new(line, 0),
+                    format!("{}% texlab: ignore-next-line {}\n", indent, name),
+                ));
+            }
+
+            actions.push(create_action(
+                req,
+                format!("Suppress `{}` for this file", name),
+                diagnostic,
+                Position::new(0, 0),
+                format!("% texlab: disable {}\n", name),
+            ));
+        }
+        actions
+    }
+}
+
+fn create_action(
+    req: &FeatureRequest<CodeActionParams>,
+    title: String,
+    diagnostic: &Diagnostic,
+    position: Position,
+    text: String,
+) -> CodeAction {
+    let edit = TextEdit::new(Range::new(position, position), text);
+    let mut changes = HashMap::new();
+    changes.insert(req.current().uri.clone().into(), vec![edit]);
+    CodeAction {
+        title,
+        kind: Some(CodeActionKind::QUICKFIX),
+        diagnostics: Some(vec![diagnostic.clone()]),
+        edit: Some(WorkspaceEdit::new(changes)),
+        ..CodeAction::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        feature::FeatureTester,
+        protocol::{NumberOrString, RangeExt},
+    };
+    use indoc::indoc;
+
+    fn create_diagnostic(line: u64) -> Diagnostic {
+        let range = Range::new_simple(line, 2, line, 3);
+        Diagnostic {
+            source: Some("chktex".into()),
+            code: Some(NumberOrString::String("8".into())),
+            ..Diagnostic::new_simple(range, "Wrong length of dash".into())
+        }
+    }
+
+    #[tokio::test]
+    async fn latex() {
+        let actual_actions = FeatureTester::new()
+            .file(
+                "foo.tex",
+                indoc!(
+                    r#"
+                        foo
+                          a-b
+                    "#
+                ),
+            )
+            .main("foo.tex")
+            .diagnostic(create_diagnostic(1))
+            .test_code_action(SuppressionCodeActionProvider)
+            .await;
+
+        let titles: Vec<_> = actual_actions
+            .iter()
+            .map(|action| action.title.as_str())
+            .collect();
+        assert_eq!(
+            titles,
+            vec![
+                "Suppress `chktex-8` for this line",
+                "Suppress `chktex-8` for this file"
+            ]
+        );
+
+        let mut expected_changes = HashMap::new();
+        expected_changes.insert(
+            FeatureTester::uri("foo.tex").into(),
+            vec![TextEdit::new(
+                Range::new_simple(1, 0, 1, 0),
+                "  % texlab: ignore-next-line chktex-8\n".into(),
+            )],
+        );
+        assert_eq!(
+            actual_actions[0].edit,
+            Some(WorkspaceEdit::new(expected_changes))
+        );
+    }
+
+    #[tokio::test]
+    async fn bibtex() {
+        let actual_actions = FeatureTester::new()
+            .file("foo.bib", "@article{foo,")
+            .main("foo.bib")
+            .diagnostic(create_diagnostic(0))
+            .test_code_action(SuppressionCodeActionProvider)
+            .await;
+
+        assert_eq!(actual_actions.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn no_code() {
+        let actual_actions = FeatureTester::new()
+            .file("foo.tex", "foo")
+            .main("foo.tex")
+            .diagnostic(Diagnostic::new_simple(Range::default(), "foo".into()))
+            .test_code_action(SuppressionCodeActionProvider)
+            .await;
+
+        assert!(actual_actions.is_empty());
+    }
+}
@@ -0,0 +1,203 @@
+use crate::{
+    components::{Command, Component, COMPONENT_DATABASE},
+    feature::{FeatureProvider, FeatureRequest},
+    protocol::{
+        CodeAction, CodeActionKind, CodeActionParams, Options, Position, Range, TextEdit,
+        WorkspaceEdit,
+    },
+    syntax::latex,
+    workspace::DocumentContent,
+};
+use async_trait::async_trait;
+use once_cell::sync::Lazy;
+use std::{collections::HashMap, ptr};
+
+/// The engines that support Unicode input without any further setup.
+const UNICODE_ENGINES: &[&str] = &["xelatex", "lualatex", "tectonic"];
+
+/// The `latexmk` flags that select one of the Unicode engines.
+const UNICODE_ENGINE_FLAGS: &[&str] = &["-xelatex", "-lualatex", "-pdfxe", "-pdflua"];
+
+type ComponentCommand = (&'static Component, &'static Command);
+
+/// The commands of the component database by the glyph that they produce.
+static COMMANDS_BY_GLYPH: Lazy<HashMap<&'static str, Vec<ComponentCommand>>> = Lazy::new(|| {
+    let mut commands_by_glyph = HashMap::new();
+    for component in &COMPONENT_DATABASE.components {
+        for cmd in &component.commands {
+            if let Some(glyph) = &cmd.glyph {
+                commands_by_glyph
+                    .entry(glyph.as_str())
+                    .or_insert_with(Vec::new)
+                    .push((component, cmd));
+            }
+        }
+    }
+    commands_by_glyph
+});
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub struct UnicodeCodeActionProvider;
+
+#[async_trait]
+impl FeatureProvider for UnicodeCodeActionProvider {
+    type Params = CodeActionParams;
+    type Output = Vec<CodeAction>;
+
+    async fn execute<'a>(&'a self, req: &'a FeatureRequest<Self::Params>) -> Self::Output {
+        let mut actions = Vec::new();
+        let table = match &req.current().content {
+            DocumentContent::Latex(table) => table,
+            DocumentContent::Bibtex(_) => return actions,
+        };
+
+        if !uses_pdflatex(&req.options) {
+            return actions;
+        }
+
+        let math_mode = latex::MathMode::analyze(table);
+        let components = req.view.components();
+        let selection = req.params.range;
+        let mut edits = Vec::new();
+        for (line, text) in req.current().text.lines().enumerate() {
+            let chars: Vec<char> = text.chars().collect();
+            for (character, c) in chars.iter().enumerate() {
+                let start = Position::new(line as u64, character as u64);
+                let end = Position::new(line as u64, character as u64 + 1);
+                if c.is_ascii()
+                    || (selection.start != selection.end
+                        && (start < selection.start || end > selection.end))
+                {
+                    continue;
+                }
+
+                let cmd = COMMANDS_BY_GLYPH
+                    .get(c.to_string().as_str())
+                    .and_then(|commands| {
+                        components.iter().find_map(|component| {
+                            commands
+                                .iter()
+                                .find(|(other, _)| ptr::eq(*component, *other))
+                                .map(|(_, cmd)| *cmd)
+                        })
+                    });
+
+                if let Some(cmd) = cmd {
+                    let mut text = cmd.symbol_text(math_mode.contains(start));
+                    if text.ends_with(|c: char| c.is_ascii_alphabetic())
+                        && chars
+                            .get(character + 1)
+                            .map_or(false, |c| c.is_ascii_alphabetic())
+                    {
+                        text.push_str("{}");
+                    }
+                    edits.push(TextEdit::new(Range::new(start, end), text));
+                }
+            }
+        }
+
+        if !edits.is_empty() {
+            let mut changes = HashMap::new();
+            changes.insert(req.current().uri.clone().into(), edits);
+            actions.push(CodeAction {
+                title: "Convert Unicode characters to LaTeX commands".into(),
+                kind: Some(CodeActionKind::REFACTOR_REWRITE),
+                edit: Some(WorkspaceEdit::new(changes)),
+                ..CodeAction::default()
+            });
+        }
+        actions
+    }
+}
+
+/// Checks whether the document is compiled with pdfLaTeX,
+/// which needs commands instead of Unicode symbols.
+fn uses_pdflatex(options: &Options) -> bool {
+    let build = options
+        .latex
+        .as_ref()
+        .and_then(|opts| opts.build.clone())
+        .unwrap_or_default();
+
+    !UNICODE_ENGINES.contains(&build.executable().as_str())
+        && !build
+            .args()
+            .iter()
+            .any(|arg| UNICODE_ENGINE_FLAGS.contains(&arg.as_str()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        feature::FeatureTester,
+        protocol::{LatexBuildOptions, LatexOptions, RangeExt},
+    };
+
+    #[tokio::test]
+    async fn empty_latex_document() {
+        let actions = FeatureTester::new()
+            .file("main.tex", "")
+            .main("main.tex")
+            .test_code_action(UnicodeCodeActionProvider)
+            .await;
+
+        assert!(actions.is_empty());
+    }
+
+    #[tokio::test]
+    async fn convert() {
+        let actions = FeatureTester::new()
+            .file("main.tex", "ε $ε$")
+            .main("main.tex")
+            .test_code_action(UnicodeCodeActionProvider)
+            .await;
+
+        assert_eq!(actions.len(), 1);
+        let mut changes = HashMap::new();
+        changes.insert(
+            FeatureTester::uri("main.tex").into(),
+            vec![
+                TextEdit::new(Range::new_simple(0, 0, 0, 1), "$\\varepsilon$".into()),
+                TextEdit::new(Range::new_simple(0, 3, 0, 4), "\\varepsilon".into()),
+            ],
+        );
+        assert_eq!(actions[0].edit, Some(WorkspaceEdit::new(changes)));
+    }
+
+    #[tokio::test]
+    async fn convert_before_letter() {
+        let actions = FeatureTester::new()
+            .file("main.tex", "$εx$")
+            .main("main.tex")
+            .test_code_action(UnicodeCodeActionProvider)
+            .await;
+
+        let mut changes = HashMap::new();
+        changes.insert(
+            FeatureTester::uri("main.tex").into(),
+            vec![TextEdit::new(
+                Range::new_simple(0, 1, 0, 2),
+                "\\varepsilon{}".into(),
+            )],
+        );
+        assert_eq!(actions[0].edit, Some(WorkspaceEdit::new(changes)));
+    }
+
+    #[test]
+    fn xelatex() {
+        let options = Options {
+            latex: Some(LatexOptions {
+                build: Some(LatexBuildOptions {
+                    args: Some(vec!["-xelatex".into()]),
+                    ..LatexBuildOptions::default()
+                }),
+                ..LatexOptions::default()
+            }),
+            ..Options::default()
+        };
+
+        assert!(!uses_pdflatex(&options));
+        assert!(uses_pdflatex(&Options::default()));
+    }
+}
@@ -30,6 +30,7 @@ pub async fn complete_bibtex_commands<'a>(
                             image: cmd.image.as_deref(),
                             glyph: cmd.glyph.as_deref(),
                             file_names: &[],
+                            arg_count: 0,
                         },
                     );
                     items.push(item);
@@ -0,0 +1,418 @@
+use crate::{
+    bibliography::{last_name_offset, split_names},
+    completion::types::{Item, ItemData},
+    feature::FeatureRequest,
+    protocol::{CompletionParams, Position, Range, RangeExt},
+    syntax::{bibtex, BibtexEntryTypeCategory, CharStream, Structure, SyntaxNode, LANGUAGE_DATA},
+    workspace::{Document, DocumentContent},
+};
+use petgraph::graph::NodeIndex;
+use std::collections::HashSet;
+
+const MONTHS: &[(&str, &str)] = &[
+    ("jan", "January"),
+    ("feb", "February"),
+    ("mar", "March"),
+    ("apr", "April"),
+    ("may", "May"),
+    ("jun", "June"),
+    ("jul", "July"),
+    ("aug", "August"),
+    ("sep", "September"),
+    ("oct", "October"),
+    ("nov", "November"),
+    ("dec", "December"),
+];
+
+const KEY_FIELDS: &[&str] = &["crossref", "xdata", "xref", "related", "set"];
+
+const NAME_FIELDS: &[&str] = &[
+    "author",
+    "editor",
+    "editora",
+    "editorb",
+    "editorc",
+    "translator",
+    "bookauthor",
+    "annotator",
+    "commentator",
+    "introduction",
+    "foreword",
+    "afterword",
+    "holder",
+];
+
+pub async fn complete_bibtex_field_values<'a>(
+    req: &'a FeatureRequest<CompletionParams>,
+    items: &mut Vec<Item<'a>>,
+) {
+    let tree = match &req.current().content {
+        DocumentContent::Bibtex(tree) => tree,
+        DocumentContent::Latex(_) => return,
+    };
+
+    let pos = req.params.text_document_position.position;
+    let path = tree.find(pos);
+    let field_index = match path.iter().rposition(|node| tree.as_field(*node).is_some()) {
+        Some(field_index) => field_index,
+        None => return,
+    };
+
+    let field = tree.as_field(path[field_index]).unwrap();
+    let is_value = field
+        .assign
+        .as_ref()
+        .map_or(false, |assign| assign.end() <= pos)
+        && field
+            .comma
+            .as_ref()
+            .map_or(true, |comma| pos <= comma.start());
+    if !is_value {
+        return;
+    }
+
+    let mut content = None;
+    let mut range = Range::new(pos, pos);
+    for node in &path[field_index + 1..] {
+        match &tree.graph[*node] {
+            bibtex::Node::QuotedContent(bibtex::QuotedContent { left, right, .. })
+            | bibtex::Node::BracedContent(bibtex::BracedContent { left, right, .. }) => {
+                if pos < left.end() || right.as_ref().map_or(false, |right| pos > right.start()) {
+                    return;
+                }
+                content = content.or(Some(left.end()));
+            }
+            bibtex::Node::Word(word) => range = word.range(),
+            bibtex::Node::Command(_) => return,
+            bibtex::Node::Concat(_) => (),
+            bibtex::Node::Root(_)
+            | bibtex::Node::Comment(_)
+            | bibtex::Node::Preamble(_)
+            | bibtex::Node::String(_)
+            | bibtex::Node::Entry(_)
+            | bibtex::Node::Field(_) => return,
+        }
+    }
+
+    let field_name = field.name.text().to_lowercase();
+    if let Some(doc) = LANGUAGE_DATA
+        .fields
+        .iter()
+        .find(|doc| doc.name == field_name)
+    {
+        for value in &doc.values {
+            let data = ItemData::FieldValue {
+                name: value,
+                field: &doc.name,
+            };
+            items.push(Item::new(range, data));
+        }
+    }
+
+    if KEY_FIELDS.contains(&field_name.as_str()) {
+        let entry = path[..field_index]
+            .iter()
+            .rev()
+            .find_map(|node| tree.as_entry(*node))
+            .and_then(|entry| entry.key.as_ref())
+            .map(bibtex::Token::text);
+        make_key_items(req, items, range, entry);
+    }
+
+    match content {
+        Some(start) => {
+            if NAME_FIELDS.contains(&field_name.as_str()) {
+                let range = name_range(&req.current().text, start, range);
+                make_name_items(req, items, range, path[field_index]);
+            }
+        }
+        None => make_string_items(req, items, range),
+    }
+}
+
+fn make_key_items<'a>(
+    req: &'a FeatureRequest<CompletionParams>,
+    items: &mut Vec<Item<'a>>,
+    range: Range,
+    current_key: Option<&str>,
+) {
+    for doc in req.related() {
+        if let DocumentContent::Bibtex(tree) = &doc.content {
+            for entry in tree
+                .children(tree.root)
+                .filter_map(|node| tree.as_entry(node))
+                .filter(|entry| !entry.is_comment())
+            {
+                let key = match &entry.key {
+                    Some(key) if Some(key.text()) != current_key => key.text(),
+                    _ => continue,
+                };
+
+                let ty = LANGUAGE_DATA
+                    .find_entry_type(&entry.ty.text()[1..])
+                    .map_or(BibtexEntryTypeCategory::Misc, |ty| ty.category);
+
+                let data = ItemData::Citation {
+                    uri: &doc.uri,
+                    key,
+                    label: key.into(),
+                    text: key.into(),
+                    ty: Structure::Entry(ty),
+                    import: None,
+                };
+                items.push(Item::new(range, data));
+            }
+        }
+    }
+}
+
+fn make_string_items<'a>(
+    req: &'a FeatureRequest<CompletionParams>,
+    items: &mut Vec<Item<'a>>,
+    range: Range,
+) {
+    for doc in req.related() {
+        if let DocumentContent::Bibtex(tree) = &doc.content {
+            for node in tree.children(tree.root) {
+                if let Some(name) = tree.as_string(node).and_then(|string| string.name.as_ref()) {
+                    let data = ItemData::BibtexString {
+                        name: name.text(),
+                        value: string_value(doc, tree, node),
+                    };
+                    items.push(Item::new(range, data));
+                }
+            }
+        }
+    }
+
+    for &(name, value) in MONTHS {
+        let data = ItemData::BibtexString {
+            name,
+            value: value.into(),
+        };
+        items.push(Item::new(range, data));
+    }
+}
+
+fn string_value(doc: &Document, tree: &bibtex::Tree, string: NodeIndex) -> String {
+    tree.children(string)
+        .next()
+        .map(|value| CharStream::extract(&doc.text, tree.graph[value].range()))
+        .unwrap_or_default()
+}
+
+fn make_name_items<'a>(
+    req: &'a FeatureRequest<CompletionParams>,
+    items: &mut Vec<Item<'a>>,
+    range: Range,
+    current_field: NodeIndex,
+) {
+    let mut names = HashSet::new();
+    for doc in req.related() {
+        if let DocumentContent::Bibtex(tree) = &doc.content {
+            for field_node in tree
+                .children(tree.root)
+                .filter(|node| tree.as_entry(*node).is_some())
+                .flat_map(|entry| tree.children(entry))
+            {
+                let is_current = doc.uri == req.current().uri && field_node == current_field;
+                let is_name_field = tree.as_field(field_node).map_or(false, |field| {
+                    NAME_FIELDS.contains(&field.name.text().to_lowercase().as_str())
+                });
+                if !is_name_field || is_current {
+                    continue;
+                }
+
+                if let Some(value) = tree.children(field_node).next() {
+                    let text = CharStream::extract(&doc.text, tree.graph[value].range());
+                    let text = text.trim_matches(|c| c == '{' || c == '}' || c == '"');
+                    for name in split_names(text) {
+                        if !name.is_empty() && name != "others" {
+                            names.insert(name);
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    for name in names {
+        items.push(Item::new(range, ItemData::AuthorName { name }));
+    }
+}
+
+/// Returns the range of the name at the cursor,
+/// which starts after the preceding `and` or at the beginning of the field value.
+fn name_range(text: &str, content_start: Position, word_range: Range) -> Range {
+    let prefix = CharStream::extract(text, Range::new(content_start, word_range.start));
+    let offset = last_name_offset(&prefix);
+
+    let skipped = &prefix[..offset];
+    let whitespace = prefix[offset..].len() - prefix[offset..].trim_start().len();
+    let mut start = content_start;
+    for c in skipped
+        .chars()
+        .chain(prefix[offset..offset + whitespace].chars())
+    {
+        if c == '\n' {
+            start.line += 1;
+            start.character = 0;
+        } else {
+            start.character += 1;
+        }
+    }
+    Range::new(start, word_range.end)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::feature::FeatureTester;
+    use indoc::indoc;
+
+    #[tokio::test]
+    async fn empty_latex_document() {
+        let req = FeatureTester::new()
+            .file("main.tex", "")
+            .main("main.tex")
+            .position(0, 0)
+            .test_completion_request()
+            .await;
+        let mut actual_items = Vec::new();
+
+        complete_bibtex_field_values(&req, &mut actual_items).await;
+
+        assert!(actual_items.is_empty());
+    }
+
+    #[tokio::test]
+    async fn field_name() {
+        let req = FeatureTester::new()
+            .file("main.bib", "@article{foo, month = jan}")
+            .main("main.bib")
+            .position(0, 16)
+            .test_completion_request()
+            .await;
+        let mut actual_items = Vec::new();
+
+        complete_bibtex_field_values(&req, &mut actual_items).await;
+
+        assert!(actual_items.is_empty());
+    }
+
+    #[tokio::test]
+    async fn string() {
+        let req = FeatureTester::new()
+            .file(
+                "main.bib",
+                indoc!(
+                    r#"
+                        @string{acm = {ACM}}
+                        @article{foo, publisher = ac, month = jan}
+                    "#
+                ),
+            )
+            .main("main.bib")
+            .position(1, 28)
+            .test_completion_request()
+            .await;
+        let mut actual_items = Vec::new();
+
+        complete_bibtex_field_values(&req, &mut actual_items).await;
+
+        assert!(actual_items.iter().any(|item| item.data.label() == "acm"));
+        assert!(actual_items.iter().any(|item| item.data.label() == "dec"));
+        assert_eq!(actual_items[0].range, Range::new_simple(1, 26, 1, 28));
+    }
+
+    #[tokio::test]
+    async fn string_inside_braces() {
+        let req = FeatureTester::new()
+            .file(
+                "main.bib",
+                indoc!(
+                    r#"
+                        @string{acm = {ACM}}
+                        @article{foo, publisher = {ac}}
+                    "#
+                ),
+            )
+            .main("main.bib")
+            .position(1, 29)
+            .test_completion_request()
+            .await;
+        let mut actual_items = Vec::new();
+
+        complete_bibtex_field_values(&req, &mut actual_items).await;
+
+        assert!(actual_items.is_empty());
+    }
+
+    #[tokio::test]
+    async fn crossref() {
+        let req = FeatureTester::new()
+            .file(
+                "main.bib",
+                indoc!(
+                    r#"
+                        @book{bar, title = {Bar}}
+                        @inbook{foo, crossref = {b}}
+                    "#
+                ),
+            )
+            .main("main.bib")
+            .position(1, 26)
+            .test_completion_request()
+            .await;
+        let mut actual_items = Vec::new();
+
+        complete_bibtex_field_values(&req, &mut actual_items).await;
+
+        assert!(actual_items.iter().any(|item| item.data.label() == "bar"));
+        assert!(actual_items.iter().all(|item| item.data.label() != "foo"));
+    }
+
+    #[tokio::test]
+    async fn known_values() {
+        let req = FeatureTester::new()
+            .file("main.bib", "@article{foo, pubstate = {}}")
+            .main("main.bib")
+            .position(0, 26)
+            .test_completion_request()
+            .await;
+        let mut actual_items = Vec::new();
+
+        complete_bibtex_field_values(&req, &mut actual_items).await;
+
+        assert!(actual_items
+            .iter()
+            .any(|item| item.data.label() == "forthcoming"));
+    }
+
+    #[tokio::test]
+    async fn author() {
+        let req = FeatureTester::new()
+            .file(
+                "main.bib",
+                indoc!(
+                    r#"
+                        @article{foo, author = {Doe, John and Smith, Jane}}
+                        @article{bar, author = {Miller, Max and Sm}}
+                    "#
+                ),
+            )
+            .main("main.bib")
+            .position(1, 41)
+            .test_completion_request()
+            .await;
+        let mut actual_items = Vec::new();
+
+        complete_bibtex_field_values(&req, &mut actual_items).await;
+
+        assert!(actual_items
+            .iter()
+            .any(|item| item.data.label() == "Smith, Jane"));
+        assert!(actual_items.iter().all(|item| item.data.label() != "Sm"));
+        assert_eq!(actual_items[0].range, Range::new_simple(1, 40, 1, 42));
+    }
+}
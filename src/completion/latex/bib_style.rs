@@ -0,0 +1,230 @@
+use super::combinators::{self, Parameter};
+use crate::{
+    completion::types::{Item, ItemData},
+    feature::FeatureRequest,
+    protocol::{CompletionParams, Position, Range},
+    syntax::{latex, CharStream, LatexIncludeKind, LANGUAGE_DATA},
+    tex::Resolver,
+};
+use std::{ffi::OsStr, path::Path};
+
+/// The biblatex options that select a style and the file extensions of the matching styles.
+const BIBLATEX_STYLE_KEYS: &[(&str, &[&str])] = &[
+    ("style", &["bbx", "cbx"]),
+    ("bibstyle", &["bbx"]),
+    ("citestyle", &["cbx"]),
+];
+
+pub async fn complete_latex_bibliography_styles<'a>(
+    req: &'a FeatureRequest<CompletionParams>,
+    items: &mut Vec<Item<'a>>,
+) {
+    let parameters = std::iter::once(Parameter {
+        name: "bibliographystyle",
+        index: 0,
+    });
+
+    combinators::argument(req, parameters, |ctx| async move {
+        let resolver = req.distro.resolver().await;
+        for data in find_styles(&resolver, &["bst"]) {
+            items.push(Item::new(ctx.range, data));
+        }
+    })
+    .await;
+}
+
+pub async fn complete_latex_biblatex_styles<'a>(
+    req: &'a FeatureRequest<CompletionParams>,
+    items: &mut Vec<Item<'a>>,
+) {
+    let parameters = LANGUAGE_DATA
+        .include_commands
+        .iter()
+        .filter(|cmd| cmd.kind == LatexIncludeKind::Package)
+        .map(|cmd| Parameter {
+            name: &cmd.name[1..],
+            index: 0,
+        });
+
+    combinators::options(req, parameters, |ctx| async move {
+        let table = req.current().content.as_latex().unwrap();
+        let name = table.as_command(ctx.node).unwrap().name.text();
+        let index = LANGUAGE_DATA
+            .include_commands
+            .iter()
+            .find(|cmd| cmd.kind == LatexIncludeKind::Package && cmd.name == name)
+            .map_or(0, |cmd| cmd.index);
+
+        if !table
+            .extract_comma_separated_words(ctx.node, latex::GroupKind::Group, index)
+            .unwrap_or_default()
+            .into_iter()
+            .any(|package| package.text() == "biblatex")
+        {
+            return;
+        }
+
+        let pos = req.params.text_document_position.position;
+        let word: Vec<char> = CharStream::extract(&req.current().text, ctx.range)
+            .chars()
+            .collect();
+        let offset = (pos.character - ctx.range.start.character) as usize;
+        let eq = match word.iter().position(|c| *c == '=') {
+            Some(eq) if eq < offset => eq,
+            _ => return,
+        };
+
+        let key: String = word[..eq].iter().collect();
+        let extensions = match BIBLATEX_STYLE_KEYS
+            .iter()
+            .find(|(name, _)| *name == key.trim())
+        {
+            Some((_, extensions)) => extensions,
+            None => return,
+        };
+
+        let start = Position::new(
+            ctx.range.start.line,
+            ctx.range.start.character + eq as u64 + 1,
+        );
+        let range = Range::new(start, ctx.range.end);
+        let resolver = req.distro.resolver().await;
+        for data in find_styles(&resolver, extensions) {
+            items.push(Item::new(range, data));
+        }
+    })
+    .await;
+}
+
+/// The directories in which packages like `biblatex` group their styles.
+/// They are skipped when determining the package of a style.
+const STYLE_DIRECTORIES: &[&str] = &["bbx", "cbx"];
+
+/// Finds the installed styles with one of the given extensions
+/// together with the name of the package that ships them.
+fn find_styles(resolver: &Resolver, extensions: &[&str]) -> Vec<ItemData<'static>> {
+    let mut styles: Vec<_> = resolver
+        .files_by_name
+        .iter()
+        .filter_map(|(file_name, path)| {
+            let file_name = Path::new(file_name);
+            let extension = file_name.extension().and_then(OsStr::to_str)?;
+            if !extensions.contains(&extension) {
+                return None;
+            }
+
+            let name = file_name.file_stem().and_then(OsStr::to_str)?.to_owned();
+            let package = path
+                .ancestors()
+                .skip(1)
+                .filter_map(|dir| dir.file_name().and_then(OsStr::to_str))
+                .find(|dir| !STYLE_DIRECTORIES.contains(dir))
+                .map(ToOwned::to_owned);
+            Some((name, package))
+        })
+        .collect();
+
+    styles.sort();
+    styles.dedup_by(|a, b| a.0 == b.0);
+    styles
+        .into_iter()
+        .map(|(name, package)| ItemData::BibliographyStyle { name, package })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{feature::FeatureTester, protocol::RangeExt};
+
+    #[tokio::test]
+    async fn empty_latex_document() {
+        let req = FeatureTester::new()
+            .file("main.tex", "")
+            .main("main.tex")
+            .position(0, 0)
+            .test_completion_request()
+            .await;
+        let mut actual_items = Vec::new();
+
+        complete_latex_bibliography_styles(&req, &mut actual_items).await;
+
+        assert!(actual_items.is_empty());
+    }
+
+    #[tokio::test]
+    async fn bibliography_style() {
+        let req = FeatureTester::new()
+            .file("main.tex", r#"\bibliographystyle{}"#)
+            .main("main.tex")
+            .position(0, 19)
+            .distro_file("/texmf/bibtex/bst/natbib/plainnat.bst")
+            .distro_file("/texmf/tex/latex/biblatex/bbx/authoryear.bbx")
+            .test_completion_request()
+            .await;
+        let mut actual_items = Vec::new();
+
+        complete_latex_bibliography_styles(&req, &mut actual_items).await;
+
+        assert_eq!(actual_items.len(), 1);
+        match &actual_items[0].data {
+            ItemData::BibliographyStyle { name, package } => {
+                assert_eq!(name, "plainnat");
+                assert_eq!(package.as_deref(), Some("natbib"));
+            }
+            _ => panic!("expected a bibliography style"),
+        }
+        assert_eq!(actual_items[0].range, Range::new_simple(0, 19, 0, 19));
+    }
+
+    #[tokio::test]
+    async fn biblatex_style() {
+        let req = FeatureTester::new()
+            .file(
+                "main.tex",
+                r#"\usepackage[backend=biber,citestyle=auth]{biblatex}"#,
+            )
+            .main("main.tex")
+            .position(0, 40)
+            .distro_file("/texmf/tex/latex/biblatex/bbx/authoryear.bbx")
+            .distro_file("/texmf/tex/latex/biblatex/cbx/authoryear.cbx")
+            .distro_file("/texmf/tex/latex/biblatex/cbx/authortitle.cbx")
+            .distro_file("/texmf/tex/latex/biblatex/bbx/reading.bbx")
+            .test_completion_request()
+            .await;
+        let mut actual_items = Vec::new();
+
+        complete_latex_biblatex_styles(&req, &mut actual_items).await;
+
+        let actual_labels: Vec<_> = actual_items
+            .iter()
+            .map(|item| item.data.label().to_owned())
+            .collect();
+        assert_eq!(actual_labels, vec!["authortitle", "authoryear"]);
+        assert_eq!(actual_items[0].range, Range::new_simple(0, 36, 0, 40));
+        for item in &actual_items {
+            match &item.data {
+                ItemData::BibliographyStyle { package, .. } => {
+                    assert_eq!(package.as_deref(), Some("biblatex"));
+                }
+                _ => panic!("expected a bibliography style"),
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn biblatex_style_other_package() {
+        let req = FeatureTester::new()
+            .file("main.tex", r#"\usepackage[style=]{foo}"#)
+            .main("main.tex")
+            .position(0, 18)
+            .distro_file("/texmf/tex/latex/biblatex/bbx/authoryear.bbx")
+            .test_completion_request()
+            .await;
+        let mut actual_items = Vec::new();
+
+        complete_latex_biblatex_styles(&req, &mut actual_items).await;
+
+        assert!(actual_items.is_empty());
+    }
+}
@@ -1,15 +1,26 @@
 use super::combinators::{self, ArgumentContext, Parameter};
 use crate::{
+    bibliography::{bib_item_text, split_names},
     completion::types::{Item, ItemData},
     feature::FeatureRequest,
-    protocol::{BibtexFormattingOptions, CompletionParams},
-    syntax::{bibtex, BibtexEntryTypeCategory, Structure, LANGUAGE_DATA},
+    protocol::{CompletionParams, Uri},
+    syntax::{
+        bibtex, latex, BibtexEntryTypeCategory, CharStream, Structure, SyntaxNode, LANGUAGE_DATA,
+    },
     workspace::{Document, DocumentContent},
 };
 use once_cell::sync::Lazy;
 use petgraph::graph::NodeIndex;
 use regex::Regex;
 
+const AUTHOR_FIELDS: &[&str] = &["author", "editor"];
+
+const YEAR_FIELDS: &[&str] = &["year", "date"];
+
+const TITLE_FIELDS: &[&str] = &["title"];
+
+const JOURNAL_FIELDS: &[&str] = &["journal", "journaltitle", "booktitle"];
+
 pub async fn complete_latex_citations<'a>(
     req: &'a FeatureRequest<CompletionParams>,
     items: &mut Vec<Item<'a>>,
@@ -21,9 +32,32 @@ pub async fn complete_latex_citations<'a>(
 
     combinators::argument(req, parameters, |ctx| async move {
         for doc in req.related() {
+            match &doc.content {
+                DocumentContent::Latex(table) => {
+                    for bib_item in &table.bib_items {
+                        items.push(make_bib_item(ctx, doc, table, *bib_item));
+                    }
+                }
+                DocumentContent::Bibtex(tree) => {
+                    for entry_node in tree.children(tree.root) {
+                        if let Some(item) = make_item(ctx, doc, tree, entry_node, None) {
+                            items.push(item);
+                        }
+                    }
+                }
+            }
+        }
+
+        let target = req
+            .related()
+            .iter()
+            .find(|doc| doc.content.as_bibtex().is_some())
+            .map(|doc| &doc.uri);
+
+        for doc in req.libraries() {
             if let DocumentContent::Bibtex(tree) = &doc.content {
                 for entry_node in tree.children(tree.root) {
-                    if let Some(item) = make_item(ctx, doc, tree, entry_node) {
+                    if let Some(item) = make_item(ctx, doc, tree, entry_node, target) {
                         items.push(item);
                     }
                 }
@@ -38,6 +72,7 @@ fn make_item<'a>(
     doc: &'a Document,
     tree: &'a bibtex::Tree,
     entry_node: NodeIndex,
+    import: Option<&'a Uri>,
 ) -> Option<Item<'a>> {
     let entry = tree.as_entry(entry_node)?;
     if entry.is_comment() {
@@ -45,27 +80,29 @@ fn make_item<'a>(
     }
 
     let key = entry.key.as_ref()?.text();
-    let options = BibtexFormattingOptions::default();
-    let params = bibtex::FormattingParams {
-        insert_spaces: true,
-        tab_size: 4,
-        options: &options,
+    let author = field_text(doc, tree, entry_node, AUTHOR_FIELDS);
+    let year = field_text(doc, tree, entry_node, YEAR_FIELDS)
+        .map(|date| date.chars().take(4).collect::<String>());
+    let title = field_text(doc, tree, entry_node, TITLE_FIELDS);
+    let journal = field_text(doc, tree, entry_node, JOURNAL_FIELDS);
+
+    let mut label = Vec::new();
+    label.extend(author.as_deref().map(short_authors));
+    label.extend(year.as_ref().map(|year| format!("({})", year)));
+    label.extend(title.clone());
+    let label = if label.is_empty() {
+        key.to_owned()
+    } else {
+        label.join(" ")
     };
-    let entry_code = bibtex::format(tree, entry_node, params);
-    let text = format!(
-        "{} {}",
-        &key,
-        WHITESPACE_REGEX
-            .replace_all(
-                &entry_code
-                    .replace('{', "")
-                    .replace('}', "")
-                    .replace(',', " ")
-                    .replace('=', " "),
-                " ",
-            )
-            .trim()
-    );
+
+    let text = std::iter::once(key.to_owned())
+        .chain(author)
+        .chain(year)
+        .chain(title)
+        .chain(journal)
+        .collect::<Vec<_>>()
+        .join(" ");
 
     let ty = LANGUAGE_DATA
         .find_entry_type(&entry.ty.text()[1..])
@@ -77,13 +114,79 @@ fn make_item<'a>(
         ItemData::Citation {
             uri: &doc.uri,
             key,
+            label,
             text,
             ty,
+            import,
         },
     );
     Some(item)
 }
 
+fn make_bib_item<'a>(
+    ctx: ArgumentContext,
+    doc: &'a Document,
+    table: &'a latex::SymbolTable,
+    bib_item: latex::BibItem,
+) -> Item<'a> {
+    let key = bib_item.key(&table.tree).text();
+    let text = format!("{} {}", key, bib_item_text(&doc.text, bib_item));
+    Item::new(
+        ctx.range,
+        ItemData::Citation {
+            uri: &doc.uri,
+            key,
+            label: key.to_owned(),
+            text,
+            ty: Structure::Entry(BibtexEntryTypeCategory::Misc),
+            import: None,
+        },
+    )
+}
+
+/// Returns the plain text of the first field with one of the given names,
+/// without braces, quotes and redundant whitespace.
+fn field_text(
+    doc: &Document,
+    tree: &bibtex::Tree,
+    entry_node: NodeIndex,
+    names: &[&str],
+) -> Option<String> {
+    let text = names
+        .iter()
+        .filter_map(|name| tree.field_by_name(entry_node, name))
+        .filter_map(|field| tree.children(field).next())
+        .map(|value| CharStream::extract(&doc.text, tree.graph[value].range()))
+        .next()?;
+
+    let text = text.replace(|c: char| c == '{' || c == '}' || c == '"', "");
+    let text = WHITESPACE_REGEX.replace_all(text.trim(), " ").into_owned();
+    if text.is_empty() {
+        None
+    } else {
+        Some(text)
+    }
+}
+
+/// Shortens a list of names to the last names of the first two authors.
+fn short_authors(author: &str) -> String {
+    let names: Vec<_> = split_names(author).collect();
+    let names: Vec<_> = names.iter().map(|name| last_name(name)).collect();
+    match names.as_slice() {
+        [first] => first.to_string(),
+        [first, second] if *second != "others" => format!("{} and {}", first, second),
+        [first, ..] => format!("{} et al.", first),
+        [] => String::new(),
+    }
+}
+
+fn last_name(name: &str) -> &str {
+    match name.find(',') {
+        Some(index) => name[..index].trim(),
+        None => name.split_whitespace().last().unwrap_or(name),
+    }
+}
+
 static WHITESPACE_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new("\\s+").unwrap());
 
 #[cfg(test)]
@@ -258,4 +361,158 @@ mod tests {
 
         assert!(actual_items.is_empty());
     }
+
+    #[tokio::test]
+    async fn rich_label() {
+        let req = FeatureTester::new()
+            .file(
+                "main.tex",
+                indoc!(
+                    r#"
+                        \addbibresource{main.bib}
+                        \cite{}
+                    "#
+                ),
+            )
+            .file(
+                "main.bib",
+                indoc!(
+                    r#"
+                        @article{foo,
+                            author = {Smith, John and Doe, Jane and Roe, Richard},
+                            title = {{Graphs} and   Trees},
+                            journal = "Journal of Graphs",
+                            year = 2019,
+                        }
+                    "#
+                ),
+            )
+            .main("main.tex")
+            .position(1, 6)
+            .test_completion_request()
+            .await;
+        let mut actual_items = Vec::new();
+
+        complete_latex_citations(&req, &mut actual_items).await;
+
+        assert_eq!(actual_items.len(), 1);
+        match &actual_items[0].data {
+            ItemData::Citation {
+                key, label, text, ..
+            } => {
+                assert_eq!(*key, "foo");
+                assert_eq!(label, "Smith et al. (2019) Graphs and Trees");
+                assert_eq!(
+                    text,
+                    "foo Smith, John and Doe, Jane and Roe, Richard 2019 Graphs and Trees Journal of Graphs"
+                );
+            }
+            _ => panic!("expected a citation"),
+        }
+    }
+
+    #[tokio::test]
+    async fn short_label() {
+        let req = FeatureTester::new()
+            .file(
+                "main.tex",
+                indoc!(
+                    r#"
+                        \addbibresource{main.bib}
+                        \cite{bar}
+                        \cite{foo, bar}
+                        \cite{}
+                    "#
+                ),
+            )
+            .file(
+                "main.bib",
+                "@article{foo, author = {John Smith}}\n@article{bar,}",
+            )
+            .main("main.tex")
+            .position(3, 6)
+            .test_completion_request()
+            .await;
+        let mut actual_items = Vec::new();
+
+        complete_latex_citations(&req, &mut actual_items).await;
+
+        let actual_labels: Vec<_> = actual_items
+            .iter()
+            .map(|item| match &item.data {
+                ItemData::Citation { key, label, .. } => (*key, label.as_str()),
+                _ => panic!("expected a citation"),
+            })
+            .collect();
+        assert_eq!(actual_labels, vec![("foo", "Smith"), ("bar", "bar")]);
+    }
+
+    #[tokio::test]
+    async fn library() {
+        let req = FeatureTester::new()
+            .file(
+                "main.tex",
+                indoc!(
+                    r#"
+                        \addbibresource{main.bib}
+                        \cite{}
+                    "#
+                ),
+            )
+            .file("main.bib", "@article{bar,}")
+            .file("library.bib", "@article{foo,}")
+            .main("main.tex")
+            .library("library.bib")
+            .position(1, 6)
+            .test_completion_request()
+            .await;
+        let mut actual_items = Vec::new();
+
+        complete_latex_citations(&req, &mut actual_items).await;
+
+        let actual_imports: Vec<_> = actual_items
+            .iter()
+            .map(|item| match &item.data {
+                ItemData::Citation { key, import, .. } => (*key, import.cloned()),
+                _ => panic!("expected a citation"),
+            })
+            .collect();
+        assert_eq!(
+            actual_imports,
+            vec![("bar", None), ("foo", Some(FeatureTester::uri("main.bib"))),]
+        );
+    }
+
+    #[tokio::test]
+    async fn bib_item() {
+        let req = FeatureTester::new()
+            .file(
+                "main.tex",
+                indoc!(
+                    r#"
+                        \cite{}
+                        \begin{thebibliography}{9}
+                        \bibitem{foo} John Smith.
+                        \newblock Graphs and Trees.
+                        \end{thebibliography}
+                    "#
+                ),
+            )
+            .main("main.tex")
+            .position(0, 6)
+            .test_completion_request()
+            .await;
+        let mut actual_items = Vec::new();
+
+        complete_latex_citations(&req, &mut actual_items).await;
+
+        assert_eq!(actual_items.len(), 1);
+        match &actual_items[0].data {
+            ItemData::Citation { key, text, .. } => {
+                assert_eq!(*key, "foo");
+                assert_eq!(text, "foo John Smith. Graphs and Trees.");
+            }
+            _ => panic!("expected a citation"),
+        }
+    }
 }
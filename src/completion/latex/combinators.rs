@@ -78,6 +78,55 @@ pub async fn argument<'a, I, E, F>(
     }
 }
 
+/// Like `argument` but for optional arguments in brackets like `\usepackage[...]{...}`.
+pub async fn options<'a, I, E, F>(
+    req: &'a FeatureRequest<CompletionParams>,
+    mut parameters: I,
+    execute: E,
+) where
+    I: Iterator<Item = Parameter<'a>>,
+    E: FnOnce(ArgumentContext<'a>) -> F,
+    F: Future<Output = ()>,
+{
+    if let DocumentContent::Latex(table) = &req.current().content {
+        let pos = req.params.text_document_position.position;
+        if let Some(node) = find_command(&table, pos) {
+            let cmd = table.as_command(node).unwrap();
+            for parameter in parameters
+                .by_ref()
+                .filter(|param| param.name == &cmd.name.text()[1..])
+            {
+                if let Some(options_node) =
+                    table.extract_group(node, latex::GroupKind::Options, parameter.index)
+                {
+                    let options = table.as_group(options_node).unwrap();
+                    if (options.right.is_some() && !options.range().contains_exclusive(pos))
+                        || !options.range().contains(pos)
+                    {
+                        continue;
+                    }
+
+                    let range = table
+                        .children(options_node)
+                        .filter_map(|child| table.as_text(child))
+                        .flat_map(|text| text.words.iter())
+                        .map(|word| word.range())
+                        .find(|range| range.contains(pos))
+                        .unwrap_or_else(|| Range::new(pos, pos));
+
+                    let context = ArgumentContext {
+                        parameter,
+                        node,
+                        range,
+                    };
+                    execute(context).await;
+                    return;
+                }
+            }
+        }
+    }
+}
+
 pub async fn argument_word<'a, I, E, F>(
     req: &'a FeatureRequest<CompletionParams>,
     mut parameters: I,
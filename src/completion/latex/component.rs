@@ -1,8 +1,12 @@
 use super::combinators;
 use crate::{
-    completion::types::{Item, ItemData},
+    completion::{
+        types::{Item, ItemData},
+        util::is_math_mode,
+    },
     feature::FeatureRequest,
     protocol::CompletionParams,
+    syntax::{LatexCommandMode, LANGUAGE_DATA},
 };
 
 pub async fn complete_latex_component_commands<'a>(
@@ -13,9 +17,19 @@ pub async fn complete_latex_component_commands<'a>(
         let table = req.current().content.as_latex().unwrap();
         let cmd = table.as_command(cmd_node).unwrap();
         let range = cmd.short_name_range();
+        let mode = if is_math_mode(req) {
+            LatexCommandMode::Math
+        } else {
+            LatexCommandMode::Text
+        };
 
         for comp in req.view.components() {
             for cmd in &comp.commands {
+                let required_mode = LANGUAGE_DATA.command_mode(&format!("\\{}", cmd.name));
+                if required_mode.map_or(false, |required_mode| required_mode != mode) {
+                    continue;
+                }
+
                 items.push(Item::new(
                     range,
                     ItemData::ComponentCommand {
@@ -23,6 +37,9 @@ pub async fn complete_latex_component_commands<'a>(
                         image: cmd.image.as_deref(),
                         glyph: cmd.glyph.as_deref(),
                         file_names: &comp.file_names,
+                        // The parameters belong to the mandatory arguments in braces
+                        // like in the argument completion, so their count is the arity.
+                        arg_count: cmd.parameters.len(),
                     },
                 ));
             }
@@ -55,6 +72,7 @@ pub async fn complete_latex_component_environments<'a>(
 mod tests {
     use super::*;
     use crate::{
+        components::COMPONENT_DATABASE,
         feature::FeatureTester,
         protocol::{Range, RangeExt},
     };
@@ -166,6 +184,75 @@ mod tests {
         assert!(actual_items.is_empty());
     }
 
+    #[tokio::test]
+    async fn command_text_mode() {
+        let req = FeatureTester::new()
+            .file("main.tex", r#"\al"#)
+            .main("main.tex")
+            .position(0, 3)
+            .test_completion_request()
+            .await;
+        let mut actual_items = Vec::new();
+
+        complete_latex_component_commands(&req, &mut actual_items).await;
+
+        assert!(actual_items.iter().all(|item| item.data.label() != "alpha"));
+    }
+
+    #[tokio::test]
+    async fn command_math_mode() {
+        let req = FeatureTester::new()
+            .file("main.tex", r#"$\al$"#)
+            .main("main.tex")
+            .position(0, 4)
+            .test_completion_request()
+            .await;
+        let mut actual_items = Vec::new();
+
+        complete_latex_component_commands(&req, &mut actual_items).await;
+
+        assert!(actual_items.iter().any(|item| item.data.label() == "alpha"));
+        assert!(actual_items
+            .iter()
+            .all(|item| item.data.label() != "section"));
+    }
+
+    #[tokio::test]
+    async fn command_arg_count() {
+        let req = FeatureTester::new()
+            .file("main.tex", r#"$\fr$"#)
+            .main("main.tex")
+            .position(0, 4)
+            .test_completion_request()
+            .await;
+        let mut actual_items = Vec::new();
+
+        complete_latex_component_commands(&req, &mut actual_items).await;
+
+        let arg_count = |label| {
+            actual_items
+                .iter()
+                .find_map(|item| match item.data {
+                    ItemData::ComponentCommand {
+                        name, arg_count, ..
+                    } if name == label => Some(arg_count),
+                    _ => None,
+                })
+                .unwrap()
+        };
+        let parameter_count = |label| {
+            COMPONENT_DATABASE
+                .kernel()
+                .commands
+                .iter()
+                .find(|cmd| cmd.name == label)
+                .map(|cmd| cmd.parameters.len())
+                .unwrap()
+        };
+        assert_eq!(arg_count("frac"), parameter_count("frac"));
+        assert_eq!(arg_count("alpha"), 0);
+    }
+
     #[tokio::test]
     async fn command_package() {
         let req = FeatureTester::new()
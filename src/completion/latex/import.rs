@@ -4,7 +4,7 @@ use crate::{
     components::COMPONENT_DATABASE,
     feature::FeatureRequest,
     protocol::CompletionParams,
-    syntax::{LatexIncludeKind, LANGUAGE_DATA},
+    syntax::{latex, LatexIncludeKind, LANGUAGE_DATA},
 };
 use std::{borrow::Cow, collections::HashSet};
 
@@ -81,10 +81,86 @@ async fn complete_latex_imports<'a, F>(
     .await;
 }
 
+pub async fn complete_latex_class_options<'a>(
+    req: &'a FeatureRequest<CompletionParams>,
+    items: &mut Vec<Item<'a>>,
+) {
+    complete_latex_import_options(req, items, LatexIncludeKind::Class, |name, file_name| {
+        ItemData::ClassOption { name, file_name }
+    })
+    .await;
+}
+
+pub async fn complete_latex_package_options<'a>(
+    req: &'a FeatureRequest<CompletionParams>,
+    items: &mut Vec<Item<'a>>,
+) {
+    complete_latex_import_options(req, items, LatexIncludeKind::Package, |name, file_name| {
+        ItemData::PackageOption { name, file_name }
+    })
+    .await;
+}
+
+async fn complete_latex_import_options<'a, F>(
+    req: &'a FeatureRequest<CompletionParams>,
+    items: &mut Vec<Item<'a>>,
+    kind: LatexIncludeKind,
+    mut factory: F,
+) where
+    F: FnMut(&'a str, &'a str) -> ItemData<'a>,
+{
+    let extension = if kind == LatexIncludeKind::Package {
+        "sty"
+    } else {
+        "cls"
+    };
+
+    let parameters = LANGUAGE_DATA
+        .include_commands
+        .iter()
+        .filter(|cmd| cmd.kind == kind)
+        .map(|cmd| Parameter {
+            name: &cmd.name[1..],
+            index: 0,
+        });
+
+    combinators::options(req, parameters, |ctx| async move {
+        let table = req.current().content.as_latex().unwrap();
+        let name = table.as_command(ctx.node).unwrap().name.text();
+        let index = LANGUAGE_DATA
+            .include_commands
+            .iter()
+            .find(|cmd| cmd.kind == kind && cmd.name == name)
+            .map_or(0, |cmd| cmd.index);
+
+        for path in table
+            .extract_comma_separated_words(ctx.node, latex::GroupKind::Group, index)
+            .unwrap_or_default()
+        {
+            let file_name = format!("{}.{}", path.text(), extension);
+            if let Some(component) = COMPONENT_DATABASE.find(&file_name) {
+                let file_name = component
+                    .file_names
+                    .iter()
+                    .find(|name| **name == file_name)
+                    .unwrap();
+
+                for option in &component.options {
+                    items.push(Item::new(ctx.range, factory(option, file_name)));
+                }
+            }
+        }
+    })
+    .await;
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::feature::FeatureTester;
+    use crate::{
+        feature::FeatureTester,
+        protocol::{Range, RangeExt},
+    };
 
     #[tokio::test]
     async fn empty_latex_document_class() {
@@ -185,4 +261,57 @@ mod tests {
             .iter()
             .any(|item| item.data.label() == "amsmath"));
     }
+
+    #[tokio::test]
+    async fn class_option() {
+        let req = FeatureTester::new()
+            .file("main.tex", r#"\documentclass[]{article}"#)
+            .main("main.tex")
+            .position(0, 15)
+            .test_completion_request()
+            .await;
+        let mut actual_items = Vec::new();
+
+        complete_latex_class_options(&req, &mut actual_items).await;
+
+        assert!(actual_items
+            .iter()
+            .any(|item| item.data.label() == "a4paper"));
+        assert!(actual_items
+            .iter()
+            .all(|item| item.data.label() != "colorlinks"));
+    }
+
+    #[tokio::test]
+    async fn package_option() {
+        let req = FeatureTester::new()
+            .file("main.tex", r#"\usepackage[col]{hyperref}"#)
+            .main("main.tex")
+            .position(0, 14)
+            .test_completion_request()
+            .await;
+        let mut actual_items = Vec::new();
+
+        complete_latex_package_options(&req, &mut actual_items).await;
+
+        assert!(actual_items
+            .iter()
+            .any(|item| item.data.label() == "colorlinks"));
+        assert_eq!(actual_items[0].range, Range::new_simple(0, 12, 0, 15));
+    }
+
+    #[tokio::test]
+    async fn package_option_outside() {
+        let req = FeatureTester::new()
+            .file("main.tex", r#"\usepackage[]{hyperref}"#)
+            .main("main.tex")
+            .position(0, 16)
+            .test_completion_request()
+            .await;
+        let mut actual_items = Vec::new();
+
+        complete_latex_package_options(&req, &mut actual_items).await;
+
+        assert!(actual_items.is_empty());
+    }
 }
@@ -3,7 +3,8 @@ use crate::{
     completion::types::{Item, ItemData},
     feature::FeatureRequest,
     protocol::{CompletionParams, Range, RangeExt},
-    syntax::{latex, AstNodeIndex, SyntaxNode, LANGUAGE_DATA},
+    syntax::{latex, AstNodeIndex, LatexIncludeCommand, SyntaxNode, LANGUAGE_DATA},
+    workspace::include_dirs,
 };
 use std::path::{Path, PathBuf};
 use tokio::fs;
@@ -47,40 +48,48 @@ async fn make_items<'a>(
     };
 
     let cmd = table.as_command(cmd_node)?;
-    let current_dir = current_dir(req, table, cmd_node)?;
-    let mut entries = fs::read_dir(current_dir).await.ok()?;
-    while let Some(entry) = entries.next_entry().await.ok()? {
-        let mut path = entry.path();
+    let desc = LANGUAGE_DATA
+        .include_commands
+        .iter()
+        .find(|c| cmd.name.text() == c.name)?;
 
-        let file_type = entry.file_type().await.ok()?;
-        if file_type.is_file() && is_included(&cmd, &path) {
-            let include_extension = LANGUAGE_DATA
-                .include_commands
-                .iter()
-                .find(|c| cmd.name.text() == c.name)?
-                .include_extension;
+    for dir in search_dirs(req, table, cmd_node, desc)? {
+        let mut entries = match fs::read_dir(dir).await {
+            Ok(entries) => entries,
+            Err(_) => continue,
+        };
 
-            if !include_extension {
-                remove_extension(&mut path);
+        while let Some(entry) = entries.next_entry().await.ok()? {
+            let mut path = entry.path();
+
+            let file_type = entry.file_type().await.ok()?;
+            if file_type.is_file() && is_included(&cmd, &path) {
+                if !desc.include_extension {
+                    remove_extension(&mut path);
+                }
+                let name = path.file_name().unwrap().to_string_lossy().into_owned();
+                let item = Item::new(name_range, ItemData::File { name });
+                items.push(item);
+            } else if file_type.is_dir() {
+                let name = path.file_name().unwrap().to_string_lossy().into_owned();
+                let item = Item::new(name_range, ItemData::Directory { name });
+                items.push(item);
             }
-            let name = path.file_name().unwrap().to_string_lossy().into_owned();
-            let item = Item::new(name_range, ItemData::File { name });
-            items.push(item);
-        } else if file_type.is_dir() {
-            let name = path.file_name().unwrap().to_string_lossy().into_owned();
-            let item = Item::new(name_range, ItemData::Directory { name });
-            items.push(item);
         }
     }
     Some(())
 }
 
-fn current_dir(
+/// Returns the directories that are listed for the path that has been typed so far.
+/// Besides the directory of the current document, this includes the directories
+/// in which TeX would search for the file like `\graphicspath` or `TEXINPUTS`.
+fn search_dirs(
     req: &FeatureRequest<CompletionParams>,
     table: &latex::SymbolTable,
     cmd_node: AstNodeIndex,
-) -> Option<PathBuf> {
-    let mut path = req
+    desc: &LatexIncludeCommand,
+) -> Option<Vec<PathBuf>> {
+    let current_dir = req
         .options
         .latex
         .as_ref()
@@ -94,14 +103,35 @@ fn current_dir(
             Clone::clone,
         );
 
-    path = PathBuf::from(path.to_str()?.replace('\\', "/"));
-    if let Some(include) = table.extract_word(cmd_node, latex::GroupKind::Group, 0) {
-        path.push(include.text());
-        if !include.text().ends_with('/') {
-            path.pop();
+    let mut base_dirs = vec![current_dir];
+    base_dirs.extend(
+        include_dirs(
+            &req.current().uri,
+            req.related(),
+            desc.kind,
+            &req.options,
+            &req.current_dir,
+        )
+        .into_iter()
+        .filter_map(|dir| dir.to_file_path().ok()),
+    );
+
+    let include = table.extract_word(cmd_node, latex::GroupKind::Group, desc.index);
+    let mut dirs = Vec::new();
+    for path in base_dirs {
+        let mut path = PathBuf::from(path.to_str()?.replace('\\', "/"));
+        if let Some(include) = include {
+            path.push(include.text());
+            if !include.text().ends_with('/') {
+                path.pop();
+            }
+        }
+
+        if !dirs.contains(&path) {
+            dirs.push(path);
         }
     }
-    Some(path)
+    Some(dirs)
 }
 
 fn is_included(cmd: &latex::Command, file: &Path) -> bool {
@@ -0,0 +1,237 @@
+use super::combinators::{self, ArgumentContext, Parameter};
+use crate::{
+    completion::types::{Item, ItemData},
+    feature::FeatureRequest,
+    protocol::{CompletionParams, Position, Range},
+    syntax::{latex, CharStream, LatexKeySet, LANGUAGE_DATA},
+};
+
+pub async fn complete_latex_key_values<'a>(
+    req: &'a FeatureRequest<CompletionParams>,
+    items: &mut Vec<Item<'a>>,
+) {
+    complete_internal(req, items, latex::GroupKind::Group).await;
+    complete_internal(req, items, latex::GroupKind::Options).await;
+}
+
+async fn complete_internal<'a>(
+    req: &'a FeatureRequest<CompletionParams>,
+    items: &mut Vec<Item<'a>>,
+    kind: latex::GroupKind,
+) {
+    let options = kind == latex::GroupKind::Options;
+    let parameters = LANGUAGE_DATA
+        .key_value_commands
+        .iter()
+        .filter(|cmd| cmd.options == options)
+        .map(|cmd| Parameter {
+            name: &cmd.name[1..],
+            index: cmd.index,
+        });
+
+    let execute = |ctx: ArgumentContext<'a>| async move {
+        let key_set = LANGUAGE_DATA
+            .key_value_commands
+            .iter()
+            .find(|cmd| cmd.options == options && &cmd.name[1..] == ctx.parameter.name)
+            .and_then(|cmd| LANGUAGE_DATA.key_set(&cmd.key_set))
+            .unwrap();
+
+        let pos = req.params.text_document_position.position;
+        let word: Vec<char> = CharStream::extract(&req.current().text, ctx.range)
+            .chars()
+            .collect();
+        let offset = (pos.character - ctx.range.start.character) as usize;
+        match word.iter().position(|c| *c == '=') {
+            Some(eq) if eq < offset => {
+                let key: String = word[..eq].iter().collect();
+                let value: String = word[eq + 1..offset].iter().collect();
+                let start = Position::new(
+                    ctx.range.start.line,
+                    ctx.range.start.character + eq as u64 + 1,
+                );
+                let range = Range::new(start, ctx.range.end);
+                complete_values(items, key_set, &key, &value, range);
+            }
+            eq => {
+                let end = eq.map_or(ctx.range.end, |eq| {
+                    Position::new(ctx.range.start.line, ctx.range.start.character + eq as u64)
+                });
+                let range = Range::new(ctx.range.start, end);
+                let table = req.current().content.as_latex().unwrap();
+                let existing_keys: Vec<&str> = table
+                    .extract_keys(ctx.node, kind, ctx.parameter.index)
+                    .unwrap_or_default()
+                    .into_iter()
+                    .filter(|(_, key_range)| key_range.start != range.start)
+                    .map(|(key, _)| key)
+                    .collect();
+
+                for key in &key_set.keys {
+                    if !existing_keys.contains(&key.name.as_str()) {
+                        let data = ItemData::OptionKey {
+                            name: &key.name,
+                            key_set: &key_set.name,
+                        };
+                        items.push(Item::new(range, data));
+                    }
+                }
+            }
+        }
+    };
+
+    if options {
+        combinators::options(req, parameters, execute).await;
+    } else {
+        combinators::argument(req, parameters, execute).await;
+    }
+}
+
+fn complete_values<'a>(
+    items: &mut Vec<Item<'a>>,
+    key_set: &'a LatexKeySet,
+    key: &str,
+    value: &str,
+    range: Range,
+) {
+    let key = match key_set.find(key.trim()) {
+        Some(key) => key,
+        None => return,
+    };
+
+    for value in &key.values {
+        let data = ItemData::OptionValue {
+            name: value.into(),
+            key: &key.name,
+        };
+        items.push(Item::new(range, data));
+    }
+
+    if key.is_length {
+        let number: String = value
+            .chars()
+            .take_while(|c| c.is_ascii_digit() || *c == '.' || *c == '-' || *c == '+')
+            .collect();
+
+        for unit in &LANGUAGE_DATA.length_units {
+            let name = if !number.is_empty() {
+                format!("{}{}", number, unit).into()
+            } else if unit.starts_with('\\') {
+                unit.into()
+            } else {
+                continue;
+            };
+
+            let data = ItemData::OptionValue {
+                name,
+                key: &key.name,
+            };
+            items.push(Item::new(range, data));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{feature::FeatureTester, protocol::RangeExt};
+
+    #[tokio::test]
+    async fn empty_latex_document() {
+        let req = FeatureTester::new()
+            .file("main.tex", "")
+            .main("main.tex")
+            .position(0, 0)
+            .test_completion_request()
+            .await;
+        let mut actual_items = Vec::new();
+
+        complete_latex_key_values(&req, &mut actual_items).await;
+
+        assert!(actual_items.is_empty());
+    }
+
+    #[tokio::test]
+    async fn key_options() {
+        let req = FeatureTester::new()
+            .file("main.tex", r#"\includegraphics[clip, wid]{foo}"#)
+            .main("main.tex")
+            .position(0, 26)
+            .test_completion_request()
+            .await;
+        let mut actual_items = Vec::new();
+
+        complete_latex_key_values(&req, &mut actual_items).await;
+
+        assert!(actual_items.iter().any(|item| item.data.label() == "width"));
+        assert!(actual_items.iter().all(|item| item.data.label() != "clip"));
+        assert_eq!(actual_items[0].range, Range::new_simple(0, 23, 0, 26));
+    }
+
+    #[tokio::test]
+    async fn key_before_value() {
+        let req = FeatureTester::new()
+            .file("main.tex", r#"\hypersetup{colo=true}"#)
+            .main("main.tex")
+            .position(0, 14)
+            .test_completion_request()
+            .await;
+        let mut actual_items = Vec::new();
+
+        complete_latex_key_values(&req, &mut actual_items).await;
+
+        assert!(actual_items
+            .iter()
+            .any(|item| item.data.label() == "colorlinks"));
+        assert_eq!(actual_items[0].range, Range::new_simple(0, 12, 0, 16));
+    }
+
+    #[tokio::test]
+    async fn enumerated_value() {
+        let req = FeatureTester::new()
+            .file("main.tex", r#"\hypersetup{pdfpagemode=}"#)
+            .main("main.tex")
+            .position(0, 24)
+            .test_completion_request()
+            .await;
+        let mut actual_items = Vec::new();
+
+        complete_latex_key_values(&req, &mut actual_items).await;
+
+        assert!(actual_items
+            .iter()
+            .any(|item| item.data.label() == "UseOutlines"));
+        assert_eq!(actual_items[0].range, Range::new_simple(0, 24, 0, 24));
+    }
+
+    #[tokio::test]
+    async fn length_value() {
+        let req = FeatureTester::new()
+            .file("main.tex", r#"\geometry{margin=2}"#)
+            .main("main.tex")
+            .position(0, 18)
+            .test_completion_request()
+            .await;
+        let mut actual_items = Vec::new();
+
+        complete_latex_key_values(&req, &mut actual_items).await;
+
+        assert!(actual_items.iter().any(|item| item.data.label() == "2cm"));
+        assert_eq!(actual_items[0].range, Range::new_simple(0, 17, 0, 18));
+    }
+
+    #[tokio::test]
+    async fn unknown_command() {
+        let req = FeatureTester::new()
+            .file("main.tex", r#"\foo[wid]{bar}"#)
+            .main("main.tex")
+            .position(0, 8)
+            .test_completion_request()
+            .await;
+        let mut actual_items = Vec::new();
+
+        complete_latex_key_values(&req, &mut actual_items).await;
+
+        assert!(actual_items.is_empty());
+    }
+}
@@ -2,14 +2,171 @@ use super::combinators::{self, ArgumentContext, Parameter};
 use crate::{
     completion::types::{Item, ItemData},
     feature::{DocumentView, FeatureRequest},
-    outline::{Outline, OutlineContext, OutlineContextItem},
-    protocol::{CompletionParams, RangeExt},
+    outline::{Outline, OutlineCaptionKind, OutlineContext, OutlineContextItem},
+    protocol::{CompletionParams, LatexLabelOptions, Position, RangeExt},
     syntax::{
         latex, LatexLabelKind, LatexLabelReferenceSource, Structure, SyntaxNode, LANGUAGE_DATA,
     },
     workspace::DocumentContent,
 };
-use std::sync::Arc;
+use once_cell::sync::Lazy;
+use regex::Regex;
+use std::{collections::HashSet, sync::Arc};
+
+const MAX_SLUG_WORDS: usize = 4;
+
+static COMMAND_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"\\[A-Za-z]+\*?").unwrap());
+
+pub async fn complete_latex_label_definitions<'a>(
+    req: &'a FeatureRequest<CompletionParams>,
+    items: &mut Vec<Item<'a>>,
+) {
+    let parameters = LANGUAGE_DATA
+        .label_commands
+        .iter()
+        .filter(|cmd| cmd.kind == LatexLabelKind::Definition)
+        .map(|cmd| Parameter {
+            name: &cmd.name[1..],
+            index: cmd.index,
+        });
+
+    combinators::argument(req, parameters, |ctx| async move {
+        let table = match &req.current().content {
+            DocumentContent::Latex(table) => table,
+            DocumentContent::Bibtex(_) => return,
+        };
+
+        let pos = req.params.text_document_position.position;
+        let options = label_options(req);
+        if let Some((kind, name)) = suggest_name(req, table, pos, &options) {
+            let name = make_unique(req, pos, name);
+            let mut item = Item::new(ctx.range, ItemData::LabelSuggestion { name, kind });
+            item.preselect = true;
+            items.push(item);
+        }
+    })
+    .await;
+}
+
+fn label_options(req: &FeatureRequest<CompletionParams>) -> LatexLabelOptions {
+    req.options
+        .latex
+        .as_ref()
+        .and_then(|opts| opts.label.clone())
+        .unwrap_or_default()
+}
+
+/// Derives a label name from the float, equation or section that surrounds the cursor,
+/// e.g. `fig:` followed by the first words of the caption.
+fn suggest_name(
+    req: &FeatureRequest<CompletionParams>,
+    table: &latex::SymbolTable,
+    pos: Position,
+    options: &LatexLabelOptions,
+) -> Option<(Structure, String)> {
+    let float = table
+        .environments
+        .iter()
+        .filter(|env| env.range(&table).contains(pos))
+        .filter_map(|env| {
+            let name = env.left.name(&table)?;
+            OutlineCaptionKind::parse(name.text()).map(|kind| (*env, kind))
+        })
+        .max_by_key(|(env, _)| env.range(&table).start);
+
+    if let Some((env, kind)) = float {
+        let prefix = match kind {
+            OutlineCaptionKind::Figure => options.figure_prefix(),
+            OutlineCaptionKind::Table => options.table_prefix(),
+            OutlineCaptionKind::Listing => options.listing_prefix(),
+            OutlineCaptionKind::Algorithm => options.algorithm_prefix(),
+        };
+
+        let slug = OutlineContext::find_float_caption(table, env)
+            .and_then(|caption| {
+                table.print_group_content(
+                    caption.parent,
+                    latex::GroupKind::Group,
+                    caption.arg_index,
+                )
+            })
+            .map(|text| slugify(&text))
+            .filter(|slug| !slug.is_empty())
+            .or_else(|| section_slug(req, table, pos))?;
+        return Some((Structure::Float, format!("{}{}", prefix, slug)));
+    }
+
+    let is_math = table
+        .environments
+        .iter()
+        .filter(|env| env.left.is_math(&table))
+        .any(|env| env.range(&table).contains(pos));
+
+    let slug = section_slug(req, table, pos)?;
+    if is_math {
+        Some((
+            Structure::Equation,
+            format!("{}{}", options.equation_prefix(), slug),
+        ))
+    } else {
+        Some((
+            Structure::Section,
+            format!("{}{}", options.section_prefix(), slug),
+        ))
+    }
+}
+
+fn section_slug(
+    req: &FeatureRequest<CompletionParams>,
+    table: &latex::SymbolTable,
+    pos: Position,
+) -> Option<String> {
+    let outline = Outline::analyze(&req.view, &req.options, &req.current_dir);
+    let section = outline.find(&req.current().uri, pos)?;
+    let text =
+        table.print_group_content(section.parent, latex::GroupKind::Group, section.arg_index)?;
+    Some(slugify(&text)).filter(|slug| !slug.is_empty())
+}
+
+/// Converts the first words of the given text into a lowercase slug like `foo-bar-baz`.
+fn slugify(text: &str) -> String {
+    COMMAND_REGEX
+        .replace_all(text, " ")
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .take(MAX_SLUG_WORDS)
+        .map(str::to_lowercase)
+        .collect::<Vec<_>>()
+        .join("-")
+}
+
+/// Appends a number to the given name if it is already defined somewhere in the project.
+fn make_unique(req: &FeatureRequest<CompletionParams>, pos: Position, name: String) -> String {
+    let mut names = HashSet::new();
+    for doc in req.related() {
+        if let DocumentContent::Latex(table) = &doc.content {
+            for label in table
+                .labels
+                .iter()
+                .filter(|label| label.kind == LatexLabelKind::Definition)
+                .filter(|label| {
+                    doc.uri != req.current().uri || !table[label.parent].range().contains(pos)
+                })
+            {
+                names.extend(label.names(&table).into_iter().map(latex::Token::text));
+            }
+        }
+    }
+
+    if !names.contains(name.as_str()) {
+        return name;
+    }
+
+    (2..)
+        .map(|i| format!("{}-{}", name, i))
+        .find(|candidate| !names.contains(candidate.as_str()))
+        .unwrap()
+}
 
 pub async fn complete_latex_labels<'a>(
     req: &'a FeatureRequest<CompletionParams>,
@@ -26,6 +183,8 @@ pub async fn complete_latex_labels<'a>(
 
     combinators::argument(req, parameters, |ctx| async move {
         let source = find_source(ctx);
+        let existing = existing_names(req, ctx);
+        let options = label_options(req);
         for doc in req.related() {
             let snapshot = Arc::clone(&req.view.snapshot);
             let view =
@@ -37,7 +196,6 @@ pub async fn complete_latex_labels<'a>(
                     .labels
                     .iter()
                     .filter(|label| label.kind == LatexLabelKind::Definition)
-                    .filter(|label| is_included(&table, label, source))
                 {
                     let outline_ctx = OutlineContext::parse(&view, &outline, *label);
 
@@ -50,8 +208,13 @@ pub async fn complete_latex_labels<'a>(
                         None => Structure::Label,
                     };
 
-                    for name in label.names(&table) {
-                        let header = outline_ctx.as_ref().and_then(|ctx| ctx.detail());
+                    for name in label
+                        .names(&table)
+                        .into_iter()
+                        .filter(|name| !existing.contains(&name.text()))
+                        .filter(|name| is_included(&table, label, name.text(), source, &options))
+                    {
+                        let header = outline_ctx.as_ref().map(OutlineContext::reference);
                         let footer = outline_ctx.as_ref().and_then(|ctx| match &ctx.item {
                             OutlineContextItem::Caption { text, .. } => Some(text.clone()),
                             _ => None,
@@ -81,6 +244,29 @@ pub async fn complete_latex_labels<'a>(
     .await;
 }
 
+/// Returns the labels that are already referenced by the argument,
+/// e.g. `foo` in `\cref{foo,}`, except for the one under the cursor.
+fn existing_names<'a>(
+    req: &'a FeatureRequest<CompletionParams>,
+    ctx: ArgumentContext,
+) -> Vec<&'a str> {
+    req.current()
+        .content
+        .as_latex()
+        .and_then(|table| {
+            table.extract_comma_separated_words(
+                ctx.node,
+                latex::GroupKind::Group,
+                ctx.parameter.index,
+            )
+        })
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|name| name.range() != ctx.range)
+        .map(latex::Token::text)
+        .collect()
+}
+
 fn find_source(ctx: ArgumentContext) -> LatexLabelReferenceSource {
     match LANGUAGE_DATA
         .label_commands
@@ -97,23 +283,28 @@ fn find_source(ctx: ArgumentContext) -> LatexLabelReferenceSource {
 fn is_included(
     table: &latex::SymbolTable,
     label: &latex::Label,
+    name: &str,
     source: LatexLabelReferenceSource,
+    options: &LatexLabelOptions,
 ) -> bool {
     let label_range = table[label.parent].range();
     match source {
         LatexLabelReferenceSource::Everything => true,
-        LatexLabelReferenceSource::Math => table
-            .environments
-            .iter()
-            .filter(|env| env.left.is_math(&table))
-            .any(|env| env.range(&table).contains_exclusive(label_range.start)),
+        LatexLabelReferenceSource::Math => {
+            name.starts_with(options.equation_prefix())
+                || table
+                    .environments
+                    .iter()
+                    .filter(|env| env.left.is_math(&table))
+                    .any(|env| env.range(&table).contains_exclusive(label_range.start))
+        }
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::feature::FeatureTester;
+    use crate::{feature::FeatureTester, protocol::Range};
     use indoc::indoc;
 
     #[tokio::test]
@@ -227,4 +418,171 @@ mod tests {
 
         assert_eq!(actual_labels, vec!["foo"]);
     }
+
+    #[tokio::test]
+    async fn eqref_prefix() {
+        let req = FeatureTester::new()
+            .file(
+                "main.tex",
+                indoc!(
+                    r#"
+                    \section{Foo}\label{eq:foo}\label{bar}
+                    \eqref{}
+                "#
+                ),
+            )
+            .main("main.tex")
+            .position(1, 7)
+            .test_completion_request()
+            .await;
+        let mut actual_items = Vec::new();
+
+        complete_latex_labels(&req, &mut actual_items).await;
+
+        let actual_labels: Vec<_> = actual_items
+            .into_iter()
+            .map(|item| item.data.label().to_owned())
+            .collect();
+
+        assert_eq!(actual_labels, vec!["eq:foo"]);
+    }
+
+    #[tokio::test]
+    async fn cref_multiple() {
+        let req = FeatureTester::new()
+            .file(
+                "main.tex",
+                indoc!(
+                    r#"
+                    \label{foo}\label{bar}\label{baz}
+                    \cref{foo,b}
+                "#
+                ),
+            )
+            .main("main.tex")
+            .position(1, 11)
+            .test_completion_request()
+            .await;
+        let mut actual_items = Vec::new();
+
+        complete_latex_labels(&req, &mut actual_items).await;
+
+        let actual_labels: Vec<_> = actual_items
+            .iter()
+            .map(|item| item.data.label().to_owned())
+            .collect();
+
+        assert_eq!(actual_labels, vec!["bar", "baz"]);
+        assert_eq!(actual_items[0].range, Range::new_simple(1, 10, 1, 11));
+    }
+
+    #[tokio::test]
+    async fn figure_number() {
+        let req = FeatureTester::new()
+            .file(
+                "main.tex",
+                indoc!(
+                    r#"
+                    \begin{figure}\caption{Foo}\label{fig:foo}\end{figure}
+                    \ref{}
+                "#
+                ),
+            )
+            .file(
+                "main.aux",
+                r#"\newlabel{fig:foo}{{\relax 2.1}{4}{Bar\relax }{figure.caption.4}{}}"#,
+            )
+            .main("main.tex")
+            .position(1, 5)
+            .test_completion_request()
+            .await;
+        let mut actual_items = Vec::new();
+
+        complete_latex_labels(&req, &mut actual_items).await;
+
+        match &actual_items[0].data {
+            ItemData::Label { header, footer, .. } => {
+                assert_eq!(header.as_deref(), Some("Figure 2.1: Foo"));
+                assert_eq!(footer.as_deref(), Some("Foo"));
+            }
+            _ => panic!("expected a label"),
+        }
+    }
+
+    async fn suggest(text: &str, line: u64, character: u64) -> Vec<String> {
+        let req = FeatureTester::new()
+            .file("main.tex", text)
+            .main("main.tex")
+            .position(line, character)
+            .test_completion_request()
+            .await;
+        let mut actual_items = Vec::new();
+
+        complete_latex_label_definitions(&req, &mut actual_items).await;
+
+        actual_items
+            .into_iter()
+            .map(|item| item.data.label().to_owned())
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn suggestion_figure() {
+        let actual_labels = suggest(
+            indoc!(
+                r#"
+                    \begin{figure}
+                    \caption{The \textbf{Quick} Brown Fox Jumps}
+                    \label{}
+                    \end{figure}
+                "#
+            ),
+            2,
+            7,
+        )
+        .await;
+
+        assert_eq!(actual_labels, vec!["fig:the-quick-brown-fox"]);
+    }
+
+    #[tokio::test]
+    async fn suggestion_section() {
+        let actual_labels = suggest("\\section{Related Work}\\label{}", 0, 29).await;
+
+        assert_eq!(actual_labels, vec!["sec:related-work"]);
+    }
+
+    #[tokio::test]
+    async fn suggestion_equation() {
+        let actual_labels = suggest(
+            indoc!(
+                r#"
+                    \section{Foo}
+                    \begin{equation}\label{}\end{equation}
+                "#
+            ),
+            1,
+            23,
+        )
+        .await;
+
+        assert_eq!(actual_labels, vec!["eq:foo"]);
+    }
+
+    #[tokio::test]
+    async fn suggestion_unique() {
+        let actual_labels = suggest(
+            indoc!(
+                r#"
+                    \section{Foo}\label{sec:foo}
+                    \section{Foo}\label{}
+                "#
+            ),
+            1,
+            20,
+        )
+        .await;
+
+        assert_eq!(actual_labels, vec!["sec:foo-2"]);
+    }
 }
@@ -1,5 +1,6 @@
 pub mod argument;
 pub mod begin_cmd;
+pub mod bib_style;
 pub mod citation;
 pub mod color;
 pub mod color_model;
@@ -8,7 +9,9 @@ pub mod component;
 pub mod glossary;
 pub mod import;
 pub mod include;
+pub mod key_value;
 pub mod label;
+pub mod symbol;
 pub mod theorem;
 pub mod tikz_lib;
 pub mod user;
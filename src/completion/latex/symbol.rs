@@ -0,0 +1,127 @@
+use crate::{
+    completion::{
+        types::{Item, ItemData},
+        util::is_math_mode,
+    },
+    feature::FeatureRequest,
+    protocol::{CompletionParams, Position, Range},
+    syntax::CharStream,
+    workspace::DocumentContent,
+};
+
+pub async fn complete_latex_symbols<'a>(
+    req: &'a FeatureRequest<CompletionParams>,
+    items: &mut Vec<Item<'a>>,
+) {
+    if let DocumentContent::Latex(_) = &req.current().content {
+        let pos = req.params.text_document_position.position;
+        let line = Range::new(Position::new(pos.line, 0), pos);
+        let glyph = match CharStream::extract(&req.current().text, line)
+            .chars()
+            .last()
+        {
+            Some(c) if !c.is_ascii() => c.to_string(),
+            _ => return,
+        };
+
+        let start = Position::new(pos.line, pos.character - 1);
+        let range = Range::new(start, pos);
+        let is_math_mode = is_math_mode(req);
+        for comp in req.view.components() {
+            for cmd in &comp.commands {
+                if cmd.glyph.as_deref() == Some(glyph.as_str()) {
+                    items.push(Item::new(
+                        range,
+                        ItemData::Symbol {
+                            name: &cmd.name,
+                            glyph: cmd.glyph.as_deref().unwrap(),
+                            text: cmd.symbol_text(is_math_mode),
+                            file_names: &comp.file_names,
+                        },
+                    ));
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{feature::FeatureTester, protocol::RangeExt};
+
+    #[tokio::test]
+    async fn empty_latex_document() {
+        let req = FeatureTester::new()
+            .file("main.tex", "")
+            .main("main.tex")
+            .position(0, 0)
+            .test_completion_request()
+            .await;
+        let mut actual_items = Vec::new();
+
+        complete_latex_symbols(&req, &mut actual_items).await;
+
+        assert!(actual_items.is_empty());
+    }
+
+    #[tokio::test]
+    async fn math_mode() {
+        let req = FeatureTester::new()
+            .file("main.tex", "$a ε$")
+            .main("main.tex")
+            .position(0, 4)
+            .test_completion_request()
+            .await;
+        let mut actual_items = Vec::new();
+
+        complete_latex_symbols(&req, &mut actual_items).await;
+
+        let item = actual_items
+            .iter()
+            .find(|item| item.data.label() == "varepsilon")
+            .unwrap();
+        assert_eq!(item.range, Range::new_simple(0, 3, 0, 4));
+        match &item.data {
+            ItemData::Symbol { text, .. } => assert_eq!(text, "\\varepsilon"),
+            _ => panic!("expected a symbol"),
+        }
+    }
+
+    #[tokio::test]
+    async fn text_mode() {
+        let req = FeatureTester::new()
+            .file("main.tex", "foo ε")
+            .main("main.tex")
+            .position(0, 5)
+            .test_completion_request()
+            .await;
+        let mut actual_items = Vec::new();
+
+        complete_latex_symbols(&req, &mut actual_items).await;
+
+        let item = actual_items
+            .iter()
+            .find(|item| item.data.label() == "varepsilon")
+            .unwrap();
+        match &item.data {
+            ItemData::Symbol { text, .. } => assert_eq!(text, "$\\varepsilon$"),
+            _ => panic!("expected a symbol"),
+        }
+    }
+
+    #[tokio::test]
+    async fn ascii() {
+        let req = FeatureTester::new()
+            .file("main.tex", "foo")
+            .main("main.tex")
+            .position(0, 3)
+            .test_completion_request()
+            .await;
+        let mut actual_items = Vec::new();
+
+        complete_latex_symbols(&req, &mut actual_items).await;
+
+        assert!(actual_items.is_empty());
+    }
+}
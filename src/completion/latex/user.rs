@@ -6,6 +6,7 @@ use crate::{
     syntax::latex,
     workspace::DocumentContent,
 };
+use std::collections::HashMap;
 
 pub async fn complete_latex_user_commands<'a>(
     req: &'a FeatureRequest<CompletionParams>,
@@ -20,6 +21,18 @@ pub async fn complete_latex_user_commands<'a>(
             .as_command(current_cmd_node)
             .unwrap();
 
+        let arg_counts: HashMap<_, _> = req
+            .related()
+            .iter()
+            .flat_map(|doc| doc.content.as_latex())
+            .flat_map(|table| {
+                table
+                    .command_definitions
+                    .iter()
+                    .map(move |def| (&def.definition_name(table)[1..], def.arg_count(table)))
+            })
+            .collect();
+
         for table in req
             .related()
             .into_iter()
@@ -31,9 +44,10 @@ pub async fn complete_latex_user_commands<'a>(
                 .filter(|cmd_node| **cmd_node != current_cmd_node)
                 .map(|cmd_node| {
                     let name = &table.as_command(*cmd_node).unwrap().name.text()[1..];
+                    let arg_count = arg_counts.get(name).copied().unwrap_or(0);
                     Item::new(
                         current_cmd.short_name_range(),
-                        ItemData::UserCommand { name },
+                        ItemData::UserCommand { name, arg_count },
                     )
                 })
                 .for_each(|item| items.push(item));
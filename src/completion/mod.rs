@@ -1,5 +1,6 @@
 mod bibtex;
 mod latex;
+mod ranking;
 mod types;
 mod util;
 
@@ -8,36 +9,48 @@ pub use self::types::{CompletionItemData, Item, ItemData};
 use self::{
     bibtex::{
         cmd::complete_bibtex_commands, entry_type::complete_bibtex_entry_types,
-        field_name::complete_bibtex_fields,
+        field_name::complete_bibtex_fields, field_value::complete_bibtex_field_values,
     },
     latex::{
         argument::complete_latex_arguments,
         begin_cmd::complete_latex_begin_command,
+        bib_style::{complete_latex_biblatex_styles, complete_latex_bibliography_styles},
         citation::complete_latex_citations,
         color::complete_latex_colors,
         color_model::complete_latex_color_models,
         component::{complete_latex_component_commands, complete_latex_component_environments},
         glossary::complete_latex_glossary_entries,
-        import::{complete_latex_classes, complete_latex_packages},
+        import::{
+            complete_latex_class_options, complete_latex_classes, complete_latex_package_options,
+            complete_latex_packages,
+        },
         include::complete_latex_includes,
-        label::complete_latex_labels,
+        key_value::complete_latex_key_values,
+        label::{complete_latex_label_definitions, complete_latex_labels},
+        symbol::complete_latex_symbols,
         theorem::complete_latex_theorem_environments,
         tikz_lib::{complete_latex_pgf_libraries, complete_latex_tikz_libraries},
         user::{complete_latex_user_commands, complete_latex_user_environments},
     },
-    util::{adjust_kind, component_detail, current_word, image_documentation},
+    ranking::Ranking,
+    util::{
+        adjust_kind, command_snippet, component_detail, current_word, environment_snippet_range,
+        image_documentation,
+    },
 };
 use crate::{
+    bibliography::IMPORT_CITATION_COMMAND,
     feature::{FeatureProvider, FeatureRequest},
     protocol::{
-        CompletionItem, CompletionParams, CompletionTextEdit, Documentation, InsertTextFormat,
-        MarkupContent, MarkupKind, RangeExt, TextEdit,
+        Command, CompletionItem, CompletionParams, CompletionTextEdit, Documentation,
+        InsertTextFormat, MarkupContent, MarkupKind, Range, RangeExt, TextEdit,
     },
-    syntax::{self, Structure, SyntaxNode},
+    syntax::{self, BibtexEntryTypeCategory, Structure, SyntaxNode, LANGUAGE_DATA},
     workspace::DocumentContent,
 };
 use async_trait::async_trait;
 use fuzzy_matcher::skim::fuzzy_match;
+use serde_json::json;
 use std::collections::HashSet;
 
 pub const COMPLETION_LIMIT: usize = 50;
@@ -65,8 +78,8 @@ impl FeatureProvider for CompletionProvider {
         items.sort_by_key(|item| (!item.preselect, -item.score.unwrap_or(std::i64::MIN + 1)));
         items
             .into_iter()
-            .take(COMPLETION_LIMIT)
             .filter(|item| item.score.is_some())
+            .take(COMPLETION_LIMIT)
             .map(|item| convert(req, item))
             .enumerate()
             .map(|(i, item)| append_sort_text(item, i))
@@ -79,7 +92,9 @@ async fn complete_all<'a>(req: &'a FeatureRequest<CompletionParams>) -> Vec<Item
     complete_bibtex_commands(req, &mut items).await;
     complete_bibtex_entry_types(req, &mut items).await;
     complete_bibtex_fields(req, &mut items).await;
+    complete_bibtex_field_values(req, &mut items).await;
     complete_latex_arguments(req, &mut items).await;
+    complete_latex_key_values(req, &mut items).await;
     complete_latex_begin_command(req, &mut items).await;
     complete_latex_colors(req, &mut items).await;
     complete_latex_color_models(req, &mut items).await;
@@ -87,7 +102,12 @@ async fn complete_all<'a>(req: &'a FeatureRequest<CompletionParams>) -> Vec<Item
     complete_latex_citations(req, &mut items).await;
     complete_latex_classes(req, &mut items).await;
     complete_latex_packages(req, &mut items).await;
+    complete_latex_class_options(req, &mut items).await;
+    complete_latex_package_options(req, &mut items).await;
+    complete_latex_bibliography_styles(req, &mut items).await;
+    complete_latex_biblatex_styles(req, &mut items).await;
     complete_latex_includes(req, &mut items).await;
+    complete_latex_label_definitions(req, &mut items).await;
     complete_latex_labels(req, &mut items).await;
     complete_latex_pgf_libraries(req, &mut items).await;
     complete_latex_tikz_libraries(req, &mut items).await;
@@ -96,6 +116,7 @@ async fn complete_all<'a>(req: &'a FeatureRequest<CompletionParams>) -> Vec<Item
     complete_latex_user_environments(req, &mut items).await;
     complete_latex_component_commands(req, &mut items).await;
     complete_latex_user_commands(req, &mut items).await;
+    complete_latex_symbols(req, &mut items).await;
     items
 }
 
@@ -145,15 +166,29 @@ fn preselect(req: &FeatureRequest<CompletionParams>, items: &mut [Item]) {
 fn score(req: &FeatureRequest<CompletionParams>, items: &mut Vec<Item>) {
     let current_word = current_word(req);
     let pattern = current_word.as_deref().unwrap_or_default();
+    let ranking = Ranking::analyze(req);
     for item in items {
-        item.score = match &item.data {
-            ItemData::ComponentCommand { name, .. } => fuzzy_match(name, pattern),
+        let score = match &item.data {
+            ItemData::ComponentCommand { name, .. } => {
+                let keywords = LANGUAGE_DATA.symbol_keywords(name);
+                std::iter::once(*name)
+                    .chain(keywords.iter().map(String::as_str))
+                    .filter_map(|text| fuzzy_match(text, pattern))
+                    .max()
+            }
             ItemData::ComponentEnvironment { name, .. } => fuzzy_match(name, pattern),
-            ItemData::UserCommand { name } => fuzzy_match(name, pattern),
+            ItemData::UserCommand { name, .. } => fuzzy_match(name, pattern),
+            ItemData::Symbol { glyph, .. } => fuzzy_match(glyph, pattern),
             ItemData::UserEnvironment { name } => fuzzy_match(name, pattern),
             ItemData::Label { text, .. } => fuzzy_match(&text, pattern),
+            ItemData::LabelSuggestion { name, .. } => fuzzy_match(&name, pattern),
             ItemData::Class { name } => fuzzy_match(&name, pattern),
             ItemData::Package { name } => fuzzy_match(&name, pattern),
+            ItemData::BibliographyStyle { name, .. } => fuzzy_match(&name, pattern),
+            ItemData::PackageOption { name, .. } => fuzzy_match(name, pattern),
+            ItemData::ClassOption { name, .. } => fuzzy_match(name, pattern),
+            ItemData::OptionKey { name, .. } => fuzzy_match(name, pattern),
+            ItemData::OptionValue { name, .. } => fuzzy_match(&name, pattern),
             ItemData::PgfLibrary { name } => fuzzy_match(name, pattern),
             ItemData::TikzLibrary { name } => fuzzy_match(name, pattern),
             ItemData::File { name } => fuzzy_match(name, pattern),
@@ -166,7 +201,11 @@ fn score(req: &FeatureRequest<CompletionParams>, items: &mut Vec<Item>) {
             ItemData::GlossaryEntry { name } => fuzzy_match(name, pattern),
             ItemData::EntryType { ty } => fuzzy_match(&ty.name, pattern),
             ItemData::Field { field } => fuzzy_match(&field.name, pattern),
+            ItemData::FieldValue { name, .. } => fuzzy_match(name, pattern),
+            ItemData::BibtexString { name, .. } => fuzzy_match(name, pattern),
+            ItemData::AuthorName { name } => fuzzy_match(&name, pattern),
         };
+        item.score = score.map(|score| score + ranking.bonus(&item.data));
     }
 }
 
@@ -177,47 +216,77 @@ fn convert(req: &FeatureRequest<CompletionParams>, item: Item) -> CompletionItem
             image,
             glyph,
             file_names,
+            arg_count,
         } => {
             let detail = glyph.map_or_else(
                 || component_detail(file_names),
                 |glyph| format!("{}, {}", glyph, component_detail(file_names)),
             );
             let documentation = image.and_then(|img| image_documentation(&req, &name, img));
-            let text_edit = TextEdit::new(item.range, name.into());
+            let (text_edit, insert_text_format) =
+                command_text_edit(req, item.range, name, arg_count);
+            let keywords = LANGUAGE_DATA.symbol_keywords(name);
+            let filter_text = if keywords.is_empty() {
+                None
+            } else {
+                Some(format!("{} {}", name, keywords.join(" ")))
+            };
             CompletionItem {
                 kind: Some(adjust_kind(req, Structure::Command.completion_kind())),
                 data: Some(CompletionItemData::Command.into()),
+                filter_text,
                 documentation,
                 text_edit: Some(CompletionTextEdit::Edit(text_edit)),
+                insert_text_format,
                 ..CompletionItem::new_simple(name.into(), detail)
             }
         }
+        ItemData::Symbol {
+            name,
+            glyph,
+            text,
+            file_names,
+        } => {
+            let detail = format!("{}, {}", glyph, component_detail(file_names));
+            let text_edit = TextEdit::new(item.range, text);
+            CompletionItem {
+                kind: Some(adjust_kind(req, Structure::Command.completion_kind())),
+                data: Some(CompletionItemData::Command.into()),
+                filter_text: Some(glyph.into()),
+                text_edit: Some(CompletionTextEdit::Edit(text_edit)),
+                ..CompletionItem::new_simple(format!("\\{}", name), detail)
+            }
+        }
         ItemData::ComponentEnvironment { name, file_names } => {
-            let text_edit = TextEdit::new(item.range, name.into());
+            let (text_edit, insert_text_format) = environment_text_edit(req, item.range, name);
             CompletionItem {
                 kind: Some(adjust_kind(req, Structure::Environment.completion_kind())),
                 data: Some(CompletionItemData::Environment.into()),
                 text_edit: Some(CompletionTextEdit::Edit(text_edit)),
+                insert_text_format,
                 ..CompletionItem::new_simple(name.into(), component_detail(file_names))
             }
         }
-        ItemData::UserCommand { name } => {
+        ItemData::UserCommand { name, arg_count } => {
             let detail = "user-defined".into();
-            let text_edit = TextEdit::new(item.range, name.into());
+            let (text_edit, insert_text_format) =
+                command_text_edit(req, item.range, name, arg_count);
             CompletionItem {
                 kind: Some(adjust_kind(req, Structure::Command.completion_kind())),
                 data: Some(CompletionItemData::Command.into()),
                 text_edit: Some(CompletionTextEdit::Edit(text_edit)),
+                insert_text_format,
                 ..CompletionItem::new_simple(name.into(), detail)
             }
         }
         ItemData::UserEnvironment { name } => {
             let detail = "user-defined".into();
-            let text_edit = TextEdit::new(item.range, name.into());
+            let (text_edit, insert_text_format) = environment_text_edit(req, item.range, name);
             CompletionItem {
                 kind: Some(adjust_kind(req, Structure::Environment.completion_kind())),
                 data: Some(CompletionItemData::Environment.into()),
                 text_edit: Some(CompletionTextEdit::Edit(text_edit)),
+                insert_text_format,
                 ..CompletionItem::new_simple(name.into(), detail)
             }
         }
@@ -241,6 +310,16 @@ fn convert(req: &FeatureRequest<CompletionParams>, item: Item) -> CompletionItem
                 ..CompletionItem::default()
             }
         }
+        ItemData::LabelSuggestion { name, kind } => {
+            let text_edit = TextEdit::new(item.range, name.clone());
+            CompletionItem {
+                label: name,
+                kind: Some(adjust_kind(req, kind.completion_kind())),
+                data: Some(CompletionItemData::Label.into()),
+                text_edit: Some(CompletionTextEdit::Edit(text_edit)),
+                ..CompletionItem::default()
+            }
+        }
         ItemData::Class { name } => {
             let text_edit = TextEdit::new(item.range, name.as_ref().into());
             CompletionItem {
@@ -261,6 +340,53 @@ fn convert(req: &FeatureRequest<CompletionParams>, item: Item) -> CompletionItem
                 ..CompletionItem::default()
             }
         }
+        ItemData::BibliographyStyle { name, package } => {
+            let text_edit = TextEdit::new(item.range, name.clone());
+            CompletionItem {
+                label: name,
+                kind: Some(adjust_kind(req, Structure::Package.completion_kind())),
+                data: Some(CompletionItemData::BibliographyStyle.into()),
+                text_edit: Some(CompletionTextEdit::Edit(text_edit)),
+                detail: package,
+                ..CompletionItem::default()
+            }
+        }
+        ItemData::PackageOption { name, file_name } => {
+            let text_edit = TextEdit::new(item.range, name.into());
+            CompletionItem {
+                kind: Some(adjust_kind(req, Structure::Argument.completion_kind())),
+                data: Some(CompletionItemData::PackageOption.into()),
+                text_edit: Some(CompletionTextEdit::Edit(text_edit)),
+                ..CompletionItem::new_simple(name.into(), file_name.into())
+            }
+        }
+        ItemData::ClassOption { name, file_name } => {
+            let text_edit = TextEdit::new(item.range, name.into());
+            CompletionItem {
+                kind: Some(adjust_kind(req, Structure::Argument.completion_kind())),
+                data: Some(CompletionItemData::ClassOption.into()),
+                text_edit: Some(CompletionTextEdit::Edit(text_edit)),
+                ..CompletionItem::new_simple(name.into(), file_name.into())
+            }
+        }
+        ItemData::OptionKey { name, key_set } => {
+            let text_edit = TextEdit::new(item.range, name.into());
+            CompletionItem {
+                kind: Some(adjust_kind(req, Structure::Argument.completion_kind())),
+                data: Some(CompletionItemData::OptionKey.into()),
+                text_edit: Some(CompletionTextEdit::Edit(text_edit)),
+                ..CompletionItem::new_simple(name.into(), key_set.into())
+            }
+        }
+        ItemData::OptionValue { name, key } => {
+            let text_edit = TextEdit::new(item.range, name.as_ref().into());
+            CompletionItem {
+                kind: Some(adjust_kind(req, Structure::Argument.completion_kind())),
+                data: Some(CompletionItemData::OptionValue.into()),
+                text_edit: Some(CompletionTextEdit::Edit(text_edit)),
+                ..CompletionItem::new_simple(name.into_owned(), key.into())
+            }
+        }
         ItemData::PgfLibrary { name } => {
             let text_edit = TextEdit::new(item.range, name.into());
             CompletionItem {
@@ -301,10 +427,24 @@ fn convert(req: &FeatureRequest<CompletionParams>, item: Item) -> CompletionItem
                 ..CompletionItem::default()
             }
         }
-        ItemData::Citation { uri, key, text, ty } => {
+        ItemData::Citation {
+            uri,
+            key,
+            label,
+            text,
+            ty,
+            import,
+            ..
+        } => {
             let text_edit = TextEdit::new(item.range, key.into());
+            let command = import.map(|target| Command {
+                title: "Import citation".into(),
+                command: IMPORT_CITATION_COMMAND.into(),
+                arguments: Some(vec![json!(uri), json!(key), json!(target)]),
+            });
             CompletionItem {
-                label: key.into(),
+                label,
+                command,
                 kind: Some(adjust_kind(req, ty.completion_kind())),
                 filter_text: Some(text.clone()),
                 sort_text: Some(text),
@@ -398,11 +538,72 @@ fn convert(req: &FeatureRequest<CompletionParams>, item: Item) -> CompletionItem
                 ..CompletionItem::default()
             }
         }
+        ItemData::FieldValue { name, field } => {
+            let text_edit = TextEdit::new(item.range, name.into());
+            CompletionItem {
+                kind: Some(adjust_kind(req, Structure::Argument.completion_kind())),
+                data: Some(CompletionItemData::FieldValue.into()),
+                text_edit: Some(CompletionTextEdit::Edit(text_edit)),
+                ..CompletionItem::new_simple(name.into(), field.into())
+            }
+        }
+        ItemData::BibtexString { name, value } => {
+            let text_edit = TextEdit::new(item.range, name.into());
+            let kind = Structure::Entry(BibtexEntryTypeCategory::String).completion_kind();
+            CompletionItem {
+                kind: Some(adjust_kind(req, kind)),
+                data: Some(CompletionItemData::BibtexString.into()),
+                text_edit: Some(CompletionTextEdit::Edit(text_edit)),
+                ..CompletionItem::new_simple(name.into(), value)
+            }
+        }
+        ItemData::AuthorName { name } => {
+            let text_edit = TextEdit::new(item.range, name.clone());
+            CompletionItem {
+                label: name,
+                kind: Some(adjust_kind(req, Structure::Argument.completion_kind())),
+                data: Some(CompletionItemData::AuthorName.into()),
+                text_edit: Some(CompletionTextEdit::Edit(text_edit)),
+                ..CompletionItem::default()
+            }
+        }
     };
     new_item.preselect = Some(item.preselect);
     new_item
 }
 
+fn command_text_edit(
+    req: &FeatureRequest<CompletionParams>,
+    range: Range,
+    name: &str,
+    arg_count: usize,
+) -> (TextEdit, Option<InsertTextFormat>) {
+    match command_snippet(req, name, arg_count) {
+        Some(snippet) => (
+            TextEdit::new(range, snippet),
+            Some(InsertTextFormat::Snippet),
+        ),
+        None => (TextEdit::new(range, name.into()), None),
+    }
+}
+
+fn environment_text_edit(
+    req: &FeatureRequest<CompletionParams>,
+    range: Range,
+    name: &str,
+) -> (TextEdit, Option<InsertTextFormat>) {
+    match environment_snippet_range(req, range) {
+        Some(range) => {
+            let snippet = format!("{}}}\n\t$0\n\\end{{{}}}", name, name);
+            (
+                TextEdit::new(range, snippet),
+                Some(InsertTextFormat::Snippet),
+            )
+        }
+        None => (TextEdit::new(range, name.into()), None),
+    }
+}
+
 fn append_sort_text(mut item: CompletionItem, index: usize) -> CompletionItem {
     let sort_prefix = format!("{:0>2}", index);
     match &item.sort_text {
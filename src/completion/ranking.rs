@@ -0,0 +1,374 @@
+use super::{types::ItemData, util::is_math_mode};
+use crate::{
+    feature::FeatureRequest,
+    protocol::{CompletionParams, Position, Range, RangeExt},
+    syntax::{CharStream, LatexCommandMode, Structure, SyntaxNode, LANGUAGE_DATA},
+};
+use std::collections::HashMap;
+
+const MATH_MODE_BONUS: i64 = 10;
+
+const EXPECTED_KIND_BONUS: i64 = 20;
+
+const FREQUENCY_BONUS: i64 = 5;
+
+const MAX_FREQUENCY: usize = 10;
+
+const RECENCY_BONUS: i64 = 30;
+
+const RECENT_LINES: u64 = 30;
+
+/// The words in front of a reference that hint at the kind of the referenced object,
+/// e.g. `Figure~\ref{...}`.
+const LABEL_CONTEXTS: &[(&[&str], Structure)] = &[
+    (
+        &[
+            "figure", "figures", "fig", "figs", "table", "tables", "tab", "listing",
+        ],
+        Structure::Float,
+    ),
+    (
+        &[
+            "section", "sections", "sec", "chapter", "chapters", "chap", "appendix", "part",
+        ],
+        Structure::Section,
+    ),
+    (
+        &["equation", "equations", "eq", "eqs", "eqn"],
+        Structure::Equation,
+    ),
+    (
+        &[
+            "theorem",
+            "thm",
+            "lemma",
+            "corollary",
+            "definition",
+            "proposition",
+            "remark",
+            "example",
+        ],
+        Structure::Theorem,
+    ),
+    (&["item", "step"], Structure::Item),
+];
+
+/// The conventional label prefixes of the different kinds of objects, e.g. `\label{fig:foo}`.
+const LABEL_PREFIXES: &[(&str, Structure)] = &[
+    ("fig:", Structure::Float),
+    ("tab:", Structure::Float),
+    ("lst:", Structure::Float),
+    ("sec:", Structure::Section),
+    ("ch:", Structure::Section),
+    ("chap:", Structure::Section),
+    ("app:", Structure::Section),
+    ("eq:", Structure::Equation),
+    ("thm:", Structure::Theorem),
+    ("lem:", Structure::Theorem),
+    ("def:", Structure::Theorem),
+    ("cor:", Structure::Theorem),
+    ("prop:", Structure::Theorem),
+    ("item:", Structure::Item),
+];
+
+/// The reference commands that only make sense for a specific kind of object.
+const TYPED_REFERENCES: &[(&str, Structure)] = &[("eqref", Structure::Equation)];
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+struct Usage {
+    count: usize,
+    /// The number of lines between the closest use above the cursor and the cursor.
+    distance: Option<u64>,
+}
+
+impl Usage {
+    fn add(&mut self, pos: Option<Position>, range: Range) {
+        self.count += 1;
+        if let Some(pos) = pos {
+            if range.end <= pos {
+                let distance = pos.line - range.start.line;
+                self.distance = Some(self.distance.map_or(distance, |d| d.min(distance)));
+            }
+        }
+    }
+
+    fn bonus(self) -> i64 {
+        let frequency = FREQUENCY_BONUS * self.count.min(MAX_FREQUENCY) as i64;
+        let recency = self
+            .distance
+            .filter(|distance| *distance < RECENT_LINES)
+            .map_or(0, |distance| {
+                RECENCY_BONUS * (RECENT_LINES - distance) as i64 / RECENT_LINES as i64
+            });
+        frequency + recency
+    }
+}
+
+/// Ranks completion items by how often and how recently their names are used in the project
+/// and by the kind of item that is expected at the cursor.
+#[derive(Debug, Clone, Default)]
+pub struct Ranking<'a> {
+    commands: HashMap<&'a str, Usage>,
+    environments: HashMap<&'a str, Usage>,
+    labels: HashMap<&'a str, Usage>,
+    citations: HashMap<&'a str, Usage>,
+    is_math_mode: bool,
+    label_kind: Option<Structure>,
+}
+
+impl<'a> Ranking<'a> {
+    pub fn analyze(req: &'a FeatureRequest<CompletionParams>) -> Self {
+        let mut ranking = Self {
+            is_math_mode: is_math_mode(req),
+            label_kind: expected_label_kind(req),
+            ..Self::default()
+        };
+
+        let cursor = req.params.text_document_position.position;
+        for doc in req.related() {
+            let table = match doc.content.as_latex() {
+                Some(table) => table,
+                None => continue,
+            };
+
+            let pos = if doc.uri == req.current().uri {
+                Some(cursor)
+            } else {
+                None
+            };
+
+            let add = |usages: &mut HashMap<&'a str, Usage>, name: &'a str, range: Range| {
+                if pos.map_or(true, |pos| !range.contains(pos)) {
+                    usages.entry(name).or_default().add(pos, range);
+                }
+            };
+
+            for cmd in table
+                .commands
+                .iter()
+                .filter_map(|node| table.as_command(*node))
+            {
+                add(&mut ranking.commands, &cmd.name.text()[1..], cmd.range());
+            }
+
+            for name in table
+                .environments
+                .iter()
+                .filter_map(|env| env.left.name(&table.tree))
+            {
+                add(&mut ranking.environments, name.text(), name.range());
+            }
+
+            for name in table
+                .labels
+                .iter()
+                .filter(|label| label.kind.is_reference())
+                .flat_map(|label| label.names(&table.tree))
+            {
+                add(&mut ranking.labels, name.text(), name.range());
+            }
+
+            for key in table
+                .citations
+                .iter()
+                .flat_map(|citation| citation.keys(&table.tree))
+            {
+                add(&mut ranking.citations, key.text(), key.range());
+            }
+        }
+        ranking
+    }
+
+    pub fn bonus(&self, data: &ItemData) -> i64 {
+        match data {
+            ItemData::ComponentCommand { name, .. } => {
+                let mode = LANGUAGE_DATA.command_mode(&format!("\\{}", name));
+                let bonus = match mode {
+                    Some(LatexCommandMode::Math) if self.is_math_mode => MATH_MODE_BONUS,
+                    _ => 0,
+                };
+                bonus + Self::usage(&self.commands, name)
+            }
+            ItemData::UserCommand { name, .. } => Self::usage(&self.commands, name),
+            ItemData::ComponentEnvironment { name, .. } | ItemData::UserEnvironment { name } => {
+                Self::usage(&self.environments, name)
+            }
+            ItemData::Label { name, kind, .. } => {
+                let bonus = match self.label_kind {
+                    Some(expected) if expected == *kind || has_label_prefix(name, expected) => {
+                        EXPECTED_KIND_BONUS
+                    }
+                    _ => 0,
+                };
+                bonus + Self::usage(&self.labels, name)
+            }
+            ItemData::Citation { key, .. } => Self::usage(&self.citations, key),
+            _ => 0,
+        }
+    }
+
+    fn usage(usages: &HashMap<&str, Usage>, name: &str) -> i64 {
+        usages.get(name).map_or(0, |usage| usage.bonus())
+    }
+}
+
+fn has_label_prefix(name: &str, kind: Structure) -> bool {
+    LABEL_PREFIXES
+        .iter()
+        .any(|(prefix, prefix_kind)| *prefix_kind == kind && name.starts_with(prefix))
+}
+
+/// Guesses the kind of the referenced object from the reference command
+/// or the word in front of it.
+fn expected_label_kind(req: &FeatureRequest<CompletionParams>) -> Option<Structure> {
+    let pos = req.params.text_document_position.position;
+    let line = Range::new(Position::new(pos.line, 0), pos);
+    let prefix = CharStream::extract(&req.current().text, line);
+    let (prefix, cmd) = prefix.split_at(prefix.rfind('\\')?);
+    if let Some((_, kind)) = TYPED_REFERENCES
+        .iter()
+        .find(|(name, _)| cmd[1..].starts_with(&format!("{}{{", name)))
+    {
+        return Some(*kind);
+    }
+
+    let word = prefix
+        .trim_end_matches(|c: char| c.is_whitespace() || c == '~')
+        .rsplit(|c: char| c.is_whitespace() || c == '~' || c == '(')
+        .next()?
+        .trim_end_matches('.')
+        .to_lowercase();
+
+    LABEL_CONTEXTS
+        .iter()
+        .find(|(words, _)| words.contains(&word.as_str()))
+        .map(|(_, kind)| *kind)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::feature::FeatureTester;
+    use indoc::indoc;
+
+    #[tokio::test]
+    async fn frequency() {
+        let req = FeatureTester::new()
+            .file(
+                "main.tex",
+                indoc!(
+                    r#"
+                        \foo \foo \bar
+                        \
+                    "#
+                ),
+            )
+            .main("main.tex")
+            .position(1, 1)
+            .test_completion_request()
+            .await;
+
+        let ranking = Ranking::analyze(&req);
+
+        let foo = ranking.bonus(&ItemData::UserCommand {
+            name: "foo",
+            arg_count: 0,
+        });
+        let bar = ranking.bonus(&ItemData::UserCommand {
+            name: "bar",
+            arg_count: 0,
+        });
+        let baz = ranking.bonus(&ItemData::UserCommand {
+            name: "baz",
+            arg_count: 0,
+        });
+        assert!(foo > bar);
+        assert!(bar > baz);
+        assert_eq!(baz, 0);
+    }
+
+    #[tokio::test]
+    async fn recency() {
+        let req = FeatureTester::new()
+            .file(
+                "main.tex",
+                indoc!(
+                    r#"
+                        \begin{foo}\end{foo}
+
+
+
+                        \begin{bar}\end{bar}
+                        \begin{}
+                    "#
+                ),
+            )
+            .main("main.tex")
+            .position(5, 7)
+            .test_completion_request()
+            .await;
+
+        let ranking = Ranking::analyze(&req);
+
+        let foo = ranking.bonus(&ItemData::UserEnvironment { name: "foo" });
+        let bar = ranking.bonus(&ItemData::UserEnvironment { name: "bar" });
+        assert!(bar > foo);
+    }
+
+    #[tokio::test]
+    async fn expected_label_kind_float() {
+        let req = FeatureTester::new()
+            .file("main.tex", "see Figure~\\ref{}")
+            .main("main.tex")
+            .position(0, 16)
+            .test_completion_request()
+            .await;
+
+        assert_eq!(expected_label_kind(&req), Some(Structure::Float));
+    }
+
+    #[tokio::test]
+    async fn expected_label_kind_none() {
+        let req = FeatureTester::new()
+            .file("main.tex", "see \\ref{}")
+            .main("main.tex")
+            .position(0, 9)
+            .test_completion_request()
+            .await;
+
+        assert_eq!(expected_label_kind(&req), None);
+    }
+
+    #[tokio::test]
+    async fn expected_label_kind_eqref() {
+        let req = FeatureTester::new()
+            .file("main.tex", "see \\eqref{}")
+            .main("main.tex")
+            .position(0, 11)
+            .test_completion_request()
+            .await;
+
+        assert_eq!(expected_label_kind(&req), Some(Structure::Equation));
+    }
+
+    #[tokio::test]
+    async fn label_prefix() {
+        let req = FeatureTester::new()
+            .file("main.tex", "Figure~\\ref{}")
+            .main("main.tex")
+            .position(0, 12)
+            .test_completion_request()
+            .await;
+
+        let ranking = Ranking::analyze(&req);
+
+        let label = |name: &'static str| ItemData::Label {
+            name,
+            kind: Structure::Label,
+            header: None,
+            footer: None,
+            text: name.into(),
+        };
+        assert!(ranking.bonus(&label("fig:foo")) > ranking.bonus(&label("sec:foo")));
+    }
+}
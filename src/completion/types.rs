@@ -31,6 +31,7 @@ pub enum ItemData<'a> {
         image: Option<&'a str>,
         glyph: Option<&'a str>,
         file_names: &'a [String],
+        arg_count: usize,
     },
     ComponentEnvironment {
         name: &'a str,
@@ -38,6 +39,13 @@ pub enum ItemData<'a> {
     },
     UserCommand {
         name: &'a str,
+        arg_count: usize,
+    },
+    Symbol {
+        name: &'a str,
+        glyph: &'a str,
+        text: String,
+        file_names: &'a [String],
     },
     UserEnvironment {
         name: &'a str,
@@ -49,6 +57,10 @@ pub enum ItemData<'a> {
         footer: Option<String>,
         text: String,
     },
+    LabelSuggestion {
+        name: String,
+        kind: Structure,
+    },
     PgfLibrary {
         name: &'a str,
     },
@@ -61,6 +73,26 @@ pub enum ItemData<'a> {
     Class {
         name: Cow<'a, str>,
     },
+    BibliographyStyle {
+        name: String,
+        package: Option<String>,
+    },
+    PackageOption {
+        name: &'a str,
+        file_name: &'a str,
+    },
+    ClassOption {
+        name: &'a str,
+        file_name: &'a str,
+    },
+    OptionKey {
+        name: &'a str,
+        key_set: &'a str,
+    },
+    OptionValue {
+        name: Cow<'a, str>,
+        key: &'a str,
+    },
     File {
         name: String,
     },
@@ -70,8 +102,10 @@ pub enum ItemData<'a> {
     Citation {
         uri: &'a Uri,
         key: &'a str,
+        label: String,
         text: String,
         ty: Structure,
+        import: Option<&'a Uri>,
     },
     Argument {
         name: &'a str,
@@ -93,6 +127,17 @@ pub enum ItemData<'a> {
     Field {
         field: &'a BibtexFieldDoc,
     },
+    FieldValue {
+        name: &'a str,
+        field: &'a str,
+    },
+    BibtexString {
+        name: &'a str,
+        value: String,
+    },
+    AuthorName {
+        name: String,
+    },
 }
 
 impl<'a> ItemData<'a> {
@@ -100,11 +145,18 @@ impl<'a> ItemData<'a> {
         match self {
             Self::ComponentCommand { name, .. } => name,
             Self::ComponentEnvironment { name, .. } => name,
-            Self::UserCommand { name } => name,
+            Self::UserCommand { name, .. } => name,
+            Self::Symbol { name, .. } => name,
             Self::UserEnvironment { name } => name,
             Self::Label { name, .. } => name,
+            Self::LabelSuggestion { name, .. } => &name,
             Self::Class { name } => &name,
             Self::Package { name } => &name,
+            Self::BibliographyStyle { name, .. } => &name,
+            Self::PackageOption { name, .. } => name,
+            Self::ClassOption { name, .. } => name,
+            Self::OptionKey { name, .. } => name,
+            Self::OptionValue { name, .. } => &name,
             Self::PgfLibrary { name } => name,
             Self::TikzLibrary { name } => name,
             Self::File { name } => &name,
@@ -117,6 +169,9 @@ impl<'a> ItemData<'a> {
             Self::GlossaryEntry { name } => name,
             Self::EntryType { ty } => &ty.name,
             Self::Field { field } => &field.name,
+            Self::FieldValue { name, .. } => name,
+            Self::BibtexString { name, .. } => name,
+            Self::AuthorName { name } => &name,
         }
     }
 }
@@ -136,8 +191,16 @@ pub enum CompletionItemData {
     ColorModel,
     Package,
     Class,
+    BibliographyStyle,
+    PackageOption,
+    ClassOption,
+    OptionKey,
+    OptionValue,
     EntryType,
     FieldName,
+    FieldValue,
+    BibtexString,
+    AuthorName,
     Citation { uri: Uri, key: String },
     Argument,
     GlossaryEntry,
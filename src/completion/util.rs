@@ -2,7 +2,7 @@ use crate::{
     feature::FeatureRequest,
     protocol::{
         CompletionItemKind, CompletionParams, Documentation, MarkupContent, MarkupKind, Position,
-        RangeExt,
+        Range, RangeExt,
     },
     syntax::{bibtex, latex, SyntaxNode},
     workspace::DocumentContent,
@@ -24,7 +24,7 @@ pub fn current_word(req: &FeatureRequest<CompletionParams>) -> Option<Cow<str>>
                     .words
                     .iter()
                     .find(|word| word.range().contains(pos))
-                    .map(|word| word.text().split('/').last().unwrap().to_owned().into()),
+                    .map(|word| word_query(word, pos).into()),
                 latex::Node::Comma(_) => Some(",".into()),
                 latex::Node::Math(math) => Some(math.token.text().to_owned().into()),
             }
@@ -61,6 +61,36 @@ pub fn current_word(req: &FeatureRequest<CompletionParams>) -> Option<Cow<str>>
     }
 }
 
+pub fn is_math_mode(req: &FeatureRequest<CompletionParams>) -> bool {
+    let pos = req.params.text_document_position.position;
+    match &req.current().content {
+        DocumentContent::Latex(table) => latex::MathMode::analyze(table).contains(pos),
+        DocumentContent::Bibtex(_) => false,
+    }
+}
+
+/// Returns the part of the word that is used for filtering,
+/// e.g. the file name of a path or the value of a key-value pair.
+fn word_query(word: &latex::Token, pos: Position) -> String {
+    let offset = (pos.character - word.start().character) as usize;
+    let start = word
+        .text()
+        .chars()
+        .take(offset)
+        .enumerate()
+        .filter(|(_, c)| *c == '=')
+        .last()
+        .map_or(0, |(i, _)| i + 1);
+
+    let text: String = word
+        .text()
+        .chars()
+        .skip(start)
+        .take_while(|c| *c != '=')
+        .collect();
+    text.split('/').last().unwrap().to_owned()
+}
+
 fn command_word(cmd: &latex::Command) -> Cow<str> {
     cmd.name.text()[1..].into()
 }
@@ -101,6 +131,84 @@ fn supports_images(req: &FeatureRequest<CompletionParams>) -> bool {
         .map_or(true, |formats| formats.contains(&MarkupKind::Markdown))
 }
 
+pub fn supports_snippets(req: &FeatureRequest<CompletionParams>) -> bool {
+    req.client_capabilities
+        .text_document
+        .as_ref()
+        .and_then(|cap| cap.completion.as_ref())
+        .and_then(|cap| cap.completion_item.as_ref())
+        .and_then(|cap| cap.snippet_support)
+        .unwrap_or(false)
+}
+
+/// Creates a snippet that inserts the command together with a tab stop for each argument.
+/// Returns `None` if the command at the cursor already has arguments.
+pub fn command_snippet(
+    req: &FeatureRequest<CompletionParams>,
+    name: &str,
+    arg_count: usize,
+) -> Option<String> {
+    if arg_count == 0 || !supports_snippets(req) {
+        return None;
+    }
+
+    let table = req.current().content.as_latex()?;
+    let pos = req.params.text_document_position.position;
+    let node = table.find_command_by_short_name_range(pos)?;
+    if table.children(node).next().is_some() {
+        return None;
+    }
+
+    let mut snippet = name.to_owned();
+    for i in 1..=arg_count {
+        snippet.push_str(&format!("{{${}}}", i));
+    }
+    Some(snippet)
+}
+
+/// Returns the range that is replaced when expanding `\begin{name}` into a full environment.
+/// Environments that have already been closed are not expanded.
+pub fn environment_snippet_range(
+    req: &FeatureRequest<CompletionParams>,
+    range: Range,
+) -> Option<Range> {
+    if !supports_snippets(req) {
+        return None;
+    }
+
+    let table = req.current().content.as_latex()?;
+    let pos = req.params.text_document_position.position;
+    let node = table
+        .find(pos)
+        .into_iter()
+        .rev()
+        .find(|node| table.as_command(*node).is_some())?;
+
+    if table.as_command(node)?.name.text() != "\\begin" {
+        return None;
+    }
+
+    let is_closed = table
+        .environments
+        .iter()
+        .filter(|env| env.left.parent == node)
+        .any(|env| {
+            env.left.name(&table.tree).map(latex::Token::text)
+                == env.right.name(&table.tree).map(latex::Token::text)
+        });
+    if is_closed {
+        return None;
+    }
+
+    let group = table.extract_group(node, latex::GroupKind::Group, 0)?;
+    let end = table
+        .as_group(group)?
+        .right
+        .as_ref()
+        .map_or(range.end, |right| right.end());
+    Some(Range::new(range.start, end))
+}
+
 pub fn adjust_kind(
     req: &FeatureRequest<CompletionParams>,
     kind: CompletionItemKind,
@@ -119,3 +227,115 @@ pub fn adjust_kind(
     }
     CompletionItemKind::Text
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        feature::FeatureTester,
+        protocol::{
+            ClientCapabilities, CompletionCapability, CompletionItemCapability,
+            TextDocumentClientCapabilities,
+        },
+    };
+
+    fn snippet_capabilities() -> ClientCapabilities {
+        ClientCapabilities {
+            text_document: Some(TextDocumentClientCapabilities {
+                completion: Some(CompletionCapability {
+                    completion_item: Some(CompletionItemCapability {
+                        snippet_support: Some(true),
+                        ..CompletionItemCapability::default()
+                    }),
+                    ..CompletionCapability::default()
+                }),
+                ..TextDocumentClientCapabilities::default()
+            }),
+            ..ClientCapabilities::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn current_word_key_value() {
+        let req = FeatureTester::new()
+            .file("main.tex", "\\geometry{margin=2c}")
+            .main("main.tex")
+            .position(0, 19)
+            .test_completion_request()
+            .await;
+
+        assert_eq!(current_word(&req), Some("2c".into()));
+    }
+
+    #[tokio::test]
+    async fn command_snippet_arguments() {
+        let req = FeatureTester::new()
+            .file("main.tex", "\\fra")
+            .main("main.tex")
+            .position(0, 4)
+            .client_capabilities(snippet_capabilities())
+            .test_completion_request()
+            .await;
+
+        assert_eq!(
+            command_snippet(&req, "frac", 2),
+            Some("frac{$1}{$2}".into())
+        );
+    }
+
+    #[tokio::test]
+    async fn command_snippet_existing_arguments() {
+        let req = FeatureTester::new()
+            .file("main.tex", "\\fra{a}{b}")
+            .main("main.tex")
+            .position(0, 4)
+            .client_capabilities(snippet_capabilities())
+            .test_completion_request()
+            .await;
+
+        assert_eq!(command_snippet(&req, "frac", 2), None);
+    }
+
+    #[tokio::test]
+    async fn command_snippet_unsupported() {
+        let req = FeatureTester::new()
+            .file("main.tex", "\\fra")
+            .main("main.tex")
+            .position(0, 4)
+            .test_completion_request()
+            .await;
+
+        assert_eq!(command_snippet(&req, "frac", 2), None);
+    }
+
+    #[tokio::test]
+    async fn environment_snippet_unclosed() {
+        let req = FeatureTester::new()
+            .file("main.tex", "\\begin{ite}")
+            .main("main.tex")
+            .position(0, 10)
+            .client_capabilities(snippet_capabilities())
+            .test_completion_request()
+            .await;
+
+        let range = Range::new_simple(0, 7, 0, 10);
+        assert_eq!(
+            environment_snippet_range(&req, range),
+            Some(Range::new_simple(0, 7, 0, 11))
+        );
+    }
+
+    #[tokio::test]
+    async fn environment_snippet_closed() {
+        let req = FeatureTester::new()
+            .file("main.tex", "\\begin{itemize}\n\\end{itemize}")
+            .main("main.tex")
+            .position(0, 10)
+            .client_capabilities(snippet_capabilities())
+            .test_completion_request()
+            .await;
+
+        let range = Range::new_simple(0, 7, 0, 14);
+        assert_eq!(environment_snippet_range(&req, range), None);
+    }
+}
@@ -1,6 +1,10 @@
-use crate::protocol::{MarkupContent, MarkupKind};
+use crate::{
+    protocol::{MarkupContent, MarkupKind},
+    syntax::{LatexCommandMode, LANGUAGE_DATA},
+};
 use once_cell::sync::Lazy;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 
 #[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -59,6 +63,8 @@ pub struct Component {
     pub references: Vec<String>,
     pub commands: Vec<Command>,
     pub environments: Vec<String>,
+    #[serde(default)]
+    pub options: Vec<String>,
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
@@ -70,6 +76,19 @@ pub struct Command {
     pub parameters: Vec<Parameter>,
 }
 
+impl Command {
+    /// Returns the code that inserts this command in the given mode,
+    /// e.g. `$\leq$` for a math command inside of text.
+    pub fn symbol_text(&self, is_math_mode: bool) -> String {
+        let mode = LANGUAGE_DATA.command_mode(&format!("\\{}", self.name));
+        match mode {
+            Some(LatexCommandMode::Math) if !is_math_mode => format!("$\\{}$", self.name),
+            Some(LatexCommandMode::Text) if is_math_mode => format!("\\text{{\\{}}}", self.name),
+            _ => format!("\\{}", self.name),
+        }
+    }
+}
+
 #[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Parameter(pub Vec<Argument>);
@@ -91,4 +110,20 @@ pub struct Metadata {
 
 const JSON: &str = include_str!("../data/components.json");
 
-pub static COMPONENT_DATABASE: Lazy<Database> = Lazy::new(|| serde_json::from_str(JSON).unwrap());
+const OPTIONS_JSON: &str = include_str!("../data/component_options.json");
+
+pub static COMPONENT_DATABASE: Lazy<Database> = Lazy::new(|| {
+    let mut database: Database = serde_json::from_str(JSON).unwrap();
+    let options_by_file_name: HashMap<String, Vec<String>> =
+        serde_json::from_str(OPTIONS_JSON).unwrap();
+    for component in &mut database.components {
+        for file_name in &component.file_names {
+            for option in options_by_file_name.get(file_name).into_iter().flatten() {
+                if !component.options.contains(option) {
+                    component.options.push(option.clone());
+                }
+            }
+        }
+    }
+    database
+});
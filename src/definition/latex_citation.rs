@@ -1,6 +1,6 @@
 use crate::{
     feature::{FeatureProvider, FeatureRequest},
-    protocol::{LocationLink, RangeExt, TextDocumentPositionParams},
+    protocol::{LocationLink, Range, RangeExt, TextDocumentPositionParams},
     syntax::{latex, SyntaxNode},
     workspace::{Document, DocumentContent},
 };
@@ -37,22 +37,40 @@ impl LatexCitationDefinitionProvider {
     }
 
     fn find_definitions(doc: &Document, reference: &latex::Token, links: &mut Vec<LocationLink>) {
-        if let DocumentContent::Bibtex(tree) = &doc.content {
-            for entry in tree
-                .children(tree.root)
-                .filter_map(|node| tree.as_entry(node))
-            {
-                if let Some(key) = &entry.key {
+        match &doc.content {
+            DocumentContent::Latex(table) => {
+                for bib_item in &table.bib_items {
+                    let key = bib_item.key(&table.tree);
                     if key.text() == reference.text() {
                         links.push(LocationLink {
                             origin_selection_range: Some(reference.range()),
                             target_uri: doc.uri.clone().into(),
-                            target_range: entry.range(),
+                            target_range: Range::new(
+                                table[bib_item.parent].start(),
+                                bib_item.body.end,
+                            ),
                             target_selection_range: key.range(),
                         });
                     }
                 }
             }
+            DocumentContent::Bibtex(tree) => {
+                for entry in tree
+                    .children(tree.root)
+                    .filter_map(|node| tree.as_entry(node))
+                {
+                    if let Some(key) = &entry.key {
+                        if key.text() == reference.text() {
+                            links.push(LocationLink {
+                                origin_selection_range: Some(reference.range()),
+                                target_uri: doc.uri.clone().into(),
+                                target_range: entry.range(),
+                                target_selection_range: key.range(),
+                            });
+                        }
+                    }
+                }
+            }
         }
     }
 }
@@ -60,7 +78,7 @@ impl LatexCitationDefinitionProvider {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::{feature::FeatureTester, protocol::Range};
+    use crate::feature::FeatureTester;
     use indoc::indoc;
 
     #[tokio::test]
@@ -115,4 +133,33 @@ mod tests {
 
         assert_eq!(actual_links, exepcted_links);
     }
+
+    #[tokio::test]
+    async fn bib_item() {
+        let actual_links = FeatureTester::new()
+            .file("main.tex", r#"\cite{foo}"#)
+            .file(
+                "main.bbl",
+                indoc!(
+                    r#"
+                        \begin{thebibliography}{1}
+                        \bibitem{foo} Foo
+                        \end{thebibliography}
+                    "#
+                ),
+            )
+            .main("main.tex")
+            .position(0, 6)
+            .test_position(LatexCitationDefinitionProvider)
+            .await;
+
+        let exepcted_links = vec![LocationLink {
+            origin_selection_range: Some(Range::new_simple(0, 6, 0, 9)),
+            target_uri: FeatureTester::uri("main.bbl").into(),
+            target_range: Range::new_simple(1, 0, 2, 0),
+            target_selection_range: Range::new_simple(1, 9, 1, 12),
+        }];
+
+        assert_eq!(actual_links, exepcted_links);
+    }
 }
@@ -0,0 +1,147 @@
+use crate::{
+    feature::{FeatureProvider, FeatureRequest},
+    protocol::{LocationLink, RangeExt, TextDocumentPositionParams},
+    syntax::{latex, SyntaxNode},
+    workspace::DocumentContent,
+};
+use async_trait::async_trait;
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub struct LatexEnvironmentDefinitionProvider;
+
+#[async_trait]
+impl FeatureProvider for LatexEnvironmentDefinitionProvider {
+    type Params = TextDocumentPositionParams;
+    type Output = Vec<LocationLink>;
+
+    async fn execute<'a>(&'a self, req: &'a FeatureRequest<Self::Params>) -> Self::Output {
+        let mut links = Vec::new();
+        if let Some(reference) = Self::find_reference(req) {
+            for doc in req.related() {
+                if let DocumentContent::Latex(table) = &doc.content {
+                    let environments = table
+                        .environment_definitions
+                        .iter()
+                        .map(|def| (def.parent, def.name(&table)));
+
+                    let theorems = table
+                        .theorem_definitions
+                        .iter()
+                        .map(|def| (def.parent, def.name(&table)));
+
+                    environments
+                        .chain(theorems)
+                        .filter(|(_, name)| name.text() == reference.text())
+                        .map(|(parent, name)| LocationLink {
+                            origin_selection_range: Some(reference.range()),
+                            target_uri: doc.uri.clone().into(),
+                            target_range: table[parent].range(),
+                            target_selection_range: name.range(),
+                        })
+                        .for_each(|link| links.push(link));
+                }
+            }
+        }
+        links
+    }
+}
+
+impl LatexEnvironmentDefinitionProvider {
+    fn find_reference(req: &FeatureRequest<TextDocumentPositionParams>) -> Option<&latex::Token> {
+        req.current().content.as_latex().and_then(|table| {
+            table
+                .environments
+                .iter()
+                .flat_map(|env| vec![env.left, env.right])
+                .filter_map(|delim| delim.name(&table))
+                .find(|name| name.range().contains(req.params.position))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{feature::FeatureTester, protocol::Range};
+    use indoc::indoc;
+
+    #[tokio::test]
+    async fn empty_latex_document() {
+        let actual_links = FeatureTester::new()
+            .file("main.tex", "")
+            .main("main.tex")
+            .position(0, 0)
+            .test_position(LatexEnvironmentDefinitionProvider)
+            .await;
+
+        assert!(actual_links.is_empty());
+    }
+
+    #[tokio::test]
+    async fn empty_bibtex_document() {
+        let actual_links = FeatureTester::new()
+            .file("main.bib", "")
+            .main("main.bib")
+            .position(0, 0)
+            .test_position(LatexEnvironmentDefinitionProvider)
+            .await;
+
+        assert!(actual_links.is_empty());
+    }
+
+    #[tokio::test]
+    async fn environment_definition() {
+        let actual_links = FeatureTester::new()
+            .file(
+                "foo.tex",
+                indoc!(
+                    r#"
+                        \include{bar}
+                        \begin{foo}\end{foo}
+                    "#
+                ),
+            )
+            .file("bar.tex", r#"\newenvironment{foo}{}{}"#)
+            .file("baz.tex", r#"\newenvironment{foo}{}{}"#)
+            .main("foo.tex")
+            .position(1, 17)
+            .test_position(LatexEnvironmentDefinitionProvider)
+            .await;
+
+        let expected_links = vec![LocationLink {
+            origin_selection_range: Some(Range::new_simple(1, 16, 1, 19)),
+            target_uri: FeatureTester::uri("bar.tex").into(),
+            target_range: Range::new_simple(0, 0, 0, 24),
+            target_selection_range: Range::new_simple(0, 16, 0, 19),
+        }];
+
+        assert_eq!(actual_links, expected_links);
+    }
+
+    #[tokio::test]
+    async fn theorem_definition() {
+        let actual_links = FeatureTester::new()
+            .file(
+                "foo.tex",
+                indoc!(
+                    r#"
+                        \newtheorem{lemma}{Lemma}
+                        \begin{lemma}\end{lemma}
+                    "#
+                ),
+            )
+            .main("foo.tex")
+            .position(1, 9)
+            .test_position(LatexEnvironmentDefinitionProvider)
+            .await;
+
+        let expected_links = vec![LocationLink {
+            origin_selection_range: Some(Range::new_simple(1, 7, 1, 12)),
+            target_uri: FeatureTester::uri("foo.tex").into(),
+            target_range: Range::new_simple(0, 0, 0, 25),
+            target_selection_range: Range::new_simple(0, 12, 0, 17),
+        }];
+
+        assert_eq!(actual_links, expected_links);
+    }
+}
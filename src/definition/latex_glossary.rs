@@ -0,0 +1,132 @@
+use crate::{
+    feature::{FeatureProvider, FeatureRequest},
+    protocol::{LocationLink, RangeExt, TextDocumentPositionParams},
+    syntax::{latex, SyntaxNode, LANGUAGE_DATA},
+    workspace::DocumentContent,
+};
+use async_trait::async_trait;
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub struct LatexGlossaryDefinitionProvider;
+
+#[async_trait]
+impl FeatureProvider for LatexGlossaryDefinitionProvider {
+    type Params = TextDocumentPositionParams;
+    type Output = Vec<LocationLink>;
+
+    async fn execute<'a>(&'a self, req: &'a FeatureRequest<Self::Params>) -> Self::Output {
+        let mut links = Vec::new();
+        if let Some(reference) = Self::find_reference(req) {
+            for doc in req.related() {
+                if let DocumentContent::Latex(table) = &doc.content {
+                    table
+                        .glossary_entries
+                        .iter()
+                        .filter(|entry| entry.label(&table).text() == reference.text())
+                        .map(|entry| LocationLink {
+                            origin_selection_range: Some(reference.range()),
+                            target_uri: doc.uri.clone().into(),
+                            target_range: table[entry.parent].range(),
+                            target_selection_range: entry.label(&table).range(),
+                        })
+                        .for_each(|link| links.push(link));
+                }
+            }
+        }
+        links
+    }
+}
+
+impl LatexGlossaryDefinitionProvider {
+    fn find_reference(req: &FeatureRequest<TextDocumentPositionParams>) -> Option<&latex::Token> {
+        let table = req.current().content.as_latex()?;
+        table.commands.iter().find_map(|node| {
+            let cmd = table.as_command(*node)?;
+            let desc = LANGUAGE_DATA
+                .glossary_entry_reference_commands
+                .iter()
+                .find(|desc| desc.name == cmd.name.text())?;
+
+            table
+                .extract_word(*node, latex::GroupKind::Group, desc.index)
+                .filter(|label| label.range().contains(req.params.position))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{feature::FeatureTester, protocol::Range};
+    use indoc::indoc;
+
+    #[tokio::test]
+    async fn empty_latex_document() {
+        let actual_links = FeatureTester::new()
+            .file("main.tex", "")
+            .main("main.tex")
+            .position(0, 0)
+            .test_position(LatexGlossaryDefinitionProvider)
+            .await;
+
+        assert!(actual_links.is_empty());
+    }
+
+    #[tokio::test]
+    async fn acronym() {
+        let actual_links = FeatureTester::new()
+            .file(
+                "foo.tex",
+                indoc!(
+                    r#"
+                        \include{bar}
+                        \acrshort{api}
+                    "#
+                ),
+            )
+            .file(
+                "bar.tex",
+                r#"\newacronym{api}{API}{Application Programming Interface}"#,
+            )
+            .main("foo.tex")
+            .position(1, 11)
+            .test_position(LatexGlossaryDefinitionProvider)
+            .await;
+
+        let expected_links = vec![LocationLink {
+            origin_selection_range: Some(Range::new_simple(1, 10, 1, 13)),
+            target_uri: FeatureTester::uri("bar.tex").into(),
+            target_range: Range::new_simple(0, 0, 0, 56),
+            target_selection_range: Range::new_simple(0, 12, 0, 15),
+        }];
+
+        assert_eq!(actual_links, expected_links);
+    }
+
+    #[tokio::test]
+    async fn glossary_entry() {
+        let actual_links = FeatureTester::new()
+            .file(
+                "foo.tex",
+                indoc!(
+                    r#"
+                        \newglossaryentry{foo}{name=foo}
+                        \gls{foo}
+                    "#
+                ),
+            )
+            .main("foo.tex")
+            .position(1, 6)
+            .test_position(LatexGlossaryDefinitionProvider)
+            .await;
+
+        let expected_links = vec![LocationLink {
+            origin_selection_range: Some(Range::new_simple(1, 5, 1, 8)),
+            target_uri: FeatureTester::uri("foo.tex").into(),
+            target_range: Range::new_simple(0, 0, 0, 32),
+            target_selection_range: Range::new_simple(0, 18, 0, 21),
+        }];
+
+        assert_eq!(actual_links, expected_links);
+    }
+}
@@ -0,0 +1,473 @@
+use crate::{
+    components::{Component, COMPONENT_DATABASE},
+    feature::{FeatureProvider, FeatureRequest},
+    protocol::{LocationLink, Position, Range, RangeExt, TextDocumentPositionParams, Uri},
+    syntax::{latex, AstNodeIndex, LatexIncludeKind, SyntaxNode, LANGUAGE_DATA},
+    tex::Resolver,
+    workspace::DocumentContent,
+};
+use async_trait::async_trait;
+use futures::lock::Mutex;
+use std::{
+    collections::{HashMap, HashSet, VecDeque},
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+use tokio::{fs, task};
+
+#[derive(Debug, Default)]
+pub struct LatexPackageDefinitionProvider {
+    cache: Mutex<TableCache>,
+}
+
+/// The parsed packages of the distribution.
+/// The cache is cleared once the distribution is reloaded.
+#[derive(Debug, Default)]
+struct TableCache {
+    resolver: Option<Arc<Resolver>>,
+    tables_by_path: HashMap<PathBuf, Arc<latex::SymbolTable>>,
+}
+
+#[async_trait]
+impl FeatureProvider for LatexPackageDefinitionProvider {
+    type Params = TextDocumentPositionParams;
+    type Output = Vec<LocationLink>;
+
+    async fn execute<'a>(&'a self, req: &'a FeatureRequest<Self::Params>) -> Self::Output {
+        let mut links = Vec::new();
+        let table = match req.current().content.as_latex() {
+            Some(table) => table,
+            None => return links,
+        };
+
+        let resolver = req.distro.resolver().await;
+        if let Some((path, file_name)) = Self::find_component(table, req.params.position) {
+            if let Some(uri) = Self::resolve(&resolver, &file_name) {
+                links.push(LocationLink {
+                    origin_selection_range: Some(path.range()),
+                    target_uri: uri.into(),
+                    target_range: Range::new_simple(0, 0, 0, 0),
+                    target_selection_range: Range::new_simple(0, 0, 0, 0),
+                });
+            }
+        } else if let Some(cmd) = table
+            .find(req.params.position)
+            .last()
+            .and_then(|node| table.as_command(*node))
+        {
+            if Self::is_user_defined(req, cmd.name.text()) {
+                return links;
+            }
+
+            let mut file_names = Self::candidate_file_names(req, &cmd.name.text()[1..]);
+            file_names.sort();
+            file_names.dedup();
+
+            self.find_definitions(req, &resolver, file_names, cmd, &mut links)
+                .await;
+        }
+        links
+    }
+}
+
+impl LatexPackageDefinitionProvider {
+    fn find_component(
+        table: &latex::SymbolTable,
+        pos: Position,
+    ) -> Option<(&latex::Token, String)> {
+        for include in &table.includes {
+            let extension = match include.kind {
+                LatexIncludeKind::Package => "sty",
+                LatexIncludeKind::Class => "cls",
+                _ => continue,
+            };
+
+            for path in include.paths(&table) {
+                if path.range().contains(pos) {
+                    return Some((path, format!("{}.{}", path.text(), extension)));
+                }
+            }
+        }
+        None
+    }
+
+    fn resolve(resolver: &Resolver, file_name: &str) -> Option<Uri> {
+        let path = resolver.files_by_name.get(file_name)?;
+        Uri::from_file_path(path).ok()
+    }
+
+    fn is_user_defined(req: &FeatureRequest<TextDocumentPositionParams>, name: &str) -> bool {
+        req.related()
+            .iter()
+            .filter_map(|doc| doc.content.as_latex())
+            .any(|table| {
+                table
+                    .command_definitions
+                    .iter()
+                    .any(|def| def.definition_name(&table) == name)
+                    || table
+                        .math_operators
+                        .iter()
+                        .any(|op| op.definition_name(&table) == name)
+            })
+    }
+
+    /// Returns the packages that may define the command with the given name (without the backslash).
+    /// If the component database knows the command, only the loaded packages that provide it
+    /// are searched. Otherwise, all loaded packages are searched.
+    fn candidate_file_names<'a>(
+        req: &'a FeatureRequest<TextDocumentPositionParams>,
+        name: &str,
+    ) -> Vec<&'a String> {
+        let provides = |comp: &Component| comp.commands.iter().any(|cmd| cmd.name == name);
+        if COMPONENT_DATABASE.components.iter().any(provides) {
+            req.view
+                .components()
+                .into_iter()
+                .filter(|comp| provides(comp))
+                .flat_map(|comp| comp.file_names.iter())
+                .collect()
+        } else {
+            req.related()
+                .iter()
+                .filter_map(|doc| doc.content.as_latex())
+                .flat_map(|table| table.components.iter())
+                .collect()
+        }
+    }
+
+    /// Searches the given packages for definitions of the referenced command.
+    /// The packages that are loaded by these packages are searched as well
+    /// because they may provide the command indirectly.
+    async fn find_definitions(
+        &self,
+        req: &FeatureRequest<TextDocumentPositionParams>,
+        resolver: &Arc<Resolver>,
+        file_names: Vec<&String>,
+        reference: &latex::Command,
+        links: &mut Vec<LocationLink>,
+    ) {
+        let mut visited = HashSet::new();
+        let mut queue: VecDeque<_> = file_names
+            .into_iter()
+            .filter_map(|file_name| resolver.files_by_name.get(file_name))
+            .cloned()
+            .collect();
+
+        while let Some(path) = queue.pop_front() {
+            if !visited.insert(path.clone()) {
+                continue;
+            }
+
+            let (uri, table) = match self.load_table(req, resolver, &path).await {
+                Some(result) => result,
+                None => continue,
+            };
+
+            for node in &table.commands {
+                let cmd = table.as_command(*node).unwrap();
+                if !LANGUAGE_DATA.is_definition_command(cmd.name.text()) {
+                    continue;
+                }
+
+                if let Some((def, target_range)) = Self::defined_command(&table, *node) {
+                    if def.name.text() == reference.name.text() {
+                        links.push(LocationLink {
+                            origin_selection_range: Some(reference.name.range()),
+                            target_uri: uri.clone().into(),
+                            target_range,
+                            target_selection_range: def.name.range(),
+                        });
+                    }
+                }
+            }
+
+            queue.extend(
+                table
+                    .components
+                    .iter()
+                    .filter_map(|file_name| resolver.files_by_name.get(file_name))
+                    .cloned(),
+            );
+        }
+    }
+
+    /// Parses the package at the given path on the blocking thread pool.
+    /// The symbol tables are cached because the files of the distribution rarely change.
+    async fn load_table(
+        &self,
+        req: &FeatureRequest<TextDocumentPositionParams>,
+        resolver: &Arc<Resolver>,
+        path: &Path,
+    ) -> Option<(Uri, Arc<latex::SymbolTable>)> {
+        let uri = Uri::from_file_path(path).ok()?;
+        {
+            let mut cache = self.cache.lock().await;
+            if !cache
+                .resolver
+                .as_ref()
+                .map_or(false, |other| Arc::ptr_eq(other, resolver))
+            {
+                cache.resolver = Some(Arc::clone(resolver));
+                cache.tables_by_path.clear();
+            }
+
+            if let Some(table) = cache.tables_by_path.get(path) {
+                return Some((uri, Arc::clone(table)));
+            }
+        }
+
+        let text = fs::read_to_string(path).await.ok()?;
+        let table = {
+            let uri = uri.clone();
+            let resolver = Arc::clone(resolver);
+            let options = req.options.clone();
+            let current_dir = Arc::clone(&req.current_dir);
+            task::spawn_blocking(move || {
+                latex::open(latex::OpenParams {
+                    text: &text,
+                    uri: &uri,
+                    resolver: &resolver,
+                    options: &options,
+                    current_dir: &current_dir,
+                })
+            })
+            .await
+            .ok()
+            .map(Arc::new)?
+        };
+
+        self.cache
+            .lock()
+            .await
+            .tables_by_path
+            .insert(path.to_owned(), Arc::clone(&table));
+        Some((uri, table))
+    }
+
+    /// Returns the command that is defined by the given definition command together with the range
+    /// of the definition, e.g. `\foo` in `\newcommand{\foo}{...}` or `\def\foo{...}`.
+    fn defined_command(
+        table: &latex::SymbolTable,
+        node: AstNodeIndex,
+    ) -> Option<(&latex::Command, Range)> {
+        if let Some(group) = table.extract_group(node, latex::GroupKind::Group, 0) {
+            let def = table
+                .children(group)
+                .next()
+                .and_then(|child| table.as_command(child))?;
+            return Some((def, table[node].range()));
+        }
+
+        let end = table[node].end();
+        let def = table
+            .commands
+            .iter()
+            .filter_map(|node| table.as_command(*node))
+            .find(|cmd| cmd.name.start() == end)?;
+        Some((def, Range::new(table[node].start(), def.end())))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::feature::FeatureTester;
+    use indoc::indoc;
+    use std::fs;
+    use tempfile::tempdir;
+
+    const PACKAGE: &str = indoc!(
+        r#"
+            \ProvidesPackage{foo}
+            \def\foo{Foo}
+            \newcommand{\qux}[1]{Qux}
+        "#
+    );
+
+    #[tokio::test]
+    async fn empty_bibtex_document() {
+        let actual_links = FeatureTester::new()
+            .file("main.bib", "")
+            .main("main.bib")
+            .position(0, 0)
+            .test_position(LatexPackageDefinitionProvider::default())
+            .await;
+
+        assert!(actual_links.is_empty());
+    }
+
+    #[tokio::test]
+    async fn package() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("foo.sty");
+        fs::write(&path, PACKAGE).unwrap();
+
+        let actual_links = FeatureTester::new()
+            .file("main.tex", r#"\usepackage{foo}"#)
+            .main("main.tex")
+            .distro_file(&path)
+            .position(0, 13)
+            .test_position(LatexPackageDefinitionProvider::default())
+            .await;
+
+        let expected_links = vec![LocationLink {
+            origin_selection_range: Some(Range::new_simple(0, 12, 0, 15)),
+            target_uri: Uri::from_file_path(&path).unwrap().into(),
+            target_range: Range::new_simple(0, 0, 0, 0),
+            target_selection_range: Range::new_simple(0, 0, 0, 0),
+        }];
+
+        assert_eq!(actual_links, expected_links);
+    }
+
+    #[tokio::test]
+    async fn def() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("foo.sty");
+        fs::write(&path, PACKAGE).unwrap();
+
+        let actual_links = FeatureTester::new()
+            .file(
+                "main.tex",
+                indoc!(
+                    r#"
+                        \usepackage{foo}
+                        \foo \qux
+                    "#
+                ),
+            )
+            .main("main.tex")
+            .distro_file(&path)
+            .position(1, 2)
+            .test_position(LatexPackageDefinitionProvider::default())
+            .await;
+
+        let expected_links = vec![LocationLink {
+            origin_selection_range: Some(Range::new_simple(1, 0, 1, 4)),
+            target_uri: Uri::from_file_path(&path).unwrap().into(),
+            target_range: Range::new_simple(1, 0, 1, 13),
+            target_selection_range: Range::new_simple(1, 4, 1, 8),
+        }];
+
+        assert_eq!(actual_links, expected_links);
+    }
+
+    #[tokio::test]
+    async fn new_command() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("foo.sty");
+        fs::write(&path, PACKAGE).unwrap();
+
+        let actual_links = FeatureTester::new()
+            .file(
+                "main.tex",
+                indoc!(
+                    r#"
+                        \usepackage{foo}
+                        \foo \qux
+                    "#
+                ),
+            )
+            .main("main.tex")
+            .distro_file(&path)
+            .position(1, 7)
+            .test_position(LatexPackageDefinitionProvider::default())
+            .await;
+
+        let expected_links = vec![LocationLink {
+            origin_selection_range: Some(Range::new_simple(1, 5, 1, 9)),
+            target_uri: Uri::from_file_path(&path).unwrap().into(),
+            target_range: Range::new_simple(2, 0, 2, 25),
+            target_selection_range: Range::new_simple(2, 12, 2, 16),
+        }];
+
+        assert_eq!(actual_links, expected_links);
+    }
+
+    #[tokio::test]
+    async fn indirect_package() {
+        let dir = tempdir().unwrap();
+        let path1 = dir.path().join("foo.sty");
+        let path2 = dir.path().join("bar.sty");
+        fs::write(&path1, r#"\RequirePackage{bar}"#).unwrap();
+        fs::write(&path2, r#"\RequirePackage{foo}\def\baz{Baz}"#).unwrap();
+
+        let actual_links = FeatureTester::new()
+            .file(
+                "main.tex",
+                indoc!(
+                    r#"
+                        \usepackage{foo}
+                        \baz
+                    "#
+                ),
+            )
+            .main("main.tex")
+            .distro_file(&path1)
+            .distro_file(&path2)
+            .position(1, 2)
+            .test_position(LatexPackageDefinitionProvider::default())
+            .await;
+
+        let expected_links = vec![LocationLink {
+            origin_selection_range: Some(Range::new_simple(1, 0, 1, 4)),
+            target_uri: Uri::from_file_path(&path2).unwrap().into(),
+            target_range: Range::new_simple(0, 20, 0, 33),
+            target_selection_range: Range::new_simple(0, 24, 0, 28),
+        }];
+
+        assert_eq!(actual_links, expected_links);
+    }
+
+    #[tokio::test]
+    async fn kernel_command() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("foo.sty");
+        fs::write(&path, r#"\def\section{Foo}"#).unwrap();
+
+        let actual_links = FeatureTester::new()
+            .file(
+                "main.tex",
+                indoc!(
+                    r#"
+                        \usepackage{foo}
+                        \section
+                    "#
+                ),
+            )
+            .main("main.tex")
+            .distro_file(&path)
+            .position(1, 2)
+            .test_position(LatexPackageDefinitionProvider::default())
+            .await;
+
+        assert!(actual_links.is_empty());
+    }
+
+    #[tokio::test]
+    async fn user_defined_command() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("foo.sty");
+        fs::write(&path, PACKAGE).unwrap();
+
+        let actual_links = FeatureTester::new()
+            .file(
+                "main.tex",
+                indoc!(
+                    r#"
+                        \usepackage{foo}
+                        \renewcommand{\foo}{Bar}
+                        \foo
+                    "#
+                ),
+            )
+            .main("main.tex")
+            .distro_file(&path)
+            .position(2, 2)
+            .test_position(LatexPackageDefinitionProvider::default())
+            .await;
+
+        assert!(actual_links.is_empty());
+    }
+}
@@ -0,0 +1,130 @@
+use crate::{
+    feature::{FeatureProvider, FeatureRequest},
+    protocol::{LocationLink, RangeExt, TextDocumentPositionParams},
+    syntax::{latex, AstNodeIndex, SyntaxNode},
+    workspace::DocumentContent,
+};
+use async_trait::async_trait;
+
+/// The command that selects the style of the following theorem definitions.
+const REFERENCE_COMMAND: &str = "\\theoremstyle";
+
+/// The command of `amsthm` that declares a new theorem style.
+const DEFINITION_COMMAND: &str = "\\newtheoremstyle";
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub struct LatexTheoremStyleDefinitionProvider;
+
+#[async_trait]
+impl FeatureProvider for LatexTheoremStyleDefinitionProvider {
+    type Params = TextDocumentPositionParams;
+    type Output = Vec<LocationLink>;
+
+    async fn execute<'a>(&'a self, req: &'a FeatureRequest<Self::Params>) -> Self::Output {
+        let mut links = Vec::new();
+        if let Some(reference) = Self::find_reference(req) {
+            for doc in req.related() {
+                if let DocumentContent::Latex(table) = &doc.content {
+                    table
+                        .commands
+                        .iter()
+                        .filter_map(|node| {
+                            Some((*node, find_style(table, *node, DEFINITION_COMMAND)?))
+                        })
+                        .filter(|(_, name)| name.text() == reference.text())
+                        .map(|(node, name)| LocationLink {
+                            origin_selection_range: Some(reference.range()),
+                            target_uri: doc.uri.clone().into(),
+                            target_range: table[node].range(),
+                            target_selection_range: name.range(),
+                        })
+                        .for_each(|link| links.push(link));
+                }
+            }
+        }
+        links
+    }
+}
+
+impl LatexTheoremStyleDefinitionProvider {
+    fn find_reference(req: &FeatureRequest<TextDocumentPositionParams>) -> Option<&latex::Token> {
+        let table = req.current().content.as_latex()?;
+        table.commands.iter().find_map(|node| {
+            find_style(table, *node, REFERENCE_COMMAND)
+                .filter(|name| name.range().contains(req.params.position))
+        })
+    }
+}
+
+fn find_style<'a>(
+    table: &'a latex::SymbolTable,
+    node: AstNodeIndex,
+    command: &str,
+) -> Option<&'a latex::Token> {
+    let cmd = table.as_command(node)?;
+    if cmd.name.text() != command {
+        return None;
+    }
+
+    table.extract_word(node, latex::GroupKind::Group, 0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{feature::FeatureTester, protocol::Range};
+    use indoc::indoc;
+
+    #[tokio::test]
+    async fn empty_latex_document() {
+        let actual_links = FeatureTester::new()
+            .file("main.tex", "")
+            .main("main.tex")
+            .position(0, 0)
+            .test_position(LatexTheoremStyleDefinitionProvider)
+            .await;
+
+        assert!(actual_links.is_empty());
+    }
+
+    #[tokio::test]
+    async fn empty_bibtex_document() {
+        let actual_links = FeatureTester::new()
+            .file("main.bib", "")
+            .main("main.bib")
+            .position(0, 0)
+            .test_position(LatexTheoremStyleDefinitionProvider)
+            .await;
+
+        assert!(actual_links.is_empty());
+    }
+
+    #[tokio::test]
+    async fn theorem_style() {
+        let actual_links = FeatureTester::new()
+            .file(
+                "foo.tex",
+                indoc!(
+                    r#"
+                        \include{bar}
+                        \theoremstyle{note}
+                    "#
+                ),
+            )
+            .file("bar.tex", r#"\newtheoremstyle{note}{}{}{}{}{}{}{}{}"#)
+            .file("baz.tex", r#"\newtheoremstyle{note}{}{}{}{}{}{}{}{}"#)
+            .main("foo.tex")
+            .position(1, 16)
+            .test_position(LatexTheoremStyleDefinitionProvider)
+            .await;
+
+        let expected_links = vec![LocationLink {
+            origin_selection_range: Some(Range::new_simple(1, 14, 1, 18)),
+            target_uri: FeatureTester::uri("bar.tex").into(),
+            target_range: Range::new_simple(0, 0, 0, 38),
+            target_selection_range: Range::new_simple(0, 17, 0, 21),
+        }];
+
+        assert_eq!(actual_links, expected_links);
+    }
+}
@@ -1,11 +1,18 @@
 mod bibtex_string;
 mod latex_citation;
 mod latex_cmd;
+mod latex_env;
+mod latex_glossary;
 mod latex_label;
+mod latex_package;
+mod latex_theorem_style;
 
 use self::{
     bibtex_string::BibtexStringDefinitionProvider, latex_citation::LatexCitationDefinitionProvider,
-    latex_cmd::LatexCommandDefinitionProvider, latex_label::LatexLabelDefinitionProvider,
+    latex_cmd::LatexCommandDefinitionProvider, latex_env::LatexEnvironmentDefinitionProvider,
+    latex_glossary::LatexGlossaryDefinitionProvider, latex_label::LatexLabelDefinitionProvider,
+    latex_package::LatexPackageDefinitionProvider,
+    latex_theorem_style::LatexTheoremStyleDefinitionProvider,
 };
 use crate::{
     feature::{ConcatProvider, FeatureProvider, FeatureRequest},
@@ -24,7 +31,11 @@ impl DefinitionProvider {
                 Box::new(BibtexStringDefinitionProvider),
                 Box::new(LatexCitationDefinitionProvider),
                 Box::new(LatexCommandDefinitionProvider),
+                Box::new(LatexEnvironmentDefinitionProvider),
+                Box::new(LatexGlossaryDefinitionProvider),
                 Box::new(LatexLabelDefinitionProvider),
+                Box::new(LatexPackageDefinitionProvider::default()),
+                Box::new(LatexTheoremStyleDefinitionProvider),
             ]),
         }
     }
@@ -0,0 +1,387 @@
+use super::remove_stale;
+use crate::{
+    protocol::{Diagnostic, DiagnosticSeverity, NumberOrString, Options, Range, RangeExt, Uri},
+    syntax::{latex, LatexIncludeKind, SyntaxNode},
+    workspace::{Document, Relations, Snapshot},
+};
+use chashmap::CHashMap;
+use std::sync::Arc;
+
+const TABLE_ENVIRONMENTS: &[&str] = &["tabular", "tabular*", "tabularx", "tabulary", "longtable"];
+
+/// Commands that mark the end of a header row.
+const HEADER_COMMANDS: &[&str] = &["\\midrule", "\\endhead", "\\endfirsthead", "\\thead"];
+
+const RULE_COMMANDS: &[&str] = &["\\hline", "\\cline"];
+
+const COLOR_COMMANDS: &[&str] = &["\\textcolor", "\\colorbox"];
+
+const EMPHASIS_COMMANDS: &[&str] = &[
+    "\\emph",
+    "\\textbf",
+    "\\textit",
+    "\\textsl",
+    "\\underline",
+    "\\bfseries",
+    "\\itshape",
+    "\\slshape",
+];
+
+#[derive(Debug, Default)]
+pub struct AccessibilityDiagnosticsProvider {
+    diagnostics_by_uri: CHashMap<Uri, Vec<Diagnostic>>,
+}
+
+impl AccessibilityDiagnosticsProvider {
+    pub fn get(&self, doc: &Document) -> Vec<Diagnostic> {
+        match self.diagnostics_by_uri.get(&doc.uri) {
+            Some(diagnostics) => diagnostics.to_owned(),
+            None => Vec::new(),
+        }
+    }
+
+    pub fn update(&self, snapshot: &Snapshot, relations: &Relations, options: &Options) {
+        remove_stale(&self.diagnostics_by_uri, snapshot);
+        let enabled = options
+            .latex
+            .as_ref()
+            .and_then(|opts| opts.lint.as_ref())
+            .map_or(false, |opts| opts.accessibility());
+
+        if !enabled {
+            self.diagnostics_by_uri.clear();
+            return;
+        }
+
+        for doc in &snapshot.0 {
+            let table = match doc.content.as_latex() {
+                Some(table) => table,
+                None => continue,
+            };
+
+            let related = relations.get(&doc.uri);
+            let mut diagnostics = Vec::new();
+            diagnostics.append(&mut missing_alt_text(table));
+            diagnostics.append(&mut missing_metadata(table, related));
+            diagnostics.append(&mut missing_table_headers(table));
+            diagnostics.append(&mut color_only_emphasis(table));
+            self.diagnostics_by_uri.insert(doc.uri.clone(), diagnostics);
+        }
+    }
+}
+
+fn missing_alt_text(table: &latex::SymbolTable) -> Vec<Diagnostic> {
+    let descriptions = find_commands(table, &["\\Description"]);
+    let mut diagnostics = Vec::new();
+    for include in table
+        .includes
+        .iter()
+        .filter(|include| include.kind == LatexIncludeKind::Image)
+    {
+        let cmd = table.as_command(include.parent).unwrap();
+        if cmd.name.text() != "\\includegraphics" {
+            continue;
+        }
+
+        let has_alt_key = table
+            .print_group_content(include.parent, latex::GroupKind::Options, 0)
+            .map_or(false, |options| {
+                option_keys(&options).iter().any(|key| key == "alt")
+            });
+
+        let range = table[include.parent].range();
+        let has_description = table
+            .environments
+            .iter()
+            .map(|env| env.range(&table.tree))
+            .filter(|env| env.contains(range.start))
+            .max_by_key(|env| env.start)
+            .map_or(false, |env| {
+                descriptions
+                    .iter()
+                    .any(|description| env.contains(description.start))
+            });
+
+        if !has_alt_key && !has_description {
+            diagnostics.push(create_diagnostic(
+                range,
+                "missing-alt-text",
+                "The image has no alternative text; add an `alt` key or a `\\Description`".into(),
+            ));
+        }
+    }
+    diagnostics
+}
+
+fn missing_metadata(table: &latex::SymbolTable, related: &[Arc<Document>]) -> Vec<Diagnostic> {
+    let root = match table
+        .environments
+        .iter()
+        .find(|env| env.is_root(&table.tree))
+    {
+        Some(root) => table[root.left.parent].range(),
+        None => return Vec::new(),
+    };
+
+    let tables: Vec<_> = related
+        .iter()
+        .filter_map(|doc| doc.content.as_latex())
+        .collect();
+
+    let mut diagnostics = Vec::new();
+    let has_title = tables
+        .iter()
+        .any(|table| !find_commands(table, &["\\title"]).is_empty());
+    if !has_title {
+        diagnostics.push(create_diagnostic(
+            root,
+            "missing-title",
+            "The document has no `\\title`".into(),
+        ));
+    }
+
+    let has_metadata = tables.iter().any(|table| has_pdf_metadata(table));
+    if !has_metadata {
+        diagnostics.push(create_diagnostic(
+            root,
+            "missing-pdf-metadata",
+            "The document does not set any PDF metadata; use `\\hypersetup{pdftitle=...}` or the `pdfusetitle` option of `hyperref`".into(),
+        ));
+    }
+    diagnostics
+}
+
+fn has_pdf_metadata(table: &latex::SymbolTable) -> bool {
+    table.commands.iter().any(|node| {
+        let cmd = table.as_command(*node).unwrap();
+        let kind = match cmd.name.text() {
+            "\\DocumentMetadata" => return true,
+            "\\hypersetup" => latex::GroupKind::Group,
+            "\\usepackage" => match table.extract_word(*node, latex::GroupKind::Group, 0) {
+                Some(name) if name.text() == "hyperref" => latex::GroupKind::Options,
+                _ => return false,
+            },
+            _ => return false,
+        };
+
+        table
+            .print_group_content(*node, kind, 0)
+            .map_or(false, |options| {
+                option_keys(&options)
+                    .iter()
+                    .any(|key| key == "pdftitle" || key == "pdfusetitle")
+            })
+    })
+}
+
+fn missing_table_headers(table: &latex::SymbolTable) -> Vec<Diagnostic> {
+    let mut diagnostics = Vec::new();
+    for env in &table.environments {
+        let is_table = env
+            .left
+            .name(&table.tree)
+            .map_or(false, |name| TABLE_ENVIRONMENTS.contains(&name.text()));
+        if !is_table {
+            continue;
+        }
+
+        let range = env.range(&table.tree);
+        let commands: Vec<_> = table
+            .commands
+            .iter()
+            .filter(|node| range.contains(table[**node].start()))
+            .map(|node| {
+                (
+                    table.as_command(*node).unwrap().name.text(),
+                    table[*node].start(),
+                )
+            })
+            .collect();
+
+        let has_header_command = commands
+            .iter()
+            .any(|(name, _)| HEADER_COMMANDS.contains(name));
+
+        let has_header_rule = commands.iter().find(|(name, _)| *name == "\\\\").map_or(
+            false,
+            |(_, first_row_end)| {
+                commands
+                    .iter()
+                    .any(|(name, start)| RULE_COMMANDS.contains(name) && start > first_row_end)
+            },
+        );
+
+        if !has_header_command && !has_header_rule {
+            diagnostics.push(create_diagnostic(
+                table[env.left.parent].range(),
+                "missing-table-header",
+                "The table has no header row; separate it with a rule like `\\midrule`".into(),
+            ));
+        }
+    }
+    diagnostics
+}
+
+fn color_only_emphasis(table: &latex::SymbolTable) -> Vec<Diagnostic> {
+    let emphasis = find_commands(table, EMPHASIS_COMMANDS);
+    find_commands(table, COLOR_COMMANDS)
+        .into_iter()
+        .filter(|range| {
+            !emphasis.iter().any(|emphasis| {
+                range.contains(emphasis.start) || emphasis.contains_exclusive(range.start)
+            })
+        })
+        .map(|range| {
+            create_diagnostic(
+                range,
+                "color-only-emphasis",
+                "The text is emphasized only through its color; combine it with `\\emph` or `\\textbf`".into(),
+            )
+        })
+        .collect()
+}
+
+fn find_commands(table: &latex::SymbolTable, names: &[&str]) -> Vec<Range> {
+    table
+        .commands
+        .iter()
+        .filter(|node| {
+            table
+                .as_command(**node)
+                .map_or(false, |cmd| names.contains(&cmd.name.text()))
+        })
+        .map(|node| table[*node].range())
+        .collect()
+}
+
+/// Extracts the keys of a key-value list like `width=\linewidth, alt={A, B}`.
+fn option_keys(text: &str) -> Vec<String> {
+    let mut keys = Vec::new();
+    let mut depth = 0;
+    let mut current = String::new();
+    let mut is_key = true;
+    for c in text.chars().chain(std::iter::once(',')) {
+        match c {
+            '{' => depth += 1,
+            '}' => depth -= 1,
+            ',' if depth == 0 => {
+                let key = current.trim();
+                if !key.is_empty() {
+                    keys.push(key.to_owned());
+                }
+                current.clear();
+                is_key = true;
+            }
+            '=' if depth == 0 => is_key = false,
+            _ if is_key && depth == 0 => current.push(c),
+            _ => (),
+        }
+    }
+    keys
+}
+
+fn create_diagnostic(range: Range, code: &str, message: String) -> Diagnostic {
+    Diagnostic {
+        source: Some("latex".into()),
+        code: Some(NumberOrString::String(code.into())),
+        severity: Some(DiagnosticSeverity::Warning),
+        ..Diagnostic::new_simple(range, message)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        diagnostics::tester::DiagnosticsTester,
+        protocol::{LatexLintOptions, LatexOptions},
+    };
+    use indoc::indoc;
+
+    fn check(text: &str) -> Vec<String> {
+        let options = Options {
+            latex: Some(LatexOptions {
+                lint: Some(LatexLintOptions {
+                    accessibility: Some(true),
+                    ..LatexLintOptions::default()
+                }),
+                ..LatexOptions::default()
+            }),
+            ..Options::default()
+        };
+
+        let mut tester = DiagnosticsTester::new();
+        tester.file("main.tex", text).options(options.clone());
+        let snapshot = tester.snapshot();
+
+        let provider = AccessibilityDiagnosticsProvider::default();
+        provider.update(&snapshot, &tester.relations(&snapshot), &options);
+        provider
+            .get(&snapshot.find(&tester.uri("main.tex")).unwrap())
+            .into_iter()
+            .map(|diagnostic| match diagnostic.code {
+                Some(NumberOrString::String(code)) => code,
+                _ => unreachable!(),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn valid() {
+        let codes = check(indoc!(
+            r#"
+                \usepackage[pdfusetitle]{hyperref}
+                \title{Foo}
+                \begin{document}
+                \includegraphics[width=\linewidth, alt={A plot, in blue}]{plot}
+                \begin{figure}
+                    \includegraphics{plot}
+                    \Description{A plot}
+                \end{figure}
+                \begin{tabular}{ll}
+                    A & B \\ \hline
+                    C & D
+                \end{tabular}
+                \textbf{\textcolor{red}{Bar}}
+                \end{document}
+            "#
+        ));
+        assert!(codes.is_empty());
+    }
+
+    #[test]
+    fn missing_alt_text() {
+        let codes = check("\\includegraphics[width=\\linewidth]{plot}");
+        assert_eq!(codes, vec!["missing-alt-text"]);
+    }
+
+    #[test]
+    fn missing_metadata() {
+        let codes = check("\\begin{document}\\end{document}");
+        assert_eq!(codes, vec!["missing-title", "missing-pdf-metadata"]);
+    }
+
+    #[test]
+    fn missing_table_header() {
+        let codes = check("\\begin{tabular}{ll}\nA & B \\\\\nC & D\n\\end{tabular}");
+        assert_eq!(codes, vec!["missing-table-header"]);
+    }
+
+    #[test]
+    fn color_only_emphasis() {
+        let codes = check("\\textcolor{red}{Foo}");
+        assert_eq!(codes, vec!["color-only-emphasis"]);
+    }
+
+    #[test]
+    fn disabled() {
+        let mut tester = DiagnosticsTester::new();
+        tester.file("main.tex", "\\textcolor{red}{Foo}");
+        let snapshot = tester.snapshot();
+
+        let provider = AccessibilityDiagnosticsProvider::default();
+        provider.update(&snapshot, &tester.relations(&snapshot), &Options::default());
+        let uri = tester.uri("main.tex");
+        assert!(provider.get(&snapshot.find(&uri).unwrap()).is_empty());
+    }
+}
@@ -1,5 +1,5 @@
 use crate::{
-    protocol::{Diagnostic, DiagnosticSeverity, Position, Range},
+    protocol::{Diagnostic, DiagnosticSeverity, NumberOrString, Position, Range},
     syntax::{bibtex, SyntaxNode},
     workspace::{Document, DocumentContent},
 };
@@ -28,6 +28,18 @@ impl BibtexErrorCode {
             BibtexErrorCode::MissingQuote => "Expecting a quote: '\"'",
         }
     }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            BibtexErrorCode::MissingBeginBrace => "missing-begin-brace",
+            BibtexErrorCode::MissingEntryKey => "missing-entry-key",
+            BibtexErrorCode::MissingComma => "missing-comma",
+            BibtexErrorCode::MissingEndBrace => "missing-end-brace",
+            BibtexErrorCode::MissingAssign => "missing-assign",
+            BibtexErrorCode::MissingContent => "missing-content",
+            BibtexErrorCode::MissingQuote => "missing-quote",
+        }
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
@@ -181,7 +193,7 @@ impl Into<Diagnostic> for BibtexError {
             range: Range::new(self.position, self.position),
             message: self.code.message().into(),
             severity: Some(DiagnosticSeverity::Error),
-            code: None,
+            code: Some(NumberOrString::String(self.code.name().into())),
             related_information: None,
             tags: None,
         }
@@ -1,5 +1,5 @@
 use crate::{
-    protocol::{Diagnostic, DiagnosticSeverity, Options, Position, Range, Uri},
+    protocol::{Diagnostic, DiagnosticSeverity, NumberOrString, Options, Position, Range, Uri},
     workspace::{Document, Snapshot},
 };
 use futures::lock::Mutex;
@@ -111,6 +111,22 @@ impl BuildError {
             line,
         }
     }
+
+    pub fn code(&self) -> Option<&'static str> {
+        if self.message.starts_with("Reference") && self.message.contains("undefined") {
+            Some("undefined-reference")
+        } else if self.message.starts_with("Citation") && self.message.contains("undefined") {
+            Some("undefined-citation")
+        } else if self.message.starts_with("Label") && self.message.contains("multiply defined") {
+            Some("multiply-defined-label")
+        } else if self.message.starts_with("Overfull") || self.message.starts_with("Underfull") {
+            Some("bad-box")
+        } else if self.message.starts_with("Undefined control sequence") {
+            Some("undefined-command")
+        } else {
+            None
+        }
+    }
 }
 
 impl Into<Diagnostic> for BuildError {
@@ -121,11 +137,14 @@ impl Into<Diagnostic> for BuildError {
             BuildErrorKind::Warning => DiagnosticSeverity::Warning,
         };
         let range = Range::new(position, position);
+        let code = self
+            .code()
+            .map(|code| NumberOrString::String(code.to_owned()));
         Diagnostic {
             range,
             severity: Some(severity),
             source: Some("latex".into()),
-            code: None,
+            code,
             message: self.message,
             tags: None,
             related_information: None,
@@ -0,0 +1,232 @@
+use super::remove_stale;
+use crate::{
+    protocol::{Diagnostic, DiagnosticSeverity, NumberOrString, Options, Uri},
+    syntax::{LatexIncludeKind, SyntaxNode},
+    workspace::{include_dirs, Document, Relations, Snapshot},
+};
+use chashmap::CHashMap;
+use std::{collections::HashSet, path::Path};
+
+pub const MISSING_FILE_CODE: &str = "missing-file";
+
+/// The maximum number of searched locations that are listed in a diagnostic.
+const MAX_SEARCHED_LOCATIONS: usize = 8;
+
+#[derive(Debug, Default)]
+pub struct IncludeDiagnosticsProvider {
+    diagnostics_by_uri: CHashMap<Uri, Vec<Diagnostic>>,
+    /// The files outside of the workspace that are known to exist.
+    /// Missing files are checked again because they are usually created after the diagnostic.
+    existing_files: CHashMap<Uri, ()>,
+}
+
+impl IncludeDiagnosticsProvider {
+    pub fn get(&self, doc: &Document) -> Vec<Diagnostic> {
+        match self.diagnostics_by_uri.get(&doc.uri) {
+            Some(diagnostics) => diagnostics.to_owned(),
+            None => Vec::new(),
+        }
+    }
+
+    pub fn update(
+        &self,
+        snapshot: &Snapshot,
+        relations: &Relations,
+        options: &Options,
+        current_dir: &Path,
+    ) {
+        remove_stale(&self.diagnostics_by_uri, snapshot);
+        let documents: HashSet<_> = snapshot.0.iter().map(|doc| &doc.uri).collect();
+        for doc in snapshot.0.iter().filter(|doc| doc.uri.scheme() == "file") {
+            let table = match doc.content.as_latex() {
+                Some(table) => table,
+                None => continue,
+            };
+
+            let related = relations.get(&doc.uri);
+            let mut diagnostics = Vec::new();
+            for include in &table.includes {
+                if include.kind == LatexIncludeKind::Package
+                    || include.kind == LatexIncludeKind::Class
+                {
+                    continue;
+                }
+
+                let dirs = include_dirs(&doc.uri, related, include.kind, options, current_dir);
+                let paths = include.paths(&table.tree);
+                for (path, targets) in paths.iter().zip(&include.all_targets) {
+                    let mut candidates = targets.clone();
+                    for candidate in dir_candidates(&dirs, include.kind, path.text()) {
+                        if !candidates.contains(&candidate) {
+                            candidates.push(candidate);
+                        }
+                    }
+
+                    if candidates
+                        .iter()
+                        .any(|candidate| self.exists(&documents, candidate))
+                    {
+                        continue;
+                    }
+
+                    let locations: Vec<_> = candidates
+                        .iter()
+                        .filter_map(|candidate| candidate.to_file_path().ok())
+                        .collect();
+
+                    let mut message = format!("Could not find the file `{}`", path.text());
+                    message.push_str("\nSearched locations:");
+                    for location in locations.iter().take(MAX_SEARCHED_LOCATIONS) {
+                        message.push_str(&format!("\n  {}", location.to_string_lossy()));
+                    }
+
+                    if locations.len() > MAX_SEARCHED_LOCATIONS {
+                        message.push_str(&format!(
+                            "\n  ... and {} more",
+                            locations.len() - MAX_SEARCHED_LOCATIONS
+                        ));
+                    }
+
+                    diagnostics.push(Diagnostic {
+                        source: Some("latex".into()),
+                        code: Some(NumberOrString::String(MISSING_FILE_CODE.into())),
+                        severity: Some(DiagnosticSeverity::Error),
+                        ..Diagnostic::new_simple(path.range(), message)
+                    });
+                }
+            }
+            self.diagnostics_by_uri.insert(doc.uri.clone(), diagnostics);
+        }
+    }
+
+    fn exists(&self, documents: &HashSet<&Uri>, uri: &Uri) -> bool {
+        if documents.contains(uri) || self.existing_files.contains_key(uri) {
+            return true;
+        }
+
+        let exists = uri.to_file_path().map_or(false, |path| path.is_file());
+        if exists {
+            self.existing_files.insert(uri.clone(), ());
+        }
+        exists
+    }
+}
+
+fn dir_candidates(dirs: &[Uri], kind: LatexIncludeKind, path: &str) -> Vec<Uri> {
+    let mut candidates = Vec::new();
+    for dir in dirs {
+        if let Ok(candidate) = dir.join(path) {
+            candidates.push(candidate.into());
+        }
+
+        for extension in kind.extensions().unwrap_or_default() {
+            if let Ok(candidate) = dir.join(&format!("{}.{}", path, extension)) {
+                candidates.push(candidate.into());
+            }
+        }
+    }
+    candidates
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::diagnostics::tester::DiagnosticsTester;
+    use std::env;
+
+    fn diagnostics(files: Vec<(&str, &str)>) -> Vec<Diagnostic> {
+        let current_dir = env::current_dir().unwrap().join("missing");
+        let mut tester = DiagnosticsTester::new();
+        tester.current_dir(&current_dir);
+        for (name, text) in &files {
+            tester.file(*name, *text);
+        }
+        let snapshot = tester.snapshot();
+
+        let provider = IncludeDiagnosticsProvider::default();
+        provider.update(
+            &snapshot,
+            &tester.relations(&snapshot),
+            &Options::default(),
+            &current_dir,
+        );
+        provider.get(&snapshot.find(&tester.uri(files[0].0)).unwrap())
+    }
+
+    fn check(files: Vec<(&str, &str)>) -> Vec<String> {
+        diagnostics(files)
+            .into_iter()
+            .map(|diagnostic| diagnostic.message.lines().next().unwrap().to_owned())
+            .collect()
+    }
+
+    #[test]
+    fn existing() {
+        let messages = check(vec![
+            ("main.tex", "\\input{foo}\n\\bibliography{bar}"),
+            ("foo.tex", ""),
+            ("bar.bib", ""),
+        ]);
+        assert!(messages.is_empty());
+    }
+
+    #[test]
+    fn missing() {
+        let messages = check(vec![("main.tex", "\\include{foo}\n\\includegraphics{bar}")]);
+        assert_eq!(
+            messages,
+            vec![
+                "Could not find the file `foo`",
+                "Could not find the file `bar`"
+            ]
+        );
+    }
+
+    #[test]
+    fn graphics_path() {
+        let messages = check(vec![
+            (
+                "main.tex",
+                "\\graphicspath{{figures/}}\n\\includegraphics{plot}",
+            ),
+            ("figures/plot", ""),
+        ]);
+        assert!(messages.is_empty());
+    }
+
+    #[test]
+    fn root_directory() {
+        let messages = check(vec![
+            ("chapters/one.tex", "\\input{chapters/two}"),
+            (
+                "main.tex",
+                "\\begin{document}\n\\input{chapters/one}\n\\end{document}",
+            ),
+            ("chapters/two.tex", ""),
+        ]);
+        assert!(messages.is_empty());
+    }
+
+    #[test]
+    fn searched_locations_capped() {
+        let graphics_paths: String = (0..10).map(|i| format!("{{dir{}/}}", i)).collect();
+        let text = format!(
+            "\\graphicspath{{{}}}\n\\includegraphics{{plot}}",
+            graphics_paths
+        );
+        let diagnostics = diagnostics(vec![("main.tex", text.as_str())]);
+        let lines: Vec<_> = diagnostics[0].message.lines().collect();
+        assert_eq!(lines.len(), 2 + MAX_SEARCHED_LOCATIONS + 1);
+        assert!(lines.last().unwrap().ends_with("more"));
+    }
+
+    #[test]
+    fn searched_locations() {
+        let diagnostics = diagnostics(vec![("main.tex", "\\input{foo}")]);
+        let current_dir = env::current_dir().unwrap().join("missing");
+        let expected_path = current_dir.join("foo.tex");
+        assert!(diagnostics[0]
+            .message
+            .contains(expected_path.to_string_lossy().as_ref()));
+    }
+}
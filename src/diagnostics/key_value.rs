@@ -0,0 +1,116 @@
+use super::{remove_stale, structure::definition_ranges};
+use crate::{
+    protocol::{Diagnostic, DiagnosticSeverity, NumberOrString, RangeExt, Uri},
+    syntax::{latex, SyntaxNode, LANGUAGE_DATA},
+    workspace::{Document, Snapshot},
+};
+use chashmap::CHashMap;
+
+#[derive(Debug, Default)]
+pub struct KeyValueDiagnosticsProvider {
+    diagnostics_by_uri: CHashMap<Uri, Vec<Diagnostic>>,
+}
+
+impl KeyValueDiagnosticsProvider {
+    pub fn get(&self, doc: &Document) -> Vec<Diagnostic> {
+        match self.diagnostics_by_uri.get(&doc.uri) {
+            Some(diagnostics) => diagnostics.to_owned(),
+            None => Vec::new(),
+        }
+    }
+
+    pub fn update(&self, snapshot: &Snapshot) {
+        remove_stale(&self.diagnostics_by_uri, snapshot);
+        for doc in &snapshot.0 {
+            if let Some(table) = doc.content.as_latex() {
+                self.diagnostics_by_uri
+                    .insert(doc.uri.clone(), Self::check(table));
+            }
+        }
+    }
+
+    fn check(table: &latex::SymbolTable) -> Vec<Diagnostic> {
+        let definitions = definition_ranges(table);
+        let mut diagnostics = Vec::new();
+        for node in &table.commands {
+            let cmd = table.as_command(*node).unwrap();
+            if definitions
+                .iter()
+                .any(|definition| definition.contains(cmd.start()))
+            {
+                continue;
+            }
+
+            for kv_cmd in LANGUAGE_DATA
+                .key_value_commands
+                .iter()
+                .filter(|kv_cmd| kv_cmd.name == cmd.name.text())
+            {
+                let key_set = match LANGUAGE_DATA.key_set(&kv_cmd.key_set) {
+                    Some(key_set) if key_set.strict => key_set,
+                    _ => continue,
+                };
+
+                let kind = if kv_cmd.options {
+                    latex::GroupKind::Options
+                } else {
+                    latex::GroupKind::Group
+                };
+
+                for (key, range) in table
+                    .extract_keys(*node, kind, kv_cmd.index)
+                    .unwrap_or_default()
+                {
+                    if key.contains('#') || key_set.find(key).is_some() {
+                        continue;
+                    }
+
+                    diagnostics.push(Diagnostic {
+                        source: Some("latex".into()),
+                        code: Some(NumberOrString::String("unknown-key".into())),
+                        severity: Some(DiagnosticSeverity::Warning),
+                        ..Diagnostic::new_simple(
+                            range,
+                            format!("Unknown key `{}` for `{}`", key, kv_cmd.name),
+                        )
+                    });
+                }
+            }
+        }
+        diagnostics
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{diagnostics::tester::DiagnosticsTester, protocol::Range};
+
+    fn check(text: &str) -> Vec<Diagnostic> {
+        let snapshot = DiagnosticsTester::new().file("main.tex", text).snapshot();
+        KeyValueDiagnosticsProvider::check(snapshot.0[0].content.as_latex().unwrap())
+    }
+
+    #[test]
+    fn valid() {
+        let diagnostics = check(r#"\includegraphics[width=3cm, clip]{foo}"#);
+        assert!(diagnostics.is_empty());
+    }
+
+    #[test]
+    fn unknown_key() {
+        let diagnostics = check(r#"\hypersetup{colorlinks, linkcolour=blue}"#);
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(
+            diagnostics[0].message,
+            "Unknown key `linkcolour` for `\\hypersetup`"
+        );
+        assert_eq!(diagnostics[0].range, Range::new_simple(0, 24, 0, 34));
+    }
+
+    #[test]
+    fn lenient_key_set() {
+        let diagnostics = check(r#"\draw[red, very thick] (0, 0) -- (1, 1);"#);
+        assert!(diagnostics.is_empty());
+    }
+}
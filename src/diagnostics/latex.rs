@@ -1,6 +1,7 @@
+use super::remove_stale;
 use crate::{
     protocol::{Diagnostic, DiagnosticSeverity, NumberOrString, Range, RangeExt, Uri},
-    workspace::Document,
+    workspace::{Document, Snapshot},
 };
 use chashmap::CHashMap;
 use futures::{
@@ -27,7 +28,8 @@ impl LatexDiagnosticsProvider {
         }
     }
 
-    pub async fn update(&self, uri: &Uri, text: &str) {
+    pub async fn update(&self, snapshot: &Snapshot, uri: &Uri, text: &str) {
+        remove_stale(&self.diagnostics_by_uri, snapshot);
         if uri.scheme() != "file" {
             return;
         }
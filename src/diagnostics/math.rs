@@ -0,0 +1,143 @@
+use super::{remove_stale, structure::definition_ranges};
+use crate::{
+    protocol::{Diagnostic, DiagnosticSeverity, NumberOrString, RangeExt, Uri},
+    syntax::{latex, LatexCommandMode, SyntaxNode, LANGUAGE_DATA},
+    workspace::{Document, Snapshot},
+};
+use chashmap::CHashMap;
+
+#[derive(Debug, Default)]
+pub struct MathDiagnosticsProvider {
+    diagnostics_by_uri: CHashMap<Uri, Vec<Diagnostic>>,
+}
+
+impl MathDiagnosticsProvider {
+    pub fn get(&self, doc: &Document) -> Vec<Diagnostic> {
+        match self.diagnostics_by_uri.get(&doc.uri) {
+            Some(diagnostics) => diagnostics.to_owned(),
+            None => Vec::new(),
+        }
+    }
+
+    pub fn update(&self, snapshot: &Snapshot) {
+        remove_stale(&self.diagnostics_by_uri, snapshot);
+        for doc in &snapshot.0 {
+            if let Some(table) = doc.content.as_latex() {
+                self.diagnostics_by_uri
+                    .insert(doc.uri.clone(), Self::check(table));
+            }
+        }
+    }
+
+    fn check(table: &latex::SymbolTable) -> Vec<Diagnostic> {
+        let math_mode = latex::MathMode::analyze(table);
+        let definitions = definition_ranges(table);
+        let mut diagnostics = Vec::new();
+        for node in &table.commands {
+            let cmd = table.as_command(*node).unwrap();
+            let name = cmd.name.text();
+            let (code, message) = match LANGUAGE_DATA.command_mode(name) {
+                Some(LatexCommandMode::Math) if !math_mode.contains(cmd.start()) => (
+                    "math-command-in-text",
+                    format!("`{}` can only be used in math mode", name),
+                ),
+                Some(LatexCommandMode::Text) if math_mode.contains(cmd.start()) => (
+                    "text-command-in-math",
+                    format!("`{}` cannot be used in math mode", name),
+                ),
+                _ => continue,
+            };
+
+            if definitions
+                .iter()
+                .any(|definition| definition.contains(cmd.start()))
+            {
+                continue;
+            }
+
+            diagnostics.push(Diagnostic {
+                source: Some("latex".into()),
+                code: Some(NumberOrString::String(code.into())),
+                severity: Some(DiagnosticSeverity::Warning),
+                ..Diagnostic::new_simple(cmd.name.range(), message)
+            });
+        }
+        diagnostics
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::diagnostics::tester::DiagnosticsTester;
+    use indoc::indoc;
+
+    fn check(text: &str) -> Vec<String> {
+        let snapshot = DiagnosticsTester::new().file("main.tex", text).snapshot();
+        MathDiagnosticsProvider::check(snapshot.0[0].content.as_latex().unwrap())
+            .into_iter()
+            .map(|diagnostic| diagnostic.message)
+            .collect()
+    }
+
+    #[test]
+    fn valid() {
+        let messages = check(indoc!(
+            r#"
+                \section{Foo}
+                $\alpha \text{\emph{bar}}$
+                \newcommand{\foo}{\beta}
+            "#
+        ));
+        assert!(messages.is_empty());
+    }
+
+    #[test]
+    fn starred_definition() {
+        let messages = check("\\newcommand*{\\R}{\\mathbb{R}}");
+        assert!(messages.is_empty());
+    }
+
+    #[test]
+    fn primitive_definition() {
+        let messages = check(indoc!(
+            r#"
+                \def\R{\mathbb{R}}
+                \gdef\N{\mathbb{N}}
+                \let\a\alpha
+            "#
+        ));
+        assert!(messages.is_empty());
+    }
+
+    #[test]
+    fn primitive_definition_followed_by_text() {
+        let messages = check("\\def\\R{\\mathbb{R}} \\alpha");
+        assert_eq!(messages, vec!["`\\alpha` can only be used in math mode"]);
+    }
+
+    #[test]
+    fn math_environments() {
+        let messages = check(indoc!(
+            r#"
+                \begin{displaymath} \alpha \end{displaymath}
+                \begin{math} \beta \end{math}
+                \begin{flalign} \gamma \end{flalign}
+                \begin{flalign*} \delta \end{flalign*}
+            "#
+        ));
+        assert!(messages.is_empty());
+    }
+
+    #[test]
+    fn math_command_in_text() {
+        let messages = check("\\alpha");
+        assert_eq!(messages, vec!["`\\alpha` can only be used in math mode"]);
+    }
+
+    #[test]
+    fn text_command_in_math() {
+        let messages = check("\\[ \\section{Foo} \\]");
+        assert_eq!(messages, vec!["`\\section` cannot be used in math mode"]);
+    }
+}
@@ -1,28 +1,206 @@
+mod accessibility;
 mod bibtex;
 mod build;
+mod include;
+mod key_value;
 mod latex;
+mod math;
+mod package;
+mod spelling;
+mod structure;
+mod suppression;
+#[cfg(test)]
+mod tester;
+mod unused_entry;
 
 pub use self::{
+    accessibility::AccessibilityDiagnosticsProvider,
     bibtex::{BibtexDiagnosticsProvider, BibtexError, BibtexErrorCode},
     build::BuildDiagnosticsProvider,
+    include::{IncludeDiagnosticsProvider, MISSING_FILE_CODE},
+    key_value::KeyValueDiagnosticsProvider,
     latex::LatexDiagnosticsProvider,
+    math::MathDiagnosticsProvider,
+    package::PackageDiagnosticsProvider,
+    spelling::SpellingDiagnosticsProvider,
+    structure::StructureDiagnosticsProvider,
+    suppression::{diagnostic_name, Suppression, SuppressionScope},
+    unused_entry::UnusedEntryDiagnosticsProvider,
 };
 
-use crate::{protocol::Diagnostic, workspace::Document};
+use crate::{
+    protocol::{Diagnostic, PublishDiagnosticsParams, Uri},
+    workspace::{Document, Snapshot},
+};
+use chashmap::CHashMap;
+use futures::lock::Mutex;
+use std::{
+    collections::{HashMap, HashSet},
+    sync::atomic::{AtomicUsize, Ordering},
+    time::Duration,
+};
+use tokio::time::delay_for;
+
+const PUBLISH_DELAY: Duration = Duration::from_millis(300);
 
 #[derive(Debug, Default)]
 pub struct DiagnosticsManager {
+    pub accessibility: AccessibilityDiagnosticsProvider,
     pub bibtex: BibtexDiagnosticsProvider,
     pub latex: LatexDiagnosticsProvider,
     pub build: BuildDiagnosticsProvider,
+    pub include: IncludeDiagnosticsProvider,
+    pub key_value: KeyValueDiagnosticsProvider,
+    pub math: MathDiagnosticsProvider,
+    pub package: PackageDiagnosticsProvider,
+    pub spelling: SpellingDiagnosticsProvider,
+    pub structure: StructureDiagnosticsProvider,
+    pub unused_entry: UnusedEntryDiagnosticsProvider,
+    versions_by_uri: CHashMap<Uri, i64>,
+    published_by_uri: Mutex<HashMap<Uri, Vec<Diagnostic>>>,
+    generation: AtomicUsize,
 }
 
 impl DiagnosticsManager {
     pub async fn get(&self, doc: &Document) -> Vec<Diagnostic> {
         let mut diagnostics = Vec::new();
+        diagnostics.append(&mut self.accessibility.get(doc));
         diagnostics.append(&mut self.bibtex.get(doc));
         diagnostics.append(&mut self.latex.get(doc));
         diagnostics.append(&mut self.build.get(doc).await);
-        diagnostics
+        diagnostics.append(&mut self.include.get(doc));
+        diagnostics.append(&mut self.key_value.get(doc));
+        diagnostics.append(&mut self.math.get(doc));
+        diagnostics.append(&mut self.package.get(doc));
+        diagnostics.append(&mut self.spelling.get(doc));
+        diagnostics.append(&mut self.structure.get(doc));
+        diagnostics.append(&mut self.unused_entry.get(doc));
+        Suppression::apply(&Suppression::parse(&doc.text), diagnostics)
+    }
+
+    pub fn update_version(&self, uri: &Uri, version: Option<i64>) {
+        match version {
+            Some(version) => {
+                self.versions_by_uri.insert(uri.clone(), version);
+            }
+            None => {
+                self.versions_by_uri.remove(uri);
+            }
+        }
+    }
+
+    /// Waits until no other publication has been requested for a short amount of time.
+    /// Returns `false` if a newer request superseded this one.
+    pub async fn debounce(&self) -> bool {
+        let generation = self.generation.fetch_add(1, Ordering::SeqCst) + 1;
+        delay_for(PUBLISH_DELAY).await;
+        self.generation.load(Ordering::SeqCst) == generation
+    }
+
+    /// Computes the diagnostics of every document and returns only those
+    /// that changed since they were last published.
+    pub async fn changes(&self, snapshot: &Snapshot) -> Vec<PublishDiagnosticsParams> {
+        let mut published_by_uri = self.published_by_uri.lock().await;
+        let mut changes = Vec::new();
+        let uris: HashSet<_> = snapshot.0.iter().map(|doc| &doc.uri).collect();
+        let stale_uris: Vec<_> = published_by_uri
+            .keys()
+            .filter(|uri| !uris.contains(uri))
+            .cloned()
+            .collect();
+
+        for uri in stale_uris {
+            published_by_uri.remove(&uri);
+            changes.push(PublishDiagnosticsParams {
+                uri: uri.into(),
+                diagnostics: Vec::new(),
+                version: None,
+            });
+        }
+
+        for doc in &snapshot.0 {
+            let diagnostics = self.get(doc).await;
+            let is_unchanged = match published_by_uri.get(&doc.uri) {
+                Some(published) => *published == diagnostics,
+                None => diagnostics.is_empty(),
+            };
+
+            if is_unchanged {
+                continue;
+            }
+
+            published_by_uri.insert(doc.uri.clone(), diagnostics.clone());
+            changes.push(PublishDiagnosticsParams {
+                uri: doc.uri.clone().into(),
+                diagnostics,
+                version: self.versions_by_uri.get(&doc.uri).map(|version| *version),
+            });
+        }
+        changes
+    }
+}
+
+/// Drops the diagnostics of the documents that are no longer part of the snapshot.
+fn remove_stale(diagnostics_by_uri: &CHashMap<Uri, Vec<Diagnostic>>, snapshot: &Snapshot) {
+    let uris: HashSet<_> = snapshot.0.iter().map(|doc| &doc.uri).collect();
+    diagnostics_by_uri.retain(|uri, _| uris.contains(uri));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{tester::DiagnosticsTester, *};
+
+    fn create_snapshot(text: &str) -> Snapshot {
+        DiagnosticsTester::new().file("foo.bib", text).snapshot()
+    }
+
+    #[tokio::test]
+    async fn changes_empty() {
+        let manager = DiagnosticsManager::default();
+        let changes = manager.changes(&create_snapshot("")).await;
+        assert!(changes.is_empty());
+    }
+
+    #[tokio::test]
+    async fn changes_only_once() {
+        let manager = DiagnosticsManager::default();
+        let snapshot = create_snapshot("@article{foo,");
+        assert_eq!(manager.changes(&snapshot).await.len(), 1);
+        assert!(manager.changes(&snapshot).await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn changes_cleared() {
+        let manager = DiagnosticsManager::default();
+        manager.changes(&create_snapshot("@article{foo,")).await;
+        let changes = manager.changes(&create_snapshot("@article{foo,}")).await;
+        assert_eq!(changes.len(), 1);
+        assert!(changes[0].diagnostics.is_empty());
+    }
+
+    #[tokio::test]
+    async fn changes_removed_document() {
+        let manager = DiagnosticsManager::default();
+        manager.changes(&create_snapshot("@article{foo,")).await;
+        let changes = manager.changes(&Snapshot::new()).await;
+        assert_eq!(changes.len(), 1);
+        assert!(changes[0].diagnostics.is_empty());
+        assert!(manager.changes(&Snapshot::new()).await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn changes_suppressed() {
+        let manager = DiagnosticsManager::default();
+        let snapshot = create_snapshot("% texlab: disable missing-comma\n@article{foo");
+        assert!(manager.changes(&snapshot).await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn changes_version() {
+        let manager = DiagnosticsManager::default();
+        let uri = DiagnosticsTester::new().uri("foo.bib");
+        manager.update_version(&uri, Some(42));
+        let changes = manager.changes(&create_snapshot("@article{foo,")).await;
+        assert_eq!(changes[0].version, Some(42));
     }
 }
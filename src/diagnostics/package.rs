@@ -0,0 +1,330 @@
+use super::remove_stale;
+use crate::{
+    protocol::{Diagnostic, DiagnosticSeverity, NumberOrString, Range, Uri},
+    syntax::{latex, LatexIncludeKind, SyntaxNode, LANGUAGE_DATA},
+    tex::Resolver,
+    workspace::{Document, Relations, Snapshot},
+};
+use chashmap::CHashMap;
+use std::sync::Arc;
+
+#[derive(Debug, Default)]
+pub struct PackageDiagnosticsProvider {
+    diagnostics_by_uri: CHashMap<Uri, Vec<Diagnostic>>,
+}
+
+impl PackageDiagnosticsProvider {
+    pub fn get(&self, doc: &Document) -> Vec<Diagnostic> {
+        match self.diagnostics_by_uri.get(&doc.uri) {
+            Some(diagnostics) => diagnostics.to_owned(),
+            None => Vec::new(),
+        }
+    }
+
+    pub fn update(&self, snapshot: &Snapshot, relations: &Relations, resolver: &Resolver) {
+        remove_stale(&self.diagnostics_by_uri, snapshot);
+        for doc in &snapshot.0 {
+            let table = match doc.content.as_latex() {
+                Some(table) => table,
+                None => continue,
+            };
+
+            let loads: Vec<_> = relations
+                .get(&doc.uri)
+                .iter()
+                .flat_map(PackageLoad::find)
+                .collect();
+            let mut diagnostics = Vec::new();
+            diagnostics.append(&mut missing_packages(snapshot, resolver, table));
+            for load in loads.iter().filter(|load| load.uri == doc.uri) {
+                diagnostics.extend(option_clash(load, &loads));
+                diagnostics.extend(incompatible_packages(load, &loads));
+                diagnostics.extend(hyperref_order(load, &loads));
+            }
+            self.diagnostics_by_uri.insert(doc.uri.clone(), diagnostics);
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct PackageLoad {
+    uri: Uri,
+    is_standalone: bool,
+    name: String,
+    range: Range,
+    options: Vec<String>,
+}
+
+impl PackageLoad {
+    fn find(doc: &Arc<Document>) -> Vec<Self> {
+        let table = match doc.content.as_latex() {
+            Some(table) => table,
+            None => return Vec::new(),
+        };
+
+        let mut loads = Vec::new();
+        for include in &table.includes {
+            if include.kind != LatexIncludeKind::Package {
+                continue;
+            }
+
+            let options: Vec<_> = table
+                .extract_comma_separated_words(include.parent, latex::GroupKind::Options, 0)
+                .unwrap_or_default()
+                .into_iter()
+                .map(|option| option.text().to_owned())
+                .collect();
+
+            for path in include.paths(&table.tree) {
+                loads.push(Self {
+                    uri: doc.uri.clone(),
+                    is_standalone: table.is_standalone,
+                    name: path.text().to_owned(),
+                    range: path.range(),
+                    options: options.clone(),
+                });
+            }
+        }
+        loads
+    }
+
+    fn is_same(&self, other: &Self) -> bool {
+        self.uri == other.uri && self.range == other.range
+    }
+
+    /// Checks whether this package is loaded after the other one.
+    /// The location of the packages decides if the loading order is unknown.
+    fn follows(&self, other: &Self) -> bool {
+        other.precedes(self)
+            || (!self.precedes(other)
+                && (other.uri.as_str(), other.range.start) < (self.uri.as_str(), self.range.start))
+    }
+
+    /// Checks whether this package is loaded before the other one.
+    /// Packages of the main document are assumed to be loaded before those of included documents.
+    fn precedes(&self, other: &Self) -> bool {
+        if self.uri == other.uri {
+            self.range.start < other.range.start
+        } else {
+            self.is_standalone && !other.is_standalone
+        }
+    }
+}
+
+fn missing_packages(
+    snapshot: &Snapshot,
+    resolver: &Resolver,
+    table: &latex::SymbolTable,
+) -> Vec<Diagnostic> {
+    if resolver.files_by_name.is_empty() {
+        return Vec::new();
+    }
+
+    let mut diagnostics = Vec::new();
+    for include in &table.includes {
+        let (extension, kind, code) = match include.kind {
+            LatexIncludeKind::Package => ("sty", "Package", "package-not-installed"),
+            LatexIncludeKind::Class => ("cls", "Document class", "class-not-installed"),
+            _ => continue,
+        };
+
+        for (path, targets) in include.paths(&table.tree).iter().zip(&include.all_targets) {
+            let file_name = format!("{}.{}", path.text(), extension);
+            let has_distro_file = resolver.files_by_name.contains_key(&file_name);
+            let has_local_file = targets.iter().any(|target| {
+                snapshot.find(target).is_some()
+                    || (target.scheme() == "file"
+                        && target.to_file_path().map_or(false, |file| file.is_file()))
+            });
+
+            if !has_distro_file && !has_local_file {
+                diagnostics.push(create_diagnostic(
+                    path.range(),
+                    DiagnosticSeverity::Error,
+                    code,
+                    format!("{} `{}` is not installed", kind, path.text()),
+                ));
+            }
+        }
+    }
+    diagnostics
+}
+
+fn option_clash(load: &PackageLoad, loads: &[PackageLoad]) -> Option<Diagnostic> {
+    let previous = loads.iter().find(|other| {
+        other.name == load.name
+            && !other.is_same(load)
+            && other.precedes(load)
+            && load
+                .options
+                .iter()
+                .any(|option| !other.options.contains(option))
+    })?;
+
+    Some(create_diagnostic(
+        load.range,
+        DiagnosticSeverity::Error,
+        "option-clash",
+        format!(
+            "Package `{}` has already been loaded with different options: [{}]",
+            load.name,
+            previous.options.join(",")
+        ),
+    ))
+}
+
+fn incompatible_packages(load: &PackageLoad, loads: &[PackageLoad]) -> Vec<Diagnostic> {
+    LANGUAGE_DATA
+        .incompatible_packages
+        .iter()
+        .filter_map(|(first, second)| {
+            if *first == load.name {
+                Some(second)
+            } else if *second == load.name {
+                Some(first)
+            } else {
+                None
+            }
+        })
+        .filter(|name| {
+            loads
+                .iter()
+                .any(|other| other.name == **name && load.follows(other))
+        })
+        .map(|name| {
+            create_diagnostic(
+                load.range,
+                DiagnosticSeverity::Warning,
+                "incompatible-packages",
+                format!("Package `{}` is incompatible with `{}`", load.name, name),
+            )
+        })
+        .collect()
+}
+
+fn hyperref_order(load: &PackageLoad, loads: &[PackageLoad]) -> Option<Diagnostic> {
+    if !LANGUAGE_DATA
+        .hyperref_predecessors
+        .iter()
+        .any(|name| *name == load.name)
+    {
+        return None;
+    }
+
+    if !loads
+        .iter()
+        .any(|other| other.name == "hyperref" && other.precedes(load))
+    {
+        return None;
+    }
+
+    Some(create_diagnostic(
+        load.range,
+        DiagnosticSeverity::Warning,
+        "package-order",
+        format!("Package `{}` should be loaded before `hyperref`", load.name),
+    ))
+}
+
+fn create_diagnostic(
+    range: Range,
+    severity: DiagnosticSeverity,
+    code: &str,
+    message: String,
+) -> Diagnostic {
+    Diagnostic {
+        source: Some("latex".into()),
+        code: Some(NumberOrString::String(code.into())),
+        severity: Some(severity),
+        ..Diagnostic::new_simple(range, message)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::diagnostics::tester::DiagnosticsTester;
+    use std::{collections::HashMap, path::PathBuf};
+
+    fn create_resolver() -> Resolver {
+        let mut files_by_name = HashMap::new();
+        for name in &[
+            "article.cls",
+            "amsmath.sty",
+            "hyperref.sty",
+            "subcaption.sty",
+        ] {
+            files_by_name.insert((*name).to_owned(), PathBuf::from(name));
+        }
+        Resolver::new(files_by_name)
+    }
+
+    fn check(text: &str) -> Vec<String> {
+        let resolver = create_resolver();
+        let mut tester = DiagnosticsTester::new();
+        tester.file("main.tex", text).resolver(resolver.clone());
+        let snapshot = tester.snapshot();
+
+        let provider = PackageDiagnosticsProvider::default();
+        provider.update(&snapshot, &tester.relations(&snapshot), &resolver);
+        provider
+            .get(&snapshot.find(&tester.uri("main.tex")).unwrap())
+            .into_iter()
+            .map(|diagnostic| diagnostic.message)
+            .collect()
+    }
+
+    #[test]
+    fn installed() {
+        let messages = check("\\documentclass{article}\n\\usepackage{amsmath}");
+        assert!(messages.is_empty());
+    }
+
+    #[test]
+    fn not_installed() {
+        let messages = check("\\documentclass{foo}\n\\usepackage{bar}");
+        assert_eq!(
+            messages,
+            vec![
+                "Document class `foo` is not installed",
+                "Package `bar` is not installed"
+            ]
+        );
+    }
+
+    #[test]
+    fn option_clash() {
+        let messages = check("\\usepackage[fleqn]{amsmath}\n\\usepackage[leqno]{amsmath}");
+        assert_eq!(
+            messages,
+            vec!["Package `amsmath` has already been loaded with different options: [fleqn]"]
+        );
+    }
+
+    #[test]
+    fn option_subset() {
+        let messages = check("\\usepackage[fleqn,leqno]{amsmath}\n\\usepackage[leqno]{amsmath}");
+        assert!(messages.is_empty());
+    }
+
+    #[test]
+    fn incompatible() {
+        let messages = check("\\usepackage{subcaption}\n\\usepackage{subfigure}");
+        assert_eq!(
+            messages,
+            vec![
+                "Package `subfigure` is not installed",
+                "Package `subfigure` is incompatible with `subcaption`"
+            ]
+        );
+    }
+
+    #[test]
+    fn hyperref_order() {
+        let messages = check("\\usepackage{hyperref}\n\\usepackage{amsmath}");
+        assert_eq!(
+            messages,
+            vec!["Package `amsmath` should be loaded before `hyperref`"]
+        );
+    }
+}
@@ -0,0 +1,67 @@
+use super::remove_stale;
+use crate::{
+    protocol::{Diagnostic, DiagnosticSeverity, NumberOrString, Options, Uri},
+    spelling,
+    workspace::{Document, Snapshot},
+};
+use chashmap::CHashMap;
+use std::path::Path;
+
+#[derive(Debug, Default)]
+pub struct SpellingDiagnosticsProvider {
+    diagnostics_by_uri: CHashMap<Uri, Vec<Diagnostic>>,
+}
+
+impl SpellingDiagnosticsProvider {
+    pub fn get(&self, doc: &Document) -> Vec<Diagnostic> {
+        match self.diagnostics_by_uri.get(&doc.uri) {
+            Some(diagnostics) => diagnostics.to_owned(),
+            None => Vec::new(),
+        }
+    }
+
+    pub async fn update(
+        &self,
+        snapshot: &Snapshot,
+        uri: &Uri,
+        options: &Options,
+        current_dir: &Path,
+    ) {
+        remove_stale(&self.diagnostics_by_uri, snapshot);
+        let doc = match snapshot.find(uri) {
+            Some(doc) => doc,
+            None => return,
+        };
+
+        let table = match doc.content.as_latex() {
+            Some(table) if spelling::spelling_options(options).enabled() => table,
+            _ => {
+                self.diagnostics_by_uri.remove(uri);
+                return;
+            }
+        };
+
+        let related = snapshot.relations(uri, options, current_dir);
+        let dictionary = match spelling::find_dictionary(&related, options).await {
+            Some(dictionary) => dictionary,
+            None => {
+                self.diagnostics_by_uri.remove(uri);
+                return;
+            }
+        };
+
+        let project_words = spelling::load_project_words(&related, options, current_dir).await;
+        let diagnostics = spelling::find_words(table)
+            .into_iter()
+            .filter(|word| !project_words.contains(&word.text) && !dictionary.check(&word.text))
+            .map(|word| Diagnostic {
+                source: Some("spelling".into()),
+                code: Some(NumberOrString::String("misspelled-word".into())),
+                severity: Some(DiagnosticSeverity::Information),
+                ..Diagnostic::new_simple(word.range, format!("Unknown word: {}", word.text))
+            })
+            .collect();
+
+        self.diagnostics_by_uri.insert(uri.clone(), diagnostics);
+    }
+}
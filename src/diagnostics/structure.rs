@@ -0,0 +1,394 @@
+use super::remove_stale;
+use crate::{
+    outline::{OutlineCaptionKind, OutlineContext},
+    protocol::{Diagnostic, DiagnosticSeverity, NumberOrString, Range, RangeExt, Uri},
+    syntax::{latex, LatexLabelKind, SyntaxNode, LANGUAGE_DATA},
+    workspace::{Document, Relations, Snapshot},
+};
+use chashmap::CHashMap;
+use std::collections::HashSet;
+
+/// Environments that are not floats but may contain a `\caption` nevertheless.
+const CAPTION_ENVIRONMENTS: &[&str] = &["longtable", "wrapfigure", "wraptable"];
+
+#[derive(Debug, Default)]
+pub struct StructureDiagnosticsProvider {
+    diagnostics_by_uri: CHashMap<Uri, Vec<Diagnostic>>,
+}
+
+impl StructureDiagnosticsProvider {
+    pub fn get(&self, doc: &Document) -> Vec<Diagnostic> {
+        match self.diagnostics_by_uri.get(&doc.uri) {
+            Some(diagnostics) => diagnostics.to_owned(),
+            None => Vec::new(),
+        }
+    }
+
+    pub fn update(&self, snapshot: &Snapshot, relations: &Relations) {
+        remove_stale(&self.diagnostics_by_uri, snapshot);
+        for doc in &snapshot.0 {
+            let table = match doc.content.as_latex() {
+                Some(table) => table,
+                None => continue,
+            };
+
+            let references: HashSet<_> = relations
+                .get(&doc.uri)
+                .iter()
+                .filter_map(|doc| doc.content.as_latex())
+                .flat_map(|table| {
+                    table
+                        .labels
+                        .iter()
+                        .filter(|label| label.kind.is_reference())
+                        .flat_map(move |label| label.names(&table.tree))
+                        .map(|name| name.text().to_owned())
+                })
+                .collect();
+
+            let checker = StructureChecker::new(table);
+            let mut diagnostics = Vec::new();
+            diagnostics.append(&mut checker.check_floats(&references));
+            diagnostics.append(&mut checker.check_captions());
+            diagnostics.append(&mut checker.check_items());
+            self.diagnostics_by_uri.insert(doc.uri.clone(), diagnostics);
+        }
+    }
+}
+
+struct StructureChecker<'a> {
+    table: &'a latex::SymbolTable,
+    floats: Vec<latex::Environment>,
+    definitions: Vec<Range>,
+}
+
+impl<'a> StructureChecker<'a> {
+    fn new(table: &'a latex::SymbolTable) -> Self {
+        let floats = table
+            .environments
+            .iter()
+            .copied()
+            .filter(|env| {
+                env.left
+                    .name(&table.tree)
+                    .map(latex::Token::text)
+                    .filter(|name| *name != "lstlisting")
+                    .and_then(OutlineCaptionKind::parse)
+                    .is_some()
+            })
+            .collect();
+
+        Self {
+            table,
+            floats,
+            definitions: definition_ranges(table),
+        }
+    }
+
+    fn check_floats(&self, references: &HashSet<String>) -> Vec<Diagnostic> {
+        let mut diagnostics = Vec::new();
+        for float in &self.floats {
+            let name = float.left.name(&self.table.tree).unwrap().text();
+            let range = self.table[float.left.parent].range();
+            let labels: Vec<_> = self
+                .table
+                .labels
+                .iter()
+                .filter(|label| label.kind == LatexLabelKind::Definition)
+                .filter(|label| {
+                    self.table
+                        .is_direct_child(*float, self.table[label.parent].start())
+                })
+                .collect();
+
+            match OutlineContext::find_float_caption(self.table, *float) {
+                Some(caption) => {
+                    let caption_start = self.table[caption.parent].start();
+                    for label in labels
+                        .iter()
+                        .filter(|label| self.table[label.parent].start() < caption_start)
+                    {
+                        diagnostics.push(create_diagnostic(
+                            self.table[label.parent].range(),
+                            DiagnosticSeverity::Warning,
+                            "label-before-caption",
+                            "The label is placed before the caption and does not refer to the float"
+                                .into(),
+                        ));
+                    }
+                }
+                None => diagnostics.push(create_diagnostic(
+                    range,
+                    DiagnosticSeverity::Warning,
+                    "missing-caption",
+                    format!("The `{}` environment has no caption", name),
+                )),
+            }
+
+            let is_referenced = labels.iter().any(|label| {
+                label
+                    .names(&self.table.tree)
+                    .iter()
+                    .any(|name| references.contains(name.text()))
+            });
+
+            if !is_referenced {
+                diagnostics.push(create_diagnostic(
+                    range,
+                    DiagnosticSeverity::Hint,
+                    "unreferenced-float",
+                    format!("The `{}` environment is never referenced", name),
+                ));
+            }
+        }
+        diagnostics
+    }
+
+    fn check_captions(&self) -> Vec<Diagnostic> {
+        self.table
+            .captions
+            .iter()
+            .map(|caption| self.table[caption.parent].range())
+            .filter(|range| !self.is_inside_definition(*range))
+            .filter(|range| {
+                !self
+                    .table
+                    .environments
+                    .iter()
+                    .filter(|env| {
+                        self.floats
+                            .iter()
+                            .any(|float| float.left.parent == env.left.parent)
+                            || env
+                                .left
+                                .name(&self.table.tree)
+                                .map_or(false, |name| CAPTION_ENVIRONMENTS.contains(&name.text()))
+                    })
+                    .any(|env| env.range(&self.table.tree).contains(range.start))
+            })
+            .map(|range| {
+                create_diagnostic(
+                    range,
+                    DiagnosticSeverity::Warning,
+                    "caption-outside-float",
+                    "\\caption is used outside of a float".into(),
+                )
+            })
+            .collect()
+    }
+
+    fn check_items(&self) -> Vec<Diagnostic> {
+        let lists: Vec<_> = self
+            .table
+            .environments
+            .iter()
+            .filter(|env| {
+                env.left.name(&self.table.tree).map_or(false, |name| {
+                    let name = name.text().trim_end_matches('*');
+                    LANGUAGE_DATA
+                        .list_environments
+                        .iter()
+                        .any(|env| env == name)
+                })
+            })
+            .map(|env| env.range(&self.table.tree))
+            .collect();
+
+        self.table
+            .items
+            .iter()
+            .map(|item| self.table[item.parent].range())
+            .filter(|range| !self.is_inside_definition(*range))
+            .filter(|range| !lists.iter().any(|list| list.contains(range.start)))
+            .map(|range| {
+                create_diagnostic(
+                    range,
+                    DiagnosticSeverity::Warning,
+                    "item-outside-list",
+                    "\\item is used outside of a list environment".into(),
+                )
+            })
+            .collect()
+    }
+
+    fn is_inside_definition(&self, range: Range) -> bool {
+        self.definitions
+            .iter()
+            .any(|definition| definition.contains(range.start))
+    }
+}
+
+/// Returns the ranges of the commands that define new commands or environments.
+/// Their arguments are not evaluated at the place of the definition.
+pub(super) fn definition_ranges(table: &latex::SymbolTable) -> Vec<Range> {
+    let mut ranges = Vec::new();
+    for node in &table.commands {
+        let name = match table.as_command(*node) {
+            Some(cmd) => cmd.name.text(),
+            None => continue,
+        };
+
+        if !LANGUAGE_DATA.is_definition_command(name) {
+            continue;
+        }
+
+        // The primitives take the defined command without braces (`\def\foo{...}`),
+        // so the body is attached to the defined command instead.
+        // `\let\foo\bar` is followed by the defined and the existing command.
+        let mut range = table[*node].range();
+        if LANGUAGE_DATA
+            .primitive_definition_commands
+            .iter()
+            .any(|cmd| cmd == name)
+        {
+            let count = if name == "\\let" { 2 } else { 1 };
+            let mut following: Vec<_> = table
+                .commands
+                .iter()
+                .map(|node| table[*node].range())
+                .filter(|other| other.start >= range.end)
+                .collect();
+            following.sort_by_key(|other| other.start);
+            if let Some(last) = following.into_iter().take(count).last() {
+                range.end = last.end;
+            }
+        }
+        ranges.push(range);
+    }
+    ranges
+}
+
+fn create_diagnostic(
+    range: Range,
+    severity: DiagnosticSeverity,
+    code: &str,
+    message: String,
+) -> Diagnostic {
+    Diagnostic {
+        source: Some("latex".into()),
+        code: Some(NumberOrString::String(code.into())),
+        severity: Some(severity),
+        ..Diagnostic::new_simple(range, message)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::diagnostics::tester::DiagnosticsTester;
+    use indoc::indoc;
+
+    fn diagnostics(text: &str) -> Vec<Diagnostic> {
+        let mut tester = DiagnosticsTester::new();
+        tester.file("main.tex", text);
+        let snapshot = tester.snapshot();
+
+        let provider = StructureDiagnosticsProvider::default();
+        provider.update(&snapshot, &tester.relations(&snapshot));
+        provider.get(&snapshot.find(&tester.uri("main.tex")).unwrap())
+    }
+
+    fn check(text: &str) -> Vec<String> {
+        diagnostics(text)
+            .into_iter()
+            .map(|diagnostic| match diagnostic.code {
+                Some(NumberOrString::String(code)) => code,
+                _ => unreachable!(),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn valid_float() {
+        let codes = check(indoc!(
+            r#"
+                \begin{figure}
+                    \caption{Foo}
+                    \label{fig:foo}
+                \end{figure}
+                \ref{fig:foo}
+            "#
+        ));
+        assert!(codes.is_empty());
+    }
+
+    #[test]
+    fn label_before_caption() {
+        let codes = check(indoc!(
+            r#"
+                \begin{table}
+                    \label{tab:foo}
+                    \caption{Foo}
+                \end{table}
+                \ref{tab:foo}
+            "#
+        ));
+        assert_eq!(codes, vec!["label-before-caption"]);
+    }
+
+    #[test]
+    fn missing_caption() {
+        let codes = check(indoc!(
+            r#"
+                \begin{figure*}
+                    \label{fig:foo}
+                \end{figure*}
+                \cref{fig:foo}
+            "#
+        ));
+        assert_eq!(codes, vec!["missing-caption"]);
+    }
+
+    #[test]
+    fn unreferenced_float() {
+        let codes = check(indoc!(
+            r#"
+                \begin{figure}
+                    \caption{Foo}
+                    \label{fig:foo}
+                \end{figure}
+            "#
+        ));
+        assert_eq!(codes, vec!["unreferenced-float"]);
+    }
+
+    #[test]
+    fn caption_outside_float() {
+        let codes = check(indoc!(
+            r#"
+                \caption{Foo}
+                \newcommand{\foo}{\caption{Bar}}
+            "#
+        ));
+        assert_eq!(codes, vec!["caption-outside-float"]);
+    }
+
+    #[test]
+    fn item_inside_definition() {
+        let codes = check(indoc!(
+            r#"
+                \DeclareRobustCommand*{\foo}{\item Foo}
+                \RenewDocumentCommand{\bar}{m}{\item #1}
+            "#
+        ));
+        assert!(codes.is_empty());
+    }
+
+    #[test]
+    fn item_outside_list() {
+        let diagnostics = diagnostics(indoc!(
+            r#"
+                \begin{itemize}
+                    \item Foo
+                \end{itemize}
+                \item Bar
+            "#
+        ));
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(
+            diagnostics[0].code,
+            Some(NumberOrString::String("item-outside-list".into()))
+        );
+        assert_eq!(diagnostics[0].severity, Some(DiagnosticSeverity::Warning));
+    }
+}
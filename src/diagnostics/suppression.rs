@@ -0,0 +1,168 @@
+use crate::protocol::{Diagnostic, NumberOrString};
+use once_cell::sync::Lazy;
+use regex::Regex;
+
+pub static SUPPRESSION_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        "(?:^|[^\\\\])%\\s*texlab:\\s*(?P<directive>ignore-next-line|disable)(?P<codes>[^\r\n]*)",
+    )
+    .unwrap()
+});
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum SuppressionScope {
+    Line(u64),
+    File,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Suppression {
+    pub scope: SuppressionScope,
+    pub codes: Vec<String>,
+}
+
+impl Suppression {
+    pub fn parse(text: &str) -> Vec<Self> {
+        let mut suppressions = Vec::new();
+        for (line, line_text) in text.lines().enumerate() {
+            if let Some(captures) = SUPPRESSION_REGEX.captures(line_text) {
+                let scope = match &captures["directive"] {
+                    "disable" => SuppressionScope::File,
+                    _ => SuppressionScope::Line(line as u64 + 1),
+                };
+
+                let codes = captures["codes"]
+                    .split(|c: char| c.is_whitespace() || c == ',')
+                    .filter(|code| !code.is_empty())
+                    .map(ToOwned::to_owned)
+                    .collect();
+
+                suppressions.push(Self { scope, codes });
+            }
+        }
+        suppressions
+    }
+
+    pub fn matches(&self, diagnostic: &Diagnostic) -> bool {
+        if let SuppressionScope::Line(line) = self.scope {
+            if diagnostic.range.start.line != line {
+                return false;
+            }
+        }
+
+        self.codes.is_empty()
+            || self.codes.iter().any(|code| {
+                diagnostic.source.as_ref() == Some(code)
+                    || diagnostic_name(diagnostic).as_ref() == Some(code)
+            })
+    }
+
+    pub fn apply(suppressions: &[Self], diagnostics: Vec<Diagnostic>) -> Vec<Diagnostic> {
+        diagnostics
+            .into_iter()
+            .filter(|diagnostic| {
+                !suppressions
+                    .iter()
+                    .any(|suppression| suppression.matches(diagnostic))
+            })
+            .collect()
+    }
+}
+
+/// Returns the name that is used to refer to the given diagnostic inside of a suppression comment.
+/// Numeric codes are prefixed with the source of the diagnostic (for example, `chktex-8`).
+pub fn diagnostic_name(diagnostic: &Diagnostic) -> Option<String> {
+    let code = match diagnostic.code.as_ref()? {
+        NumberOrString::Number(number) => number.to_string(),
+        NumberOrString::String(code) => code.clone(),
+    };
+
+    if code.chars().all(|c| c.is_ascii_digit()) {
+        let source = diagnostic.source.as_ref()?;
+        Some(format!("{}-{}", source, code))
+    } else {
+        Some(code)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::protocol::{Range, RangeExt};
+    use indoc::indoc;
+
+    fn create_diagnostic(line: u64, source: &str, code: &str) -> Diagnostic {
+        let range = Range::new_simple(line, 0, line, 1);
+        Diagnostic {
+            source: Some(source.into()),
+            code: Some(NumberOrString::String(code.into())),
+            ..Diagnostic::new_simple(range, String::new())
+        }
+    }
+
+    #[test]
+    fn parse_next_line() {
+        let suppressions = Suppression::parse(indoc!(
+            r#"
+                foo
+                % texlab: ignore-next-line chktex-8, chktex-24
+                bar
+            "#
+        ));
+        assert_eq!(
+            suppressions,
+            vec![Suppression {
+                scope: SuppressionScope::Line(2),
+                codes: vec!["chktex-8".into(), "chktex-24".into()],
+            }]
+        );
+    }
+
+    #[test]
+    fn parse_file() {
+        let suppressions = Suppression::parse("foo % texlab: disable undefined-reference");
+        assert_eq!(
+            suppressions,
+            vec![Suppression {
+                scope: SuppressionScope::File,
+                codes: vec!["undefined-reference".into()],
+            }]
+        );
+    }
+
+    #[test]
+    fn parse_escaped_percent() {
+        let suppressions = Suppression::parse("\\% texlab: disable");
+        assert!(suppressions.is_empty());
+    }
+
+    #[test]
+    fn apply_code() {
+        let suppressions = Suppression::parse("% texlab: ignore-next-line chktex-8");
+        let diagnostics = vec![
+            create_diagnostic(1, "chktex", "8"),
+            create_diagnostic(1, "chktex", "24"),
+            create_diagnostic(2, "chktex", "8"),
+        ];
+        let actual = Suppression::apply(&suppressions, diagnostics.clone());
+        assert_eq!(actual, diagnostics[1..].to_vec());
+    }
+
+    #[test]
+    fn apply_source() {
+        let suppressions = Suppression::parse("% texlab: disable chktex");
+        let diagnostics = vec![
+            create_diagnostic(1, "chktex", "8"),
+            create_diagnostic(2, "latex", "undefined-reference"),
+        ];
+        let actual = Suppression::apply(&suppressions, diagnostics.clone());
+        assert_eq!(actual, diagnostics[1..].to_vec());
+    }
+
+    #[test]
+    fn apply_everything() {
+        let suppressions = Suppression::parse("% texlab: disable");
+        let diagnostics = vec![create_diagnostic(0, "bibtex", "missing-comma")];
+        assert!(Suppression::apply(&suppressions, diagnostics).is_empty());
+    }
+}
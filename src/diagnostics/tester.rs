@@ -0,0 +1,87 @@
+use crate::{
+    protocol::{Options, Uri},
+    tex::{Language, Resolver},
+    workspace::{Document, DocumentParams, Relations, Snapshot},
+};
+use std::{env, path::PathBuf};
+
+/// Builds the snapshots that are used to test the diagnostics providers.
+/// The documents are placed in the current directory
+/// and their language is determined by the extension of their name.
+#[derive(Debug)]
+pub struct DiagnosticsTester {
+    files: Vec<(String, String)>,
+    resolver: Resolver,
+    options: Options,
+    current_dir: PathBuf,
+}
+
+impl Default for DiagnosticsTester {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl DiagnosticsTester {
+    pub fn new() -> Self {
+        Self {
+            files: Vec::new(),
+            resolver: Resolver::default(),
+            options: Options::default(),
+            current_dir: env::current_dir().unwrap(),
+        }
+    }
+
+    pub fn file<S, T>(&mut self, name: S, text: T) -> &mut Self
+    where
+        S: Into<String>,
+        T: Into<String>,
+    {
+        self.files.push((name.into(), text.into()));
+        self
+    }
+
+    pub fn resolver(&mut self, resolver: Resolver) -> &mut Self {
+        self.resolver = resolver;
+        self
+    }
+
+    pub fn options(&mut self, options: Options) -> &mut Self {
+        self.options = options;
+        self
+    }
+
+    pub fn current_dir<P: Into<PathBuf>>(&mut self, path: P) -> &mut Self {
+        self.current_dir = path.into();
+        self
+    }
+
+    pub fn uri(&self, name: &str) -> Uri {
+        Uri::from_file_path(self.current_dir.join(name)).unwrap()
+    }
+
+    pub fn snapshot(&self) -> Snapshot {
+        let mut snapshot = Snapshot::new();
+        for (name, text) in &self.files {
+            let language = name
+                .rsplit('.')
+                .next()
+                .and_then(Language::by_extension)
+                .unwrap_or(Language::Latex);
+
+            snapshot.push(Document::open(DocumentParams {
+                uri: self.uri(name),
+                text: text.clone(),
+                language,
+                resolver: &self.resolver,
+                options: &self.options,
+                current_dir: &self.current_dir,
+            }));
+        }
+        snapshot
+    }
+
+    pub fn relations(&self, snapshot: &Snapshot) -> Relations {
+        snapshot.all_relations(&self.options, &self.current_dir)
+    }
+}
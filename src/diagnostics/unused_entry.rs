@@ -0,0 +1,118 @@
+use super::remove_stale;
+use crate::{
+    bibliography::CitationUsage,
+    protocol::{Diagnostic, DiagnosticSeverity, DiagnosticTag, NumberOrString, Uri},
+    syntax::SyntaxNode,
+    workspace::{Document, Relations, Snapshot},
+};
+use chashmap::CHashMap;
+
+#[derive(Debug, Default)]
+pub struct UnusedEntryDiagnosticsProvider {
+    diagnostics_by_uri: CHashMap<Uri, Vec<Diagnostic>>,
+}
+
+impl UnusedEntryDiagnosticsProvider {
+    pub fn get(&self, doc: &Document) -> Vec<Diagnostic> {
+        match self.diagnostics_by_uri.get(&doc.uri) {
+            Some(diagnostics) => diagnostics.to_owned(),
+            None => Vec::new(),
+        }
+    }
+
+    pub fn update(&self, snapshot: &Snapshot, relations: &Relations) {
+        remove_stale(&self.diagnostics_by_uri, snapshot);
+        for doc in &snapshot.0 {
+            let tree = match doc.content.as_bibtex() {
+                Some(tree) => tree,
+                None => continue,
+            };
+
+            let usage = match CitationUsage::find(relations.get(&doc.uri)) {
+                Some(usage) => usage,
+                None => {
+                    self.diagnostics_by_uri.remove(&doc.uri);
+                    continue;
+                }
+            };
+
+            let entries = usage.used_entries(tree);
+            let mut diagnostics = Vec::new();
+            for node in tree.children(tree.root) {
+                let entry = match tree.as_entry(node) {
+                    Some(entry) if !entry.is_comment() && !entries.contains(&node) => entry,
+                    _ => continue,
+                };
+
+                if let Some(key) = &entry.key {
+                    diagnostics.push(Diagnostic {
+                        source: Some("bibtex".into()),
+                        code: Some(NumberOrString::String("unused-entry".into())),
+                        severity: Some(DiagnosticSeverity::Hint),
+                        tags: Some(vec![DiagnosticTag::Unnecessary]),
+                        ..Diagnostic::new_simple(
+                            entry.range(),
+                            format!("The entry `{}` is never cited", key.text()),
+                        )
+                    });
+                }
+            }
+            self.diagnostics_by_uri.insert(doc.uri.clone(), diagnostics);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::diagnostics::tester::DiagnosticsTester;
+
+    fn check(files: Vec<(&str, &str)>) -> Vec<String> {
+        let mut tester = DiagnosticsTester::new();
+        for (name, text) in &files {
+            tester.file(*name, *text);
+        }
+        let snapshot = tester.snapshot();
+
+        let provider = UnusedEntryDiagnosticsProvider::default();
+        provider.update(&snapshot, &tester.relations(&snapshot));
+        provider
+            .get(&snapshot.find(&tester.uri("main.bib")).unwrap())
+            .into_iter()
+            .map(|diagnostic| diagnostic.message)
+            .collect()
+    }
+
+    #[test]
+    fn unused() {
+        let messages = check(vec![
+            ("main.bib", "@article{foo,}\n@article{bar,}"),
+            ("main.tex", "\\addbibresource{main.bib}\n\\cite{foo}"),
+        ]);
+        assert_eq!(messages, vec!["The entry `bar` is never cited"]);
+    }
+
+    #[test]
+    fn crossref() {
+        let messages = check(vec![
+            ("main.bib", "@book{foo,}\n@inbook{bar, crossref = {foo}}"),
+            ("main.tex", "\\addbibresource{main.bib}\n\\cite{bar}"),
+        ]);
+        assert!(messages.is_empty());
+    }
+
+    #[test]
+    fn nocite_all() {
+        let messages = check(vec![
+            ("main.bib", "@article{foo,}"),
+            ("main.tex", "\\addbibresource{main.bib}\n\\nocite{*}"),
+        ]);
+        assert!(messages.is_empty());
+    }
+
+    #[test]
+    fn standalone_bibliography() {
+        let messages = check(vec![("main.bib", "@article{foo,}")]);
+        assert!(messages.is_empty());
+    }
+}
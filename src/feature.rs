@@ -1,12 +1,14 @@
 use crate::{
+    bibliography::library_uris,
     components::{Component, COMPONENT_DATABASE},
     protocol::*,
-    tex::{Distribution, Language, UnknownDistribution},
+    tex::{Distribution, Language, Resolver, UnknownDistribution},
     workspace::{Document, DocumentContent, DocumentParams, Snapshot},
 };
 use async_trait::async_trait;
 use itertools::Itertools;
 use std::{
+    collections::HashMap,
     env,
     path::{Path, PathBuf},
     sync::Arc,
@@ -17,6 +19,7 @@ pub struct DocumentView {
     pub snapshot: Arc<Snapshot>,
     pub current: Arc<Document>,
     pub related: Vec<Arc<Document>>,
+    pub libraries: Vec<Arc<Document>>,
 }
 
 impl DocumentView {
@@ -27,10 +30,17 @@ impl DocumentView {
         current_dir: &Path,
     ) -> Self {
         let related = snapshot.relations(&current.uri, options, current_dir);
+        let libraries = library_uris(options, current_dir)
+            .iter()
+            .filter(|uri| related.iter().all(|doc| doc.uri != **uri))
+            .filter_map(|uri| snapshot.find(uri))
+            .collect();
+
         Self {
             snapshot,
             current,
             related,
+            libraries,
         }
     }
 
@@ -85,6 +95,11 @@ impl<P> FeatureRequest<P> {
     pub fn related(&self) -> &[Arc<Document>] {
         &self.view.related
     }
+
+    /// The global bibliographies that are not part of the project.
+    pub fn libraries(&self) -> &[Arc<Document>] {
+        &self.view.libraries
+    }
 }
 
 #[async_trait]
@@ -167,10 +182,13 @@ pub struct FeatureTester {
     position: Position,
     new_name: String,
     include_declaration: bool,
+    diagnostics: Vec<Diagnostic>,
     client_capabilities: Arc<ClientCapabilities>,
     current_dir: Arc<PathBuf>,
     root_dir: Option<PathBuf>,
     output_dir: Option<PathBuf>,
+    libraries: Vec<PathBuf>,
+    distro_files: HashMap<String, PathBuf>,
 }
 
 impl Default for FeatureTester {
@@ -188,10 +206,13 @@ impl FeatureTester {
             position: Position::default(),
             new_name: String::new(),
             include_declaration: false,
+            diagnostics: Vec::new(),
             client_capabilities: Arc::default(),
             current_dir: Arc::new(env::temp_dir()),
             root_dir: None,
             output_dir: None,
+            libraries: Vec::new(),
+            distro_files: HashMap::new(),
         }
     }
 
@@ -224,6 +245,16 @@ impl FeatureTester {
         self
     }
 
+    pub fn diagnostic(&mut self, diagnostic: Diagnostic) -> &mut Self {
+        self.diagnostics.push(diagnostic);
+        self
+    }
+
+    pub fn client_capabilities(&mut self, capabilities: ClientCapabilities) -> &mut Self {
+        self.client_capabilities = Arc::new(capabilities);
+        self
+    }
+
     pub fn root_directory<P: Into<PathBuf>>(&mut self, path: P) -> &mut Self {
         self.root_dir = Some(path.into());
         self
@@ -234,6 +265,20 @@ impl FeatureTester {
         self
     }
 
+    pub fn library<P: Into<PathBuf>>(&mut self, path: P) -> &mut Self {
+        self.libraries.push(path.into());
+        self
+    }
+
+    pub fn distro_file<P: Into<PathBuf>>(&mut self, path: P) -> &mut Self {
+        let path = path.into();
+        let name = path.file_name().unwrap().to_string_lossy().into_owned();
+        self.distro_files.insert(name, path);
+        let resolver = Resolver::new(self.distro_files.clone());
+        self.distro = Arc::new(UnknownDistribution::new(resolver));
+        self
+    }
+
     pub fn uri(name: &str) -> Uri {
         let path = env::temp_dir().join(name);
         Uri::from_file_path(path).unwrap()
@@ -254,7 +299,10 @@ impl FeatureTester {
                 root_directory: self.root_dir.clone(),
                 ..LatexOptions::default()
             }),
-            ..Options::default()
+            bibtex: Some(BibtexOptions {
+                libraries: Some(self.libraries.clone()),
+                ..BibtexOptions::default()
+            }),
         }
     }
 
@@ -305,6 +353,24 @@ impl FeatureTester {
         provider.execute(&req).await
     }
 
+    pub async fn test_code_action<F, O>(&self, provider: F) -> O
+    where
+        F: FeatureProvider<Params = CodeActionParams, Output = O>,
+    {
+        let params = CodeActionParams {
+            text_document: self.identifier(),
+            range: Range::new(self.position, self.position),
+            context: CodeActionContext {
+                diagnostics: self.diagnostics.clone(),
+                only: None,
+            },
+            work_done_progress_params: WorkDoneProgressParams::default(),
+            partial_result_params: PartialResultParams::default(),
+        };
+        let req = self.request(params).await;
+        provider.execute(&req).await
+    }
+
     pub async fn test_completion<F, O>(&self, provider: F) -> O
     where
         F: FeatureProvider<Params = CompletionParams, Output = O>,
@@ -0,0 +1,92 @@
+use crate::{
+    feature::{FeatureProvider, FeatureRequest},
+    protocol::{DocumentHighlight, DocumentHighlightKind, RangeExt, TextDocumentPositionParams},
+    syntax::{latex, SyntaxNode},
+    workspace::DocumentContent,
+};
+use async_trait::async_trait;
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub struct LatexCitationHighlightProvider;
+
+#[async_trait]
+impl FeatureProvider for LatexCitationHighlightProvider {
+    type Params = TextDocumentPositionParams;
+    type Output = Vec<DocumentHighlight>;
+
+    async fn execute<'a>(&'a self, req: &'a FeatureRequest<Self::Params>) -> Self::Output {
+        let mut highlights = Vec::new();
+        if let DocumentContent::Latex(table) = &req.current().content {
+            if let Some(key) = table
+                .citations
+                .iter()
+                .flat_map(|citation| citation.keys(&table))
+                .find(|key| key.range().contains(req.params.position))
+                .map(latex::Token::text)
+            {
+                for citation in &table.citations {
+                    for citation_key in citation.keys(&table) {
+                        if citation_key.text() == key {
+                            let highlight = DocumentHighlight {
+                                range: citation_key.range(),
+                                kind: Some(DocumentHighlightKind::Read),
+                            };
+                            highlights.push(highlight);
+                        }
+                    }
+                }
+            }
+        }
+        highlights
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{feature::FeatureTester, protocol::Range};
+    use indoc::indoc;
+
+    #[tokio::test]
+    async fn has_citation() {
+        let actual_highlights = FeatureTester::new()
+            .file(
+                "foo.tex",
+                indoc!(
+                    r#"
+                        \cite{foo,bar}
+                        \cite{foo}
+                    "#
+                ),
+            )
+            .main("foo.tex")
+            .position(1, 7)
+            .test_position(LatexCitationHighlightProvider)
+            .await;
+
+        let expected_highlights = vec![
+            DocumentHighlight {
+                range: Range::new_simple(0, 6, 0, 9),
+                kind: Some(DocumentHighlightKind::Read),
+            },
+            DocumentHighlight {
+                range: Range::new_simple(1, 6, 1, 9),
+                kind: Some(DocumentHighlightKind::Read),
+            },
+        ];
+
+        assert_eq!(actual_highlights, expected_highlights);
+    }
+
+    #[tokio::test]
+    async fn no_citation_latex() {
+        let actual_highlights = FeatureTester::new()
+            .file("foo.tex", "")
+            .main("foo.tex")
+            .position(0, 0)
+            .test_position(LatexCitationHighlightProvider)
+            .await;
+
+        assert!(actual_highlights.is_empty());
+    }
+}
@@ -0,0 +1,131 @@
+use crate::{
+    feature::{FeatureProvider, FeatureRequest},
+    protocol::{DocumentHighlight, DocumentHighlightKind, TextDocumentPositionParams},
+    syntax::SyntaxNode,
+    workspace::DocumentContent,
+};
+use async_trait::async_trait;
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub struct LatexCommandHighlightProvider;
+
+#[async_trait]
+impl FeatureProvider for LatexCommandHighlightProvider {
+    type Params = TextDocumentPositionParams;
+    type Output = Vec<DocumentHighlight>;
+
+    async fn execute<'a>(&'a self, req: &'a FeatureRequest<Self::Params>) -> Self::Output {
+        let mut highlights = Vec::new();
+        if let DocumentContent::Latex(table) = &req.current().content {
+            if let Some(name) = table
+                .find(req.params.position)
+                .last()
+                .and_then(|node| table.as_command(*node))
+                .map(|cmd| cmd.name.text())
+            {
+                let definitions: Vec<_> = table
+                    .command_definitions
+                    .iter()
+                    .map(|def| def.definition)
+                    .chain(table.math_operators.iter().map(|op| op.definition))
+                    .collect();
+
+                for node in &table.commands {
+                    if let Some(cmd) = table.as_command(*node) {
+                        if cmd.name.text() == name {
+                            let kind = if definitions.contains(node) {
+                                DocumentHighlightKind::Write
+                            } else {
+                                DocumentHighlightKind::Read
+                            };
+
+                            let highlight = DocumentHighlight {
+                                range: cmd.name.range(),
+                                kind: Some(kind),
+                            };
+                            highlights.push(highlight);
+                        }
+                    }
+                }
+            }
+        }
+        highlights
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{feature::FeatureTester, protocol::Range};
+    use indoc::indoc;
+
+    #[tokio::test]
+    async fn user_defined_command() {
+        let actual_highlights = FeatureTester::new()
+            .file(
+                "foo.tex",
+                indoc!(
+                    r#"
+                        \newcommand{\foo}{Foo}
+                        \foo \bar \foo
+                    "#
+                ),
+            )
+            .main("foo.tex")
+            .position(1, 12)
+            .test_position(LatexCommandHighlightProvider)
+            .await;
+
+        let expected_highlights = vec![
+            DocumentHighlight {
+                range: Range::new_simple(0, 12, 0, 16),
+                kind: Some(DocumentHighlightKind::Write),
+            },
+            DocumentHighlight {
+                range: Range::new_simple(1, 0, 1, 4),
+                kind: Some(DocumentHighlightKind::Read),
+            },
+            DocumentHighlight {
+                range: Range::new_simple(1, 10, 1, 14),
+                kind: Some(DocumentHighlightKind::Read),
+            },
+        ];
+
+        assert_eq!(actual_highlights, expected_highlights);
+    }
+
+    #[tokio::test]
+    async fn builtin_command() {
+        let actual_highlights = FeatureTester::new()
+            .file("foo.tex", r#"\alpha + \beta = \alpha"#)
+            .main("foo.tex")
+            .position(0, 2)
+            .test_position(LatexCommandHighlightProvider)
+            .await;
+
+        let expected_highlights = vec![
+            DocumentHighlight {
+                range: Range::new_simple(0, 0, 0, 6),
+                kind: Some(DocumentHighlightKind::Read),
+            },
+            DocumentHighlight {
+                range: Range::new_simple(0, 17, 0, 23),
+                kind: Some(DocumentHighlightKind::Read),
+            },
+        ];
+
+        assert_eq!(actual_highlights, expected_highlights);
+    }
+
+    #[tokio::test]
+    async fn no_command_bibtex() {
+        let actual_highlights = FeatureTester::new()
+            .file("foo.bib", "")
+            .main("foo.bib")
+            .position(0, 0)
+            .test_position(LatexCommandHighlightProvider)
+            .await;
+
+        assert!(actual_highlights.is_empty());
+    }
+}
@@ -0,0 +1,103 @@
+use crate::{
+    feature::{FeatureProvider, FeatureRequest},
+    protocol::{DocumentHighlight, DocumentHighlightKind, RangeExt, TextDocumentPositionParams},
+    syntax::SyntaxNode,
+    workspace::DocumentContent,
+};
+use async_trait::async_trait;
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub struct LatexEnvironmentHighlightProvider;
+
+#[async_trait]
+impl FeatureProvider for LatexEnvironmentHighlightProvider {
+    type Params = TextDocumentPositionParams;
+    type Output = Vec<DocumentHighlight>;
+
+    async fn execute<'a>(&'a self, req: &'a FeatureRequest<Self::Params>) -> Self::Output {
+        let mut highlights = Vec::new();
+        if let DocumentContent::Latex(table) = &req.current().content {
+            for env in &table.environments {
+                let names = [env.left.name(&table), env.right.name(&table)];
+                if names
+                    .iter()
+                    .flatten()
+                    .any(|name| name.range().contains(req.params.position))
+                {
+                    for name in names.iter().flatten() {
+                        let highlight = DocumentHighlight {
+                            range: name.range(),
+                            kind: Some(DocumentHighlightKind::Text),
+                        };
+                        highlights.push(highlight);
+                    }
+                    break;
+                }
+            }
+        }
+        highlights
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{feature::FeatureTester, protocol::Range};
+    use indoc::indoc;
+
+    #[tokio::test]
+    async fn nested_environment() {
+        let actual_highlights = FeatureTester::new()
+            .file(
+                "foo.tex",
+                indoc!(
+                    r#"
+                        \begin{foo}
+                        \begin{foo}\end{foo}
+                        \end{foo}
+                    "#
+                ),
+            )
+            .main("foo.tex")
+            .position(2, 6)
+            .test_position(LatexEnvironmentHighlightProvider)
+            .await;
+
+        let expected_highlights = vec![
+            DocumentHighlight {
+                range: Range::new_simple(0, 7, 0, 10),
+                kind: Some(DocumentHighlightKind::Text),
+            },
+            DocumentHighlight {
+                range: Range::new_simple(2, 5, 2, 8),
+                kind: Some(DocumentHighlightKind::Text),
+            },
+        ];
+
+        assert_eq!(actual_highlights, expected_highlights);
+    }
+
+    #[tokio::test]
+    async fn no_environment_latex() {
+        let actual_highlights = FeatureTester::new()
+            .file("foo.tex", "")
+            .main("foo.tex")
+            .position(0, 0)
+            .test_position(LatexEnvironmentHighlightProvider)
+            .await;
+
+        assert!(actual_highlights.is_empty());
+    }
+
+    #[tokio::test]
+    async fn no_environment_bibtex() {
+        let actual_highlights = FeatureTester::new()
+            .file("foo.bib", "")
+            .main("foo.bib")
+            .position(0, 0)
+            .test_position(LatexEnvironmentHighlightProvider)
+            .await;
+
+        assert!(actual_highlights.is_empty());
+    }
+}
@@ -1,6 +1,12 @@
+mod latex_citation;
+mod latex_cmd;
+mod latex_env;
 mod latex_label;
 
-use self::latex_label::LatexLabelHighlightProvider;
+use self::{
+    latex_citation::LatexCitationHighlightProvider, latex_cmd::LatexCommandHighlightProvider,
+    latex_env::LatexEnvironmentHighlightProvider, latex_label::LatexLabelHighlightProvider,
+};
 use crate::{
     feature::{ConcatProvider, FeatureProvider, FeatureRequest},
     protocol::{DocumentHighlight, TextDocumentPositionParams},
@@ -14,7 +20,12 @@ pub struct HighlightProvider {
 impl HighlightProvider {
     pub fn new() -> Self {
         Self {
-            provider: ConcatProvider::new(vec![Box::new(LatexLabelHighlightProvider)]),
+            provider: ConcatProvider::new(vec![
+                Box::new(LatexCitationHighlightProvider),
+                Box::new(LatexCommandHighlightProvider),
+                Box::new(LatexEnvironmentHighlightProvider),
+                Box::new(LatexLabelHighlightProvider),
+            ]),
         }
     }
 }
@@ -1,7 +1,10 @@
 use crate::{
+    bibliography::bib_item_text,
     citeproc::render_citation,
     feature::{FeatureProvider, FeatureRequest},
-    protocol::{Hover, HoverContents, RangeExt, TextDocumentPositionParams},
+    protocol::{
+        Hover, HoverContents, MarkupContent, MarkupKind, RangeExt, TextDocumentPositionParams,
+    },
     syntax::{bibtex, Span, SyntaxNode},
     workspace::DocumentContent,
 };
@@ -17,7 +20,10 @@ impl FeatureProvider for LatexCitationHoverProvider {
     type Output = Option<Hover>;
 
     async fn execute<'a>(&'a self, req: &'a FeatureRequest<Self::Params>) -> Self::Output {
-        let (tree, src_key, entry) = Self::get_entry(req)?;
+        let (tree, src_key, entry) = match Self::get_entry(req) {
+            Some(entry) => entry,
+            None => return Self::get_bib_item_hover(req),
+        };
         if entry.is_comment() {
             None
         } else {
@@ -44,6 +50,7 @@ impl LatexCitationHoverProvider {
         for tree in req
             .related()
             .iter()
+            .chain(req.libraries())
             .filter_map(|doc| doc.content.as_bibtex())
         {
             for entry in tree
@@ -60,12 +67,27 @@ impl LatexCitationHoverProvider {
         None
     }
 
+    fn get_bib_item_hover(req: &FeatureRequest<TextDocumentPositionParams>) -> Option<Hover> {
+        let key = Self::get_key(req)?;
+        req.related().iter().find_map(|doc| {
+            let bib_item = doc.content.as_latex()?.find_bib_item(&key.text)?;
+            Some(Hover {
+                contents: HoverContents::Markup(MarkupContent {
+                    kind: MarkupKind::PlainText,
+                    value: bib_item_text(&doc.text, bib_item),
+                }),
+                range: Some(key.range()),
+            })
+        })
+    }
+
     fn get_key(req: &FeatureRequest<TextDocumentPositionParams>) -> Option<&Span> {
         match &req.current().content {
             DocumentContent::Latex(table) => table
                 .citations
                 .iter()
                 .flat_map(|citation| citation.keys(&table))
+                .chain(table.bib_items.iter().map(|bib_item| bib_item.key(&table)))
                 .find(|key| key.range().contains(req.params.position))
                 .map(|token| &token.span),
             DocumentContent::Bibtex(tree) => tree
@@ -83,7 +105,7 @@ mod tests {
     use super::*;
     use crate::{
         feature::FeatureTester,
-        protocol::{MarkupContent, MarkupKind, Range, RangeExt},
+        protocol::{Range, RangeExt},
     };
     use indoc::indoc;
 
@@ -176,4 +198,62 @@ mod tests {
 
         assert_eq!(actual_hover, expected_hover);
     }
+
+    #[tokio::test]
+    async fn library() {
+        let actual_hover = FeatureTester::new()
+            .file(
+                "library.bib",
+                "@article{foo, author = {Foo Bar}, title = {Baz Qux}, year = 1337}",
+            )
+            .file("main.tex", r#"\cite{foo}"#)
+            .main("main.tex")
+            .library("library.bib")
+            .position(0, 7)
+            .test_position(LatexCitationHoverProvider)
+            .await
+            .unwrap();
+
+        let expected_hover = Hover {
+            contents: HoverContents::Markup(MarkupContent {
+                kind: MarkupKind::Markdown,
+                value: "Bar, F. (1337). *Baz Qux*.".into(),
+            }),
+            range: Some(Range::new_simple(0, 6, 0, 9)),
+        };
+
+        assert_eq!(actual_hover, expected_hover);
+    }
+
+    #[tokio::test]
+    async fn bib_item() {
+        let actual_hover = FeatureTester::new()
+            .file(
+                "main.tex",
+                indoc!(
+                    r#"
+                        \cite{foo}
+                        \begin{thebibliography}{1}
+                        \bibitem{foo} Foo Bar.
+                        \newblock Baz Qux.
+                        \end{thebibliography}
+                    "#
+                ),
+            )
+            .main("main.tex")
+            .position(0, 7)
+            .test_position(LatexCitationHoverProvider)
+            .await
+            .unwrap();
+
+        let expected_hover = Hover {
+            contents: HoverContents::Markup(MarkupContent {
+                kind: MarkupKind::PlainText,
+                value: "Foo Bar. Baz Qux.".into(),
+            }),
+            range: Some(Range::new_simple(0, 6, 0, 9)),
+        };
+
+        assert_eq!(actual_hover, expected_hover);
+    }
 }
@@ -13,6 +13,8 @@ cfg_if::cfg_if! {
     }
 }
 
+pub mod bibliography;
+pub mod code_action;
 pub mod completion;
 pub mod components;
 pub mod definition;
@@ -27,6 +29,7 @@ pub mod outline;
 pub mod protocol;
 pub mod reference;
 pub mod rename;
+pub mod spelling;
 pub mod symbol;
 pub mod syntax;
 pub mod tex;
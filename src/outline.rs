@@ -126,7 +126,7 @@ impl OutlineCaptionKind {
     }
 
     pub fn parse(env_name: &str) -> Option<Self> {
-        match env_name {
+        match env_name.trim_end_matches('*') {
             "figure" | "subfigure" => Some(Self::Figure),
             "table" | "subtable" => Some(Self::Table),
             "listing" | "lstlisting" => Some(Self::Listing),
@@ -250,10 +250,7 @@ impl OutlineContext {
             .filter(|env| !env.is_root(&table))
             .find(|env| env.range(&table).contains(label_range.start))?;
 
-        let caption = table
-            .captions
-            .iter()
-            .find(|cap| table.is_direct_child(*caption_env, table[cap.parent].start()))?;
+        let caption = Self::find_float_caption(table, *caption_env)?;
 
         let caption_text = table.print_group_content(
             caption.parent,
@@ -277,6 +274,18 @@ impl OutlineContext {
         })
     }
 
+    /// Finds the caption that belongs to the given float environment.
+    pub fn find_float_caption(
+        table: &latex::SymbolTable,
+        env: latex::Environment,
+    ) -> Option<latex::Caption> {
+        table
+            .captions
+            .iter()
+            .copied()
+            .find(|cap| table.is_direct_child(env, table[cap.parent].start()))
+    }
+
     fn find_theorem(
         view: &DocumentView,
         main_table: &latex::SymbolTable,
@@ -435,3 +444,25 @@ impl OutlineContext {
         None
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn caption_kind_starred_environment() {
+        assert_eq!(
+            OutlineCaptionKind::parse("figure*"),
+            Some(OutlineCaptionKind::Figure)
+        );
+        assert_eq!(
+            OutlineCaptionKind::parse("table*"),
+            Some(OutlineCaptionKind::Table)
+        );
+    }
+
+    #[test]
+    fn caption_kind_unknown_environment() {
+        assert_eq!(OutlineCaptionKind::parse("itemize*"), None);
+    }
+}
@@ -24,4 +24,10 @@ pub trait LspClient {
 
     #[jsonrpc_method("window/logMessage", kind = "notification")]
     async fn log_message(&self, params: LogMessageParams);
+
+    #[jsonrpc_method("workspace/applyEdit", kind = "request")]
+    async fn apply_edit(
+        &self,
+        params: ApplyWorkspaceEditParams,
+    ) -> Result<ApplyWorkspaceEditResponse>;
 }
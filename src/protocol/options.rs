@@ -32,6 +32,7 @@ pub struct LatexForwardSearchOptions {
 pub struct LatexLintOptions {
     pub on_change: Option<bool>,
     pub on_save: Option<bool>,
+    pub accessibility: Option<bool>,
 }
 
 impl LatexLintOptions {
@@ -42,6 +43,10 @@ impl LatexLintOptions {
     pub fn on_save(&self) -> bool {
         self.on_save.unwrap_or(false)
     }
+
+    pub fn accessibility(&self) -> bool {
+        self.accessibility.unwrap_or(false)
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Default, Serialize, Deserialize)]
@@ -81,6 +86,65 @@ impl LatexBuildOptions {
     }
 }
 
+#[derive(Debug, PartialEq, Eq, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LatexSpellingOptions {
+    pub enabled: Option<bool>,
+    pub language: Option<String>,
+    pub dictionary_directories: Option<Vec<PathBuf>>,
+    pub project_dictionary: Option<PathBuf>,
+}
+
+impl LatexSpellingOptions {
+    pub fn enabled(&self) -> bool {
+        self.enabled.unwrap_or(false)
+    }
+
+    pub fn project_dictionary(&self) -> PathBuf {
+        self.project_dictionary
+            .as_ref()
+            .map(Clone::clone)
+            .unwrap_or_else(|| PathBuf::from(".texlab-dictionary"))
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LatexLabelOptions {
+    pub section_prefix: Option<String>,
+    pub figure_prefix: Option<String>,
+    pub table_prefix: Option<String>,
+    pub listing_prefix: Option<String>,
+    pub algorithm_prefix: Option<String>,
+    pub equation_prefix: Option<String>,
+}
+
+impl LatexLabelOptions {
+    pub fn section_prefix(&self) -> &str {
+        self.section_prefix.as_deref().unwrap_or("sec:")
+    }
+
+    pub fn figure_prefix(&self) -> &str {
+        self.figure_prefix.as_deref().unwrap_or("fig:")
+    }
+
+    pub fn table_prefix(&self) -> &str {
+        self.table_prefix.as_deref().unwrap_or("tab:")
+    }
+
+    pub fn listing_prefix(&self) -> &str {
+        self.listing_prefix.as_deref().unwrap_or("lst:")
+    }
+
+    pub fn algorithm_prefix(&self) -> &str {
+        self.algorithm_prefix.as_deref().unwrap_or("alg:")
+    }
+
+    pub fn equation_prefix(&self) -> &str {
+        self.equation_prefix.as_deref().unwrap_or("eq:")
+    }
+}
+
 #[derive(Debug, PartialEq, Eq, Clone, Default, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct LatexOptions {
@@ -88,12 +152,15 @@ pub struct LatexOptions {
     pub lint: Option<LatexLintOptions>,
     pub build: Option<LatexBuildOptions>,
     pub root_directory: Option<PathBuf>,
+    pub spelling: Option<LatexSpellingOptions>,
+    pub label: Option<LatexLabelOptions>,
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Default, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct BibtexOptions {
     pub formatting: Option<BibtexFormattingOptions>,
+    pub libraries: Option<Vec<PathBuf>>,
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Default, Serialize, Deserialize)]
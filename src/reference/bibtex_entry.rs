@@ -19,13 +19,27 @@ impl FeatureProvider for BibtexEntryReferenceProvider {
         if let Some(key) = Self::find_key(req) {
             for doc in req.related() {
                 match &doc.content {
-                    DocumentContent::Latex(table) => table
-                        .citations
-                        .iter()
-                        .flat_map(|citation| citation.keys(&table))
-                        .filter(|citation| citation.text() == key)
-                        .map(|citation| Location::new(doc.uri.clone().into(), citation.range()))
-                        .for_each(|location| refs.push(location)),
+                    DocumentContent::Latex(table) => {
+                        table
+                            .citations
+                            .iter()
+                            .flat_map(|citation| citation.keys(&table))
+                            .filter(|citation| citation.text() == key)
+                            .map(|citation| Location::new(doc.uri.clone().into(), citation.range()))
+                            .for_each(|location| refs.push(location));
+
+                        if req.params.context.include_declaration {
+                            table
+                                .bib_items
+                                .iter()
+                                .map(|bib_item| bib_item.key(&table))
+                                .filter(|key_tok| key_tok.text() == key)
+                                .map(|key_tok| {
+                                    Location::new(doc.uri.clone().into(), key_tok.range())
+                                })
+                                .for_each(|location| refs.push(location));
+                        }
+                    }
                     DocumentContent::Bibtex(tree) => {
                         if req.params.context.include_declaration {
                             let uri: Url = doc.uri.clone().into();
@@ -52,6 +66,7 @@ impl BibtexEntryReferenceProvider {
                 .citations
                 .iter()
                 .flat_map(|citation| citation.keys(&table))
+                .chain(table.bib_items.iter().map(|bib_item| bib_item.key(&table)))
                 .find(|key| key.range().contains(pos))
                 .map(latex::Token::text),
             DocumentContent::Bibtex(tree) => tree
@@ -192,6 +207,40 @@ mod tests {
         assert_eq!(actual_refs, expected_refs);
     }
 
+    #[tokio::test]
+    async fn bib_item_include_declaration() {
+        let actual_refs = FeatureTester::new()
+            .file(
+                "main.tex",
+                indoc!(
+                    r#"
+                        \cite{foo}
+                        \begin{thebibliography}{1}
+                        \bibitem{foo} Foo
+                        \end{thebibliography}
+                    "#
+                ),
+            )
+            .main("main.tex")
+            .position(2, 10)
+            .include_declaration()
+            .test_reference(BibtexEntryReferenceProvider)
+            .await;
+
+        let expected_refs = vec![
+            Location::new(
+                FeatureTester::uri("main.tex").into(),
+                Range::new_simple(0, 6, 0, 9),
+            ),
+            Location::new(
+                FeatureTester::uri("main.tex").into(),
+                Range::new_simple(2, 9, 2, 12),
+            ),
+        ];
+
+        assert_eq!(actual_refs, expected_refs);
+    }
+
     #[tokio::test]
     async fn empty_latex_document() {
         let actual_refs = FeatureTester::new()
@@ -0,0 +1,172 @@
+use crate::{
+    feature::{FeatureProvider, FeatureRequest},
+    protocol::{Location, ReferenceParams},
+    syntax::{latex, AstNodeIndex, SyntaxNode},
+    workspace::DocumentContent,
+};
+use async_trait::async_trait;
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub struct LatexCommandReferenceProvider;
+
+#[async_trait]
+impl FeatureProvider for LatexCommandReferenceProvider {
+    type Params = ReferenceParams;
+    type Output = Vec<Location>;
+
+    async fn execute<'a>(&'a self, req: &'a FeatureRequest<Self::Params>) -> Self::Output {
+        let mut refs = Vec::new();
+        if let Some(name) = Self::find_name(req) {
+            let is_user_defined = req
+                .related()
+                .iter()
+                .filter_map(|doc| doc.content.as_latex())
+                .any(|table| !Self::definitions(table, name).is_empty());
+
+            if !is_user_defined {
+                return refs;
+            }
+
+            for doc in req.related() {
+                if let DocumentContent::Latex(table) = &doc.content {
+                    let definitions = Self::definitions(table, name);
+                    table
+                        .commands
+                        .iter()
+                        .filter(|node| {
+                            req.params.context.include_declaration || !definitions.contains(node)
+                        })
+                        .filter_map(|node| table.as_command(*node))
+                        .filter(|cmd| cmd.name.text() == name)
+                        .map(|cmd| Location::new(doc.uri.clone().into(), cmd.name.range()))
+                        .for_each(|location| refs.push(location));
+                }
+            }
+        }
+        refs
+    }
+}
+
+impl LatexCommandReferenceProvider {
+    fn find_name(req: &FeatureRequest<ReferenceParams>) -> Option<&str> {
+        let pos = req.params.text_document_position.position;
+        let table = req.current().content.as_latex()?;
+        table
+            .find(pos)
+            .last()
+            .and_then(|node| table.as_command(*node))
+            .map(|cmd| cmd.name.text())
+    }
+
+    /// Returns the nodes that define the given command, e.g. `\foo` in `\newcommand{\foo}{...}`.
+    fn definitions(table: &latex::SymbolTable, name: &str) -> Vec<AstNodeIndex> {
+        let commands = table
+            .command_definitions
+            .iter()
+            .filter(|def| def.definition_name(&table) == name)
+            .map(|def| def.definition);
+
+        let operators = table
+            .math_operators
+            .iter()
+            .filter(|op| op.definition_name(&table) == name)
+            .map(|op| op.definition);
+
+        commands.chain(operators).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        feature::FeatureTester,
+        protocol::{Range, RangeExt},
+    };
+    use indoc::indoc;
+
+    #[tokio::test]
+    async fn definition() {
+        let actual_refs = FeatureTester::new()
+            .file("foo.tex", r#"\newcommand{\foo}{Foo}"#)
+            .file(
+                "bar.tex",
+                indoc!(
+                    r#"
+                        \input{foo.tex}
+                        \foo \bar
+                    "#
+                ),
+            )
+            .file("baz.tex", r#"\foo"#)
+            .main("foo.tex")
+            .position(0, 13)
+            .test_reference(LatexCommandReferenceProvider)
+            .await;
+
+        let expected_refs = vec![Location::new(
+            FeatureTester::uri("bar.tex").into(),
+            Range::new_simple(1, 0, 1, 4),
+        )];
+
+        assert_eq!(actual_refs, expected_refs);
+    }
+
+    #[tokio::test]
+    async fn usage_include_declaration() {
+        let actual_refs = FeatureTester::new()
+            .file("foo.tex", r#"\DeclareMathOperator{\foo}{foo}"#)
+            .file(
+                "bar.tex",
+                indoc!(
+                    r#"
+                        \input{foo.tex}
+                        \foo \bar
+                    "#
+                ),
+            )
+            .main("bar.tex")
+            .position(1, 2)
+            .include_declaration()
+            .test_reference(LatexCommandReferenceProvider)
+            .await;
+
+        let expected_refs = vec![
+            Location::new(
+                FeatureTester::uri("bar.tex").into(),
+                Range::new_simple(1, 0, 1, 4),
+            ),
+            Location::new(
+                FeatureTester::uri("foo.tex").into(),
+                Range::new_simple(0, 21, 0, 25),
+            ),
+        ];
+
+        assert_eq!(actual_refs, expected_refs);
+    }
+
+    #[tokio::test]
+    async fn builtin_command() {
+        let actual_refs = FeatureTester::new()
+            .file("foo.tex", r#"\section{Foo}\section{Bar}"#)
+            .main("foo.tex")
+            .position(0, 2)
+            .include_declaration()
+            .test_reference(LatexCommandReferenceProvider)
+            .await;
+
+        assert!(actual_refs.is_empty());
+    }
+
+    #[tokio::test]
+    async fn empty_bibtex_document() {
+        let actual_refs = FeatureTester::new()
+            .file("foo.bib", "")
+            .main("foo.bib")
+            .position(0, 0)
+            .test_reference(LatexCommandReferenceProvider)
+            .await;
+
+        assert!(actual_refs.is_empty());
+    }
+}
@@ -0,0 +1,161 @@
+use crate::{
+    feature::{FeatureProvider, FeatureRequest},
+    protocol::{Location, RangeExt, ReferenceParams},
+    syntax::{latex, SyntaxNode},
+    workspace::DocumentContent,
+};
+use async_trait::async_trait;
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub struct LatexEnvironmentReferenceProvider;
+
+#[async_trait]
+impl FeatureProvider for LatexEnvironmentReferenceProvider {
+    type Params = ReferenceParams;
+    type Output = Vec<Location>;
+
+    async fn execute<'a>(&'a self, req: &'a FeatureRequest<Self::Params>) -> Self::Output {
+        let mut refs = Vec::new();
+        if let Some(name) = Self::find_name(req) {
+            for doc in req.related() {
+                if let DocumentContent::Latex(table) = &doc.content {
+                    if req.params.context.include_declaration {
+                        table
+                            .theorem_definitions
+                            .iter()
+                            .map(|def| def.name(&table))
+                            .filter(|def| def.text() == name)
+                            .map(|def| Location::new(doc.uri.clone().into(), def.range()))
+                            .for_each(|location| refs.push(location));
+                    }
+
+                    table
+                        .environments
+                        .iter()
+                        .flat_map(|env| vec![env.left, env.right])
+                        .filter_map(|delim| delim.name(&table))
+                        .filter(|delim| delim.text() == name)
+                        .map(|delim| Location::new(doc.uri.clone().into(), delim.range()))
+                        .for_each(|location| refs.push(location));
+                }
+            }
+        }
+        refs
+    }
+}
+
+impl LatexEnvironmentReferenceProvider {
+    fn find_name(req: &FeatureRequest<ReferenceParams>) -> Option<&str> {
+        let pos = req.params.text_document_position.position;
+        let table = req.current().content.as_latex()?;
+        let env_names = table
+            .environments
+            .iter()
+            .flat_map(|env| vec![env.left, env.right])
+            .filter_map(|delim| delim.name(&table));
+
+        table
+            .theorem_definitions
+            .iter()
+            .map(|def| def.name(&table))
+            .chain(env_names)
+            .find(|name| name.range().contains(pos))
+            .map(latex::Token::text)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{feature::FeatureTester, protocol::Range};
+    use indoc::indoc;
+
+    #[tokio::test]
+    async fn environment() {
+        let actual_refs = FeatureTester::new()
+            .file(
+                "foo.tex",
+                indoc!(
+                    r#"
+                        \begin{foo}
+                        \end{foo}
+                        \begin{bar}\end{bar}
+                    "#
+                ),
+            )
+            .file("bar.tex", r#"\input{foo.tex}\begin{foo}\end{foo}"#)
+            .main("foo.tex")
+            .position(1, 6)
+            .test_reference(LatexEnvironmentReferenceProvider)
+            .await;
+
+        let expected_refs = vec![
+            Location::new(
+                FeatureTester::uri("foo.tex").into(),
+                Range::new_simple(0, 7, 0, 10),
+            ),
+            Location::new(
+                FeatureTester::uri("foo.tex").into(),
+                Range::new_simple(1, 5, 1, 8),
+            ),
+            Location::new(
+                FeatureTester::uri("bar.tex").into(),
+                Range::new_simple(0, 22, 0, 25),
+            ),
+            Location::new(
+                FeatureTester::uri("bar.tex").into(),
+                Range::new_simple(0, 31, 0, 34),
+            ),
+        ];
+
+        assert_eq!(actual_refs, expected_refs);
+    }
+
+    #[tokio::test]
+    async fn theorem_include_declaration() {
+        let actual_refs = FeatureTester::new()
+            .file(
+                "foo.tex",
+                indoc!(
+                    r#"
+                        \newtheorem{lemma}{Lemma}
+                        \begin{lemma}\end{lemma}
+                    "#
+                ),
+            )
+            .main("foo.tex")
+            .position(0, 14)
+            .include_declaration()
+            .test_reference(LatexEnvironmentReferenceProvider)
+            .await;
+
+        let expected_refs = vec![
+            Location::new(
+                FeatureTester::uri("foo.tex").into(),
+                Range::new_simple(0, 12, 0, 17),
+            ),
+            Location::new(
+                FeatureTester::uri("foo.tex").into(),
+                Range::new_simple(1, 7, 1, 12),
+            ),
+            Location::new(
+                FeatureTester::uri("foo.tex").into(),
+                Range::new_simple(1, 18, 1, 23),
+            ),
+        ];
+
+        assert_eq!(actual_refs, expected_refs);
+    }
+
+    #[tokio::test]
+    async fn empty_latex_document() {
+        let actual_refs = FeatureTester::new()
+            .file("main.tex", "")
+            .main("main.tex")
+            .position(0, 0)
+            .test_reference(LatexEnvironmentReferenceProvider)
+            .await;
+
+        assert!(actual_refs.is_empty());
+    }
+}
@@ -0,0 +1,155 @@
+use crate::{
+    feature::{FeatureProvider, FeatureRequest},
+    protocol::{Location, RangeExt, ReferenceParams},
+    syntax::{latex, SyntaxNode, LANGUAGE_DATA},
+    workspace::DocumentContent,
+};
+use async_trait::async_trait;
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub struct LatexGlossaryReferenceProvider;
+
+#[async_trait]
+impl FeatureProvider for LatexGlossaryReferenceProvider {
+    type Params = ReferenceParams;
+    type Output = Vec<Location>;
+
+    async fn execute<'a>(&'a self, req: &'a FeatureRequest<Self::Params>) -> Self::Output {
+        let mut refs = Vec::new();
+        if let Some(name) = Self::find_name(req) {
+            for doc in req.related() {
+                if let DocumentContent::Latex(table) = &doc.content {
+                    if req.params.context.include_declaration {
+                        table
+                            .glossary_entries
+                            .iter()
+                            .map(|entry| entry.label(&table))
+                            .filter(|label| label.text() == name)
+                            .map(|label| Location::new(doc.uri.clone().into(), label.range()))
+                            .for_each(|location| refs.push(location));
+                    }
+
+                    Self::references(table)
+                        .into_iter()
+                        .filter(|label| label.text() == name)
+                        .map(|label| Location::new(doc.uri.clone().into(), label.range()))
+                        .for_each(|location| refs.push(location));
+                }
+            }
+        }
+        refs
+    }
+}
+
+impl LatexGlossaryReferenceProvider {
+    fn find_name(req: &FeatureRequest<ReferenceParams>) -> Option<&str> {
+        let pos = req.params.text_document_position.position;
+        let table = req.current().content.as_latex()?;
+        table
+            .glossary_entries
+            .iter()
+            .map(|entry| entry.label(&table))
+            .chain(Self::references(table))
+            .find(|label| label.range().contains(pos))
+            .map(latex::Token::text)
+    }
+
+    /// Returns the arguments of the glossary entry reference commands, e.g. `foo` in `\gls{foo}`.
+    fn references(table: &latex::SymbolTable) -> Vec<&latex::Token> {
+        table
+            .commands
+            .iter()
+            .filter_map(|node| {
+                let cmd = table.as_command(*node)?;
+                let desc = LANGUAGE_DATA
+                    .glossary_entry_reference_commands
+                    .iter()
+                    .find(|desc| desc.name == cmd.name.text())?;
+                table.extract_word(*node, latex::GroupKind::Group, desc.index)
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{feature::FeatureTester, protocol::Range};
+    use indoc::indoc;
+
+    #[tokio::test]
+    async fn definition() {
+        let actual_refs = FeatureTester::new()
+            .file(
+                "foo.tex",
+                indoc!(
+                    r#"
+                        \newacronym{foo}{FOO}{Foo}
+                        \gls{foo}
+                    "#
+                ),
+            )
+            .file("bar.tex", r#"\input{foo.tex}\acrshort{foo}\gls{bar}"#)
+            .main("foo.tex")
+            .position(0, 13)
+            .test_reference(LatexGlossaryReferenceProvider)
+            .await;
+
+        let expected_refs = vec![
+            Location::new(
+                FeatureTester::uri("foo.tex").into(),
+                Range::new_simple(1, 5, 1, 8),
+            ),
+            Location::new(
+                FeatureTester::uri("bar.tex").into(),
+                Range::new_simple(0, 25, 0, 28),
+            ),
+        ];
+
+        assert_eq!(actual_refs, expected_refs);
+    }
+
+    #[tokio::test]
+    async fn reference_include_declaration() {
+        let actual_refs = FeatureTester::new()
+            .file(
+                "foo.tex",
+                indoc!(
+                    r#"
+                        \newglossaryentry{foo}{name=foo}
+                        \Gls{foo}
+                    "#
+                ),
+            )
+            .main("foo.tex")
+            .position(1, 6)
+            .include_declaration()
+            .test_reference(LatexGlossaryReferenceProvider)
+            .await;
+
+        let expected_refs = vec![
+            Location::new(
+                FeatureTester::uri("foo.tex").into(),
+                Range::new_simple(0, 18, 0, 21),
+            ),
+            Location::new(
+                FeatureTester::uri("foo.tex").into(),
+                Range::new_simple(1, 5, 1, 8),
+            ),
+        ];
+
+        assert_eq!(actual_refs, expected_refs);
+    }
+
+    #[tokio::test]
+    async fn empty_latex_document() {
+        let actual_refs = FeatureTester::new()
+            .file("main.tex", "")
+            .main("main.tex")
+            .position(0, 0)
+            .test_reference(LatexGlossaryReferenceProvider)
+            .await;
+
+        assert!(actual_refs.is_empty());
+    }
+}
@@ -1,10 +1,14 @@
 mod bibtex_entry;
 mod bibtex_string;
+mod latex_cmd;
+mod latex_env;
+mod latex_glossary;
 mod latex_label;
 
 use self::{
     bibtex_entry::BibtexEntryReferenceProvider, bibtex_string::BibtexStringReferenceProvider,
-    latex_label::LatexLabelReferenceProvider,
+    latex_cmd::LatexCommandReferenceProvider, latex_env::LatexEnvironmentReferenceProvider,
+    latex_glossary::LatexGlossaryReferenceProvider, latex_label::LatexLabelReferenceProvider,
 };
 use crate::{
     feature::{ConcatProvider, FeatureProvider, FeatureRequest},
@@ -22,6 +26,9 @@ impl ReferenceProvider {
             provider: ConcatProvider::new(vec![
                 Box::new(BibtexEntryReferenceProvider),
                 Box::new(BibtexStringReferenceProvider),
+                Box::new(LatexCommandReferenceProvider),
+                Box::new(LatexEnvironmentReferenceProvider),
+                Box::new(LatexGlossaryReferenceProvider),
                 Box::new(LatexLabelReferenceProvider),
             ]),
         }
@@ -43,6 +43,7 @@ impl FeatureProvider for BibtexEntryRenameProvider {
                     .citations
                     .iter()
                     .flat_map(|citation| citation.keys(&table))
+                    .chain(table.bib_items.iter().map(|bib_item| bib_item.key(&table)))
                     .filter(|citation| citation.text() == key_name.text)
                     .map(|citation| TextEdit::new(citation.range(), req.params.new_name.clone()))
                     .collect(),
@@ -66,6 +67,7 @@ fn find_key(content: &DocumentContent, pos: Position) -> Option<&Span> {
             .citations
             .iter()
             .flat_map(|citation| citation.keys(&table))
+            .chain(table.bib_items.iter().map(|bib_item| bib_item.key(&table)))
             .find(|key| key.range().contains(pos))
             .map(|key| &key.span),
         DocumentContent::Bibtex(tree) => tree
@@ -177,6 +179,41 @@ mod tests {
         assert_eq!(actual_edit, None);
     }
 
+    #[tokio::test]
+    async fn bib_item() {
+        let actual_edit = FeatureTester::new()
+            .file("main.tex", r#"\cite{foo}"#)
+            .file(
+                "main.bbl",
+                indoc!(
+                    r#"
+                        \begin{thebibliography}{1}
+                        \bibitem{foo} Foo
+                        \end{thebibliography}
+                    "#
+                ),
+            )
+            .main("main.tex")
+            .position(0, 6)
+            .new_name("bar")
+            .test_rename(BibtexEntryRenameProvider)
+            .await
+            .unwrap();
+
+        let mut expected_changes = HashMap::new();
+        expected_changes.insert(
+            FeatureTester::uri("main.tex").into(),
+            vec![TextEdit::new(Range::new_simple(0, 6, 0, 9), "bar".into())],
+        );
+        expected_changes.insert(
+            FeatureTester::uri("main.bbl").into(),
+            vec![TextEdit::new(Range::new_simple(1, 9, 1, 12), "bar".into())],
+        );
+        let expected_edit = WorkspaceEdit::new(expected_changes);
+
+        assert_eq!(actual_edit, expected_edit);
+    }
+
     #[tokio::test]
     async fn empty_bibtex_document() {
         let actual_edit = FeatureTester::new()
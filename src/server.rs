@@ -2,7 +2,9 @@
 use crate::citeproc::render_citation;
 
 use crate::{
+    bibliography,
     build::BuildProvider,
+    code_action::CodeActionProvider,
     completion::{CompletionItemData, CompletionProvider, COMPLETION_LIMIT},
     components::COMPONENT_DATABASE,
     config::ConfigManager,
@@ -17,9 +19,10 @@ use crate::{
     protocol::*,
     reference::ReferenceProvider,
     rename::{PrepareRenameProvider, RenameProvider},
+    spelling,
     symbol::{document_symbols, workspace_symbols, SymbolProvider},
     syntax::{bibtex, latexindent, CharStream, SyntaxNode},
-    tex::{Distribution, DistributionKind, KpsewhichError},
+    tex::{self, Distribution, DistributionKind, KpsewhichError},
     workspace::{DocumentContent, Workspace},
 };
 use async_trait::async_trait;
@@ -30,6 +33,7 @@ use jsonrpc_derive::{jsonrpc_method, jsonrpc_server};
 use log::{debug, error, info, warn};
 use once_cell::sync::{Lazy, OnceCell};
 use std::{mem, path::PathBuf, sync::Arc};
+use tokio::fs;
 
 pub struct LatexLspServer<C> {
     distro: Arc<dyn Distribution>,
@@ -41,6 +45,7 @@ pub struct LatexLspServer<C> {
     action_manager: ActionManager,
     workspace: Workspace,
     build_provider: BuildProvider<C>,
+    code_action_provider: CodeActionProvider,
     completion_provider: CompletionProvider,
     definition_provider: DefinitionProvider,
     folding_provider: FoldingProvider,
@@ -69,6 +74,7 @@ impl<C: LspClient + Send + Sync + 'static> LatexLspServer<C> {
             action_manager: ActionManager::default(),
             workspace,
             build_provider: BuildProvider::new(client),
+            code_action_provider: CodeActionProvider::new(),
             completion_provider: CompletionProvider::new(),
             definition_provider: DefinitionProvider::new(),
             folding_provider: FoldingProvider::new(),
@@ -132,6 +138,9 @@ impl<C: LspClient + Send + Sync + 'static> LatexLspServer<C> {
                     "\\".into(),
                     "{".into(),
                     "}".into(),
+                    "[".into(),
+                    "=".into(),
+                    ",".into(),
                     "@".into(),
                     "/".into(),
                     " ".into(),
@@ -153,6 +162,15 @@ impl<C: LspClient + Send + Sync + 'static> LatexLspServer<C> {
                 work_done_progress_options: WorkDoneProgressOptions::default(),
             }),
             folding_range_provider: Some(FoldingRangeProviderCapability::Simple(true)),
+            code_action_provider: Some(CodeActionProviderCapability::Simple(true)),
+            execute_command_provider: Some(ExecuteCommandOptions {
+                commands: vec![
+                    spelling::ADD_TO_DICTIONARY_COMMAND.into(),
+                    bibliography::TRIM_BIBLIOGRAPHY_COMMAND.into(),
+                    bibliography::IMPORT_CITATION_COMMAND.into(),
+                ],
+                work_done_progress_options: WorkDoneProgressOptions::default(),
+            }),
             ..ServerCapabilities::default()
         };
 
@@ -188,19 +206,28 @@ impl<C: LspClient + Send + Sync + 'static> LatexLspServer<C> {
     #[jsonrpc_method("textDocument/didOpen", kind = "notification")]
     pub async fn did_open(&self, params: DidOpenTextDocumentParams) {
         let uri = params.text_document.uri.clone();
+        self.diagnostics_manager
+            .update_version(&uri.clone().into(), Some(params.text_document.version));
         let options = self.config_manager().get().await;
         self.workspace.add(params.text_document, &options).await;
         self.action_manager
             .push(Action::DetectRoot(uri.clone().into()))
             .await;
         self.action_manager
-            .push(Action::RunLinter(uri.into(), LintReason::Save))
+            .push(Action::RunLinter(uri.clone().into(), LintReason::Save))
+            .await;
+        self.action_manager
+            .push(Action::CheckSpelling(uri.into()))
             .await;
         self.action_manager.push(Action::PublishDiagnostics).await;
     }
 
     #[jsonrpc_method("textDocument/didChange", kind = "notification")]
     pub async fn did_change(&self, params: DidChangeTextDocumentParams) {
+        self.diagnostics_manager.update_version(
+            &params.text_document.uri.clone().into(),
+            params.text_document.version,
+        );
         let options = self.config_manager().get().await;
         for change in params.content_changes {
             let uri = params.text_document.uri.clone();
@@ -214,6 +241,9 @@ impl<C: LspClient + Send + Sync + 'static> LatexLspServer<C> {
                 LintReason::Change,
             ))
             .await;
+        self.action_manager
+            .push(Action::CheckSpelling(params.text_document.uri.into()))
+            .await;
         self.action_manager.push(Action::PublishDiagnostics).await;
     }
 
@@ -292,9 +322,17 @@ impl<C: LspClient + Send + Sync + 'static> LatexLspServer<C> {
             CompletionItemData::Citation { uri, key } => {
                 let snapshot = self.workspace.get().await;
                 if let Some(doc) = snapshot.find(&uri) {
-                    if let DocumentContent::Bibtex(tree) = &doc.content {
-                        let markup = render_citation(&tree, &key);
-                        item.documentation = markup.map(Documentation::MarkupContent);
+                    match &doc.content {
+                        DocumentContent::Latex(table) => {
+                            item.documentation = table
+                                .find_bib_item(&key)
+                                .map(|bib_item| bibliography::bib_item_text(&doc.text, bib_item))
+                                .map(Documentation::String);
+                        }
+                        DocumentContent::Bibtex(tree) => {
+                            let markup = render_citation(&tree, &key);
+                            item.documentation = markup.map(Documentation::MarkupContent);
+                        }
                     }
                 }
             }
@@ -495,6 +533,116 @@ impl<C: LspClient + Send + Sync + 'static> LatexLspServer<C> {
         Ok(self.folding_provider.execute(&req).await)
     }
 
+    #[jsonrpc_method("textDocument/codeAction", kind = "request")]
+    pub async fn code_action(&self, params: CodeActionParams) -> Result<Vec<CodeActionOrCommand>> {
+        let req = self
+            .make_feature_request(params.text_document.as_uri(), params)
+            .await?;
+        let actions = self.code_action_provider.execute(&req).await;
+        Ok(actions
+            .into_iter()
+            .map(CodeActionOrCommand::CodeAction)
+            .collect())
+    }
+
+    #[jsonrpc_method("workspace/executeCommand", kind = "request")]
+    pub async fn execute_command(
+        &self,
+        params: ExecuteCommandParams,
+    ) -> Result<Option<serde_json::Value>> {
+        match params.command.as_str() {
+            spelling::ADD_TO_DICTIONARY_COMMAND => {
+                let word = params
+                    .arguments
+                    .first()
+                    .and_then(|arg| arg.as_str())
+                    .ok_or_else(|| "Expecting the word to add".to_owned())?;
+
+                let snapshot = self.workspace.get().await;
+                let options = self.config_manager().get().await;
+                let related = params
+                    .arguments
+                    .get(1)
+                    .and_then(|arg| arg.as_str())
+                    .and_then(|uri| Uri::parse(uri).ok())
+                    .map(|uri| snapshot.relations(&uri, &options, &self.current_dir))
+                    .unwrap_or_default();
+
+                spelling::add_project_word(word, &related, &options, &self.current_dir)
+                    .await
+                    .map_err(|why| format!("Unable to update the project dictionary: {}", why))?;
+
+                for doc in &snapshot.0 {
+                    self.action_manager
+                        .push(Action::CheckSpelling(doc.uri.clone()))
+                        .await;
+                }
+                self.action_manager.push(Action::PublishDiagnostics).await;
+                Ok(None)
+            }
+            bibliography::TRIM_BIBLIOGRAPHY_COMMAND => {
+                let uri = params
+                    .arguments
+                    .first()
+                    .and_then(|arg| arg.as_str())
+                    .and_then(|uri| Uri::parse(uri).ok())
+                    .ok_or_else(|| "Expecting the URI of a BibTeX document".to_owned())?;
+
+                let snapshot = self.workspace.get().await;
+                let options = self.config_manager().get().await;
+                let doc = snapshot
+                    .find(&uri)
+                    .ok_or_else(|| format!("Unknown document: {}", uri.as_str()))?;
+                let tree = doc
+                    .content
+                    .as_bibtex()
+                    .ok_or_else(|| format!("Not a BibTeX document: {}", uri.as_str()))?;
+                let path = bibliography::trimmed_path(&uri)
+                    .ok_or_else(|| format!("Not a local file: {}", uri.as_str()))?;
+
+                let related = snapshot.relations(&uri, &options, &self.current_dir);
+                let usage = bibliography::CitationUsage::find(&related).unwrap_or_default();
+                let text = bibliography::trim(&doc.text, tree, &usage);
+                fs::write(&path, text)
+                    .await
+                    .map_err(|why| format!("Unable to write the bibliography: {}", why))?;
+
+                let output = Uri::from_file_path(&path).unwrap();
+                Ok(Some(serde_json::Value::String(output.as_str().to_owned())))
+            }
+            bibliography::IMPORT_CITATION_COMMAND => {
+                let args: Vec<_> = params
+                    .arguments
+                    .iter()
+                    .filter_map(|arg| arg.as_str())
+                    .collect();
+                let (library_uri, key, target_uri) = match args.as_slice() {
+                    [library, key, target] => (Uri::parse(library), *key, Uri::parse(target)),
+                    _ => return Err("Expecting a library, a key and a bibliography".into()),
+                };
+                let library_uri = library_uri.map_err(|_| "Invalid library URI".to_owned())?;
+                let target_uri = target_uri.map_err(|_| "Invalid bibliography URI".to_owned())?;
+
+                let snapshot = self.workspace.get().await;
+                let library = snapshot
+                    .find(&library_uri)
+                    .ok_or_else(|| format!("Unknown document: {}", library_uri.as_str()))?;
+                let target = snapshot
+                    .find(&target_uri)
+                    .ok_or_else(|| format!("Unknown document: {}", target_uri.as_str()))?;
+
+                if let Some(edit) = bibliography::import_entry(&library, key, &target) {
+                    self.client
+                        .apply_edit(ApplyWorkspaceEditParams { edit })
+                        .await
+                        .map_err(|why| format!("Unable to import the citation: {}", why.message))?;
+                }
+                Ok(None)
+            }
+            _ => Err(format!("Unknown command: {}", params.command)),
+        }
+    }
+
     #[jsonrpc_method("textDocument/build", kind = "request")]
     pub async fn build(&self, params: BuildParams) -> Result<BuildResult> {
         let req = self
@@ -617,6 +765,7 @@ impl<C: LspClient + Send + Sync + 'static> LatexLspServer<C> {
             self.client.show_message(params).await;
         }
 
+        tex::clear_search_paths();
         if let Err(why) = self.distro.load().await {
             let message = match why {
                 KpsewhichError::NotInstalled | KpsewhichError::InvalidOutput => {
@@ -674,14 +823,36 @@ impl<C: LspClient + Send + Sync + 'static> Middleware for LatexLspServer<C> {
                     let _ = self.workspace.detect_root(&uri, &options).await;
                 }
                 Action::PublishDiagnostics => {
+                    if !self.diagnostics_manager.debounce().await {
+                        continue;
+                    }
+
                     let snapshot = self.workspace.get().await;
-                    for doc in &snapshot.0 {
-                        let diagnostics = self.diagnostics_manager.get(doc).await;
-                        let params = PublishDiagnosticsParams {
-                            uri: doc.uri.clone().into(),
-                            diagnostics,
-                            version: None,
-                        };
+                    let options = self.config_manager().get().await;
+                    let resolver = self.distro.resolver().await;
+                    let relations = snapshot.all_relations(&options, &self.current_dir);
+                    self.diagnostics_manager
+                        .package
+                        .update(&snapshot, &relations, &resolver);
+                    self.diagnostics_manager.include.update(
+                        &snapshot,
+                        &relations,
+                        &options,
+                        &self.current_dir,
+                    );
+                    self.diagnostics_manager
+                        .structure
+                        .update(&snapshot, &relations);
+                    self.diagnostics_manager.math.update(&snapshot);
+                    self.diagnostics_manager.key_value.update(&snapshot);
+                    self.diagnostics_manager
+                        .accessibility
+                        .update(&snapshot, &relations, &options);
+                    self.diagnostics_manager
+                        .unused_entry
+                        .update(&snapshot, &relations);
+
+                    for params in self.diagnostics_manager.changes(&snapshot).await {
                         self.client.publish_diagnostics(params).await;
                     }
                 }
@@ -717,11 +888,22 @@ impl<C: LspClient + Send + Sync + 'static> Middleware for LatexLspServer<C> {
                         let snapshot = self.workspace.get().await;
                         if let Some(doc) = snapshot.find(&uri) {
                             if let DocumentContent::Latex(_) = &doc.content {
-                                self.diagnostics_manager.latex.update(&uri, &doc.text).await;
+                                self.diagnostics_manager
+                                    .latex
+                                    .update(&snapshot, &uri, &doc.text)
+                                    .await;
                             }
                         }
                     }
                 }
+                Action::CheckSpelling(uri) => {
+                    let snapshot = self.workspace.get().await;
+                    let options = self.config_manager().get().await;
+                    self.diagnostics_manager
+                        .spelling
+                        .update(&snapshot, &uri, &options, &self.current_dir)
+                        .await;
+                }
             }
         }
     }
@@ -742,6 +924,7 @@ enum Action {
     PublishDiagnostics,
     Build(Uri),
     RunLinter(Uri, LintReason),
+    CheckSpelling(Uri),
 }
 
 #[derive(Debug, Default)]
@@ -0,0 +1,356 @@
+use regex::Regex;
+use std::collections::{HashMap, HashSet};
+
+const MAX_SUGGESTIONS: usize = 5;
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+enum FlagFormat {
+    Single,
+    Long,
+    Numeric,
+}
+
+impl Default for FlagFormat {
+    fn default() -> Self {
+        Self::Single
+    }
+}
+
+impl FlagFormat {
+    fn split(self, flags: &str) -> Vec<String> {
+        match self {
+            Self::Single => flags.chars().map(|c| c.to_string()).collect(),
+            Self::Long => {
+                let chars: Vec<_> = flags.chars().collect();
+                chars
+                    .chunks(2)
+                    .map(|chunk| chunk.iter().collect())
+                    .collect()
+            }
+            Self::Numeric => flags
+                .split(',')
+                .map(|flag| flag.trim().to_owned())
+                .filter(|flag| !flag.is_empty())
+                .collect(),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Affix {
+    flag: String,
+    strip: String,
+    add: String,
+    condition: Option<Regex>,
+    cross_product: bool,
+}
+
+impl Affix {
+    fn parse(kind: &str, flag: &str, cross_product: bool, fields: &[&str]) -> Option<Self> {
+        let strip = match *fields.get(0)? {
+            "0" => "",
+            strip => strip,
+        };
+
+        let add = match fields.get(1)?.split('/').next()? {
+            "0" => "",
+            add => add,
+        };
+
+        let condition = match fields.get(2).copied().unwrap_or(".") {
+            "." => None,
+            condition if kind == "SFX" => Regex::new(&format!("{}$", condition)).ok(),
+            condition => Regex::new(&format!("^{}", condition)).ok(),
+        };
+
+        Some(Self {
+            flag: flag.to_owned(),
+            strip: strip.to_owned(),
+            add: add.to_owned(),
+            condition,
+            cross_product,
+        })
+    }
+
+    fn remove_suffix(&self, word: &str) -> Option<String> {
+        if !word.ends_with(&self.add) || word.len() == self.add.len() {
+            return None;
+        }
+
+        let base = format!("{}{}", &word[..word.len() - self.add.len()], self.strip);
+        if self.matches_condition(&base) {
+            Some(base)
+        } else {
+            None
+        }
+    }
+
+    fn remove_prefix(&self, word: &str) -> Option<String> {
+        if !word.starts_with(&self.add) || word.len() == self.add.len() {
+            return None;
+        }
+
+        let base = format!("{}{}", self.strip, &word[self.add.len()..]);
+        if self.matches_condition(&base) {
+            Some(base)
+        } else {
+            None
+        }
+    }
+
+    fn matches_condition(&self, base: &str) -> bool {
+        self.condition
+            .as_ref()
+            .map_or(true, |condition| condition.is_match(base))
+    }
+}
+
+/// A spelling dictionary in the format used by Hunspell (`.dic` and `.aff` files).
+/// Only the subset of the affix file format that is needed for
+/// checking words with prefix and suffix rules is supported.
+#[derive(Debug, Clone, Default)]
+pub struct Dictionary {
+    words: HashMap<String, HashSet<String>>,
+    prefixes: Vec<Affix>,
+    suffixes: Vec<Affix>,
+    try_chars: Vec<char>,
+}
+
+impl Dictionary {
+    pub fn parse(dic: &[u8], aff: &[u8]) -> Self {
+        let encoding = decode(aff, None)
+            .lines()
+            .find(|line| line.starts_with("SET "))
+            .map(|line| line[4..].trim().to_owned());
+        let aff = decode(aff, encoding.as_deref());
+        let dic = decode(dic, encoding.as_deref());
+
+        let mut dictionary = Self::default();
+        let mut flag_format = FlagFormat::default();
+        let mut cross_product_by_header = HashMap::new();
+        for line in aff.lines() {
+            let fields: Vec<_> = line.split_whitespace().collect();
+            match fields.as_slice() {
+                ["FLAG", "long", ..] => flag_format = FlagFormat::Long,
+                ["FLAG", "num", ..] => flag_format = FlagFormat::Numeric,
+                ["TRY", chars, ..] => dictionary.try_chars = chars.chars().collect(),
+                [kind @ "PFX", flag, rest @ ..] | [kind @ "SFX", flag, rest @ ..] => {
+                    let header = (kind.to_string(), flag.to_string());
+                    let cross_product = match cross_product_by_header.get(&header) {
+                        Some(cross_product) => *cross_product,
+                        None => {
+                            cross_product_by_header.insert(header, rest.get(0) == Some(&"Y"));
+                            continue;
+                        }
+                    };
+
+                    if let Some(affix) = Affix::parse(kind, flag, cross_product, rest) {
+                        if *kind == "PFX" {
+                            dictionary.prefixes.push(affix);
+                        } else {
+                            dictionary.suffixes.push(affix);
+                        }
+                    }
+                }
+                _ => (),
+            }
+        }
+
+        for line in dic.lines().skip(1) {
+            let entry = match line.split_whitespace().next() {
+                Some(entry) => entry,
+                None => continue,
+            };
+
+            let (word, flags) = match entry.find('/') {
+                Some(index) => (&entry[..index], flag_format.split(&entry[index + 1..])),
+                None => (entry, Vec::new()),
+            };
+
+            dictionary
+                .words
+                .entry(word.to_owned())
+                .or_insert_with(HashSet::new)
+                .extend(flags);
+        }
+        dictionary
+    }
+
+    pub fn check(&self, word: &str) -> bool {
+        if self.check_exact(word) {
+            return true;
+        }
+
+        let mut chars = word.chars();
+        let first = chars.next();
+        if first.map_or(false, char::is_uppercase) && chars.all(char::is_lowercase) {
+            return self.check_exact(&word.to_lowercase());
+        }
+
+        false
+    }
+
+    pub fn suggest(&self, word: &str) -> Vec<String> {
+        let chars: Vec<char> = word.chars().collect();
+        let alphabet = if self.try_chars.is_empty() {
+            ('a'..='z').collect()
+        } else {
+            self.try_chars.clone()
+        };
+
+        let mut candidates = Vec::new();
+        for i in 0..chars.len() {
+            let mut deletion = chars.clone();
+            deletion.remove(i);
+            candidates.push(deletion);
+
+            if i + 1 < chars.len() {
+                let mut transposition = chars.clone();
+                transposition.swap(i, i + 1);
+                candidates.push(transposition);
+            }
+        }
+
+        for c in &alphabet {
+            for i in 0..=chars.len() {
+                let mut insertion = chars.clone();
+                insertion.insert(i, *c);
+                candidates.push(insertion);
+
+                if i < chars.len() && chars[i] != *c {
+                    let mut replacement = chars.clone();
+                    replacement[i] = *c;
+                    candidates.push(replacement);
+                }
+            }
+        }
+
+        let mut suggestions = Vec::new();
+        for candidate in candidates {
+            let candidate: String = candidate.into_iter().collect();
+            if !candidate.is_empty() && !suggestions.contains(&candidate) && self.check(&candidate)
+            {
+                suggestions.push(candidate);
+                if suggestions.len() == MAX_SUGGESTIONS {
+                    break;
+                }
+            }
+        }
+        suggestions
+    }
+
+    fn check_exact(&self, word: &str) -> bool {
+        if self.words.contains_key(word) {
+            return true;
+        }
+
+        for suffix in &self.suffixes {
+            if let Some(base) = suffix.remove_suffix(word) {
+                if self.has_flag(&base, &suffix.flag) {
+                    return true;
+                }
+
+                if suffix.cross_product {
+                    for prefix in self.prefixes.iter().filter(|prefix| prefix.cross_product) {
+                        if let Some(root) = prefix.remove_prefix(&base) {
+                            if self.has_flag(&root, &prefix.flag)
+                                && self.has_flag(&root, &suffix.flag)
+                            {
+                                return true;
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        self.prefixes.iter().any(|prefix| {
+            prefix
+                .remove_prefix(word)
+                .map_or(false, |base| self.has_flag(&base, &prefix.flag))
+        })
+    }
+
+    fn has_flag(&self, word: &str, flag: &str) -> bool {
+        self.words
+            .get(word)
+            .map_or(false, |flags| flags.contains(flag))
+    }
+}
+
+fn decode(bytes: &[u8], encoding: Option<&str>) -> String {
+    match encoding.map(str::to_uppercase).as_deref() {
+        Some("ISO8859-1") | Some("ISO-8859-1") | Some("ISO8859-15") => {
+            bytes.iter().map(|byte| *byte as char).collect()
+        }
+        _ => String::from_utf8_lossy(bytes).into_owned(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use indoc::indoc;
+
+    fn create_dictionary() -> Dictionary {
+        let aff = indoc!(
+            r#"
+                SET UTF-8
+                TRY esianrtolcdugmphbyfvkwzESIANRTOLCDUGMPHBYFVKWZ'
+                PFX U Y 1
+                PFX U 0 un .
+                SFX S Y 2
+                SFX S y ies [^aeiou]y
+                SFX S 0 s [^y]
+            "#
+        );
+
+        let dic = indoc!(
+            r#"
+                4
+                hello
+                world/S
+                fly/S
+                do/U
+            "#
+        );
+
+        Dictionary::parse(dic.as_bytes(), aff.as_bytes())
+    }
+
+    #[test]
+    fn check_word() {
+        assert!(create_dictionary().check("hello"));
+    }
+
+    #[test]
+    fn check_capitalized() {
+        assert!(create_dictionary().check("Hello"));
+    }
+
+    #[test]
+    fn check_suffix() {
+        let dictionary = create_dictionary();
+        assert!(dictionary.check("worlds"));
+        assert!(dictionary.check("flies"));
+        assert!(!dictionary.check("flys"));
+    }
+
+    #[test]
+    fn check_prefix() {
+        let dictionary = create_dictionary();
+        assert!(dictionary.check("undo"));
+        assert!(!dictionary.check("unhello"));
+    }
+
+    #[test]
+    fn check_unknown() {
+        assert!(!create_dictionary().check("helo"));
+    }
+
+    #[test]
+    fn suggest() {
+        let suggestions = create_dictionary().suggest("helo");
+        assert_eq!(suggestions, vec!["hello".to_owned()]);
+    }
+}
@@ -0,0 +1,467 @@
+mod dictionary;
+
+pub use self::dictionary::Dictionary;
+
+use crate::{
+    protocol::{LatexSpellingOptions, Options, Range, RangeExt},
+    syntax::{
+        latex::{self, Visitor},
+        AstNodeIndex, LatexIncludeKind, SyntaxNode, LANGUAGE_DATA,
+    },
+    workspace::{Document, DocumentContent},
+};
+use futures::lock::Mutex;
+use once_cell::sync::Lazy;
+use std::{
+    collections::{HashMap, HashSet},
+    env,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+use tokio::{fs, prelude::*};
+
+pub const ADD_TO_DICTIONARY_COMMAND: &str = "texlab.addToDictionary";
+
+const DEFAULT_LANGUAGE: &str = "en_US";
+
+static DICTIONARIES: Lazy<Mutex<HashMap<(String, Vec<PathBuf>), Arc<Dictionary>>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Word {
+    pub text: String,
+    pub range: Range,
+}
+
+/// Extracts the words of a document that are part of the prose.
+/// Math, verbatim content and command arguments that are not meant
+/// to be typeset as text are skipped.
+pub fn find_words(table: &latex::SymbolTable) -> Vec<Word> {
+    let mut excluded = Vec::new();
+    excluded.extend(table.equations.iter().map(|eq| eq.range(&table.tree)));
+    excluded.extend(table.inlines.iter().map(|inline| inline.range(&table.tree)));
+    excluded.extend(
+        table
+            .environments
+            .iter()
+            .filter(|env| env.left.is_math(&table.tree) || env.left.is_verbatim(&table.tree))
+            .map(|env| env.range(&table.tree)),
+    );
+
+    let mut finder = WordFinder {
+        excluded,
+        words: Vec::new(),
+    };
+    finder.visit(&table.tree, table.tree.root);
+    finder.words
+}
+
+#[derive(Debug)]
+struct WordFinder {
+    excluded: Vec<Range>,
+    words: Vec<Word>,
+}
+
+impl WordFinder {
+    fn is_prose_command(name: &str) -> bool {
+        LANGUAGE_DATA.prose_commands.iter().any(|cmd| cmd == name)
+            || LANGUAGE_DATA
+                .section_commands
+                .iter()
+                .any(|cmd| cmd.name == name)
+    }
+
+    fn split(&mut self, token: &latex::Token) {
+        let start = token.start();
+        let chars: Vec<char> = token.text().chars().collect();
+        let mut i = 0;
+        while i < chars.len() {
+            if !chars[i].is_alphabetic() {
+                i += 1;
+                continue;
+            }
+
+            let begin = i;
+            while i < chars.len()
+                && (chars[i].is_alphabetic()
+                    || (chars[i] == '\'' && chars.get(i + 1).map_or(false, |c| c.is_alphabetic())))
+            {
+                i += 1;
+            }
+
+            let is_numeric = (begin > 0 && chars[begin - 1].is_numeric())
+                || chars.get(i).map_or(false, |c| c.is_numeric());
+            let word = &chars[begin..i];
+            if is_numeric || word.len() < 2 || word[1..].iter().any(|c| c.is_uppercase()) {
+                continue;
+            }
+
+            self.words.push(Word {
+                text: word.iter().collect(),
+                range: Range::new_simple(
+                    start.line,
+                    start.character + begin as u64,
+                    start.line,
+                    start.character + i as u64,
+                ),
+            });
+        }
+    }
+}
+
+impl Visitor for WordFinder {
+    fn visit(&mut self, tree: &latex::Tree, node: AstNodeIndex) {
+        match &tree[node] {
+            latex::Node::Root(_) => tree.walk(self, node),
+            latex::Node::Group(group) => {
+                if group.kind == latex::GroupKind::Group {
+                    tree.walk(self, node);
+                }
+            }
+            latex::Node::Command(cmd) => {
+                if Self::is_prose_command(cmd.name.text()) {
+                    tree.walk(self, node);
+                }
+            }
+            latex::Node::Text(text) => {
+                if self
+                    .excluded
+                    .iter()
+                    .all(|range| !range.contains(text.start()))
+                {
+                    for word in &text.words {
+                        self.split(word);
+                    }
+                }
+            }
+            latex::Node::Comma(_) | latex::Node::Math(_) => (),
+        }
+    }
+}
+
+/// Detects the main language of a project by looking at the options of `babel`
+/// or the language commands of `polyglossia`.
+pub fn detect_language(docs: &[Arc<Document>]) -> Option<String> {
+    for doc in docs {
+        if let DocumentContent::Latex(table) = &doc.content {
+            if let Some(language) = detect_babel_language(table) {
+                return Some(dictionary_name(&language).to_owned());
+            }
+
+            if let Some(language) = detect_polyglossia_language(table) {
+                return Some(dictionary_name(&language).to_owned());
+            }
+        }
+    }
+    None
+}
+
+fn detect_babel_language(table: &latex::SymbolTable) -> Option<String> {
+    let include = table.includes.iter().find(|include| {
+        include.kind == LatexIncludeKind::Package
+            && include
+                .paths(&table.tree)
+                .iter()
+                .any(|path| path.text() == "babel")
+    })?;
+
+    let options =
+        table.extract_comma_separated_words(include.parent, latex::GroupKind::Options, 0)?;
+    options
+        .iter()
+        .find_map(|option| option_value(option.text(), "main"))
+        .or_else(|| {
+            options
+                .iter()
+                .rev()
+                .map(|option| option.text())
+                .find(|option| !option.contains('='))
+        })
+        .map(ToOwned::to_owned)
+}
+
+fn option_value<'a>(option: &'a str, key: &str) -> Option<&'a str> {
+    let mut parts = option.splitn(2, '=');
+    if parts.next()? == key {
+        parts.next()
+    } else {
+        None
+    }
+}
+
+fn detect_polyglossia_language(table: &latex::SymbolTable) -> Option<String> {
+    table.commands.iter().find_map(|node| {
+        let cmd = table.as_command(*node)?;
+        if cmd.name.text() != "\\setdefaultlanguage" && cmd.name.text() != "\\setmainlanguage" {
+            return None;
+        }
+
+        let language = table
+            .extract_word(*node, latex::GroupKind::Group, 0)?
+            .text();
+        let variant = table
+            .extract_comma_separated_words(*node, latex::GroupKind::Options, 0)
+            .and_then(|options| {
+                options
+                    .iter()
+                    .find_map(|option| option_value(option.text(), "variant"))
+                    .map(ToOwned::to_owned)
+            });
+
+        match variant {
+            Some(variant) if language == "english" => Some(variant),
+            _ => Some(language.to_owned()),
+        }
+    })
+}
+
+/// Maps the name of a `babel` or `polyglossia` language to the name of a Hunspell dictionary.
+fn dictionary_name(language: &str) -> &str {
+    match language {
+        "english" | "american" | "USenglish" | "us" => "en_US",
+        "british" | "UKenglish" | "uk" => "en_GB",
+        "canadian" => "en_CA",
+        "australian" => "en_AU",
+        "newzealand" => "en_NZ",
+        "german" | "ngerman" => "de_DE",
+        "austrian" | "naustrian" => "de_AT",
+        "swissgerman" | "nswissgerman" => "de_CH",
+        "french" | "francais" | "frenchb" => "fr_FR",
+        "spanish" => "es_ES",
+        "italian" => "it_IT",
+        "dutch" => "nl_NL",
+        "portuguese" | "portuges" => "pt_PT",
+        "brazil" | "brazilian" => "pt_BR",
+        "polish" => "pl_PL",
+        "russian" => "ru_RU",
+        "swedish" => "sv_SE",
+        "danish" => "da_DK",
+        "norsk" | "norwegian" => "nb_NO",
+        "finnish" => "fi_FI",
+        "czech" => "cs_CZ",
+        "greek" => "el_GR",
+        "turkish" => "tr_TR",
+        "hungarian" | "magyar" => "hu_HU",
+        language => language,
+    }
+}
+
+fn dictionary_directories(options: &LatexSpellingOptions) -> Vec<PathBuf> {
+    let mut dirs = options.dictionary_directories.clone().unwrap_or_default();
+    if let Some(paths) = env::var_os("DICPATH") {
+        dirs.extend(env::split_paths(&paths));
+    }
+
+    dirs.push("/usr/share/hunspell".into());
+    dirs.push("/usr/share/myspell".into());
+    dirs.push("/usr/share/myspell/dicts".into());
+    dirs.push("/Library/Spelling".into());
+    if let Some(home) = env::var_os("HOME") {
+        dirs.push(PathBuf::from(home).join("Library/Spelling"));
+    }
+    dirs
+}
+
+pub fn spelling_options(options: &Options) -> LatexSpellingOptions {
+    options
+        .latex
+        .as_ref()
+        .and_then(|opts| opts.spelling.clone())
+        .unwrap_or_default()
+}
+
+/// Loads the dictionary that should be used to check the given documents.
+/// Dictionaries are cached after they have been loaded once.
+/// Missing dictionaries are not cached because they may be installed later.
+pub async fn find_dictionary(docs: &[Arc<Document>], options: &Options) -> Option<Arc<Dictionary>> {
+    let options = spelling_options(options);
+    let language = options
+        .language
+        .clone()
+        .or_else(|| detect_language(docs))
+        .unwrap_or_else(|| DEFAULT_LANGUAGE.to_owned());
+
+    let dirs = dictionary_directories(&options);
+    let key = (language, dirs);
+    let mut dictionaries = DICTIONARIES.lock().await;
+    if let Some(dictionary) = dictionaries.get(&key) {
+        return Some(Arc::clone(dictionary));
+    }
+
+    let (language, dirs) = &key;
+    for dir in dirs {
+        let dic = fs::read(dir.join(format!("{}.dic", language))).await;
+        let aff = fs::read(dir.join(format!("{}.aff", language))).await;
+        if let (Ok(dic), Ok(aff)) = (dic, aff) {
+            let dictionary = Arc::new(Dictionary::parse(&dic, &aff));
+            dictionaries.insert(key.clone(), Arc::clone(&dictionary));
+            return Some(dictionary);
+        }
+    }
+    None
+}
+
+/// Returns the root directory of the project that consists of the given documents:
+/// either the configured root directory or the directory of the root document.
+fn project_dir(docs: &[Arc<Document>], options: &Options, current_dir: &Path) -> PathBuf {
+    if let Some(root_directory) = options
+        .latex
+        .as_ref()
+        .and_then(|opts| opts.root_directory.as_ref())
+    {
+        return current_dir.join(root_directory);
+    }
+
+    docs.iter()
+        .find(|doc| {
+            doc.content
+                .as_latex()
+                .map_or(false, |table| table.is_standalone)
+        })
+        .or_else(|| docs.first())
+        .and_then(|doc| doc.uri.to_file_path().ok())
+        .and_then(|path| path.parent().map(ToOwned::to_owned))
+        .unwrap_or_else(|| current_dir.to_owned())
+}
+
+fn project_dictionary_path(
+    docs: &[Arc<Document>],
+    options: &Options,
+    current_dir: &Path,
+) -> PathBuf {
+    project_dir(docs, options, current_dir).join(spelling_options(options).project_dictionary())
+}
+
+pub async fn load_project_words(
+    docs: &[Arc<Document>],
+    options: &Options,
+    current_dir: &Path,
+) -> HashSet<String> {
+    match fs::read_to_string(project_dictionary_path(docs, options, current_dir)).await {
+        Ok(text) => text
+            .lines()
+            .map(str::trim)
+            .filter(|word| !word.is_empty())
+            .map(ToOwned::to_owned)
+            .collect(),
+        Err(_) => HashSet::new(),
+    }
+}
+
+pub async fn add_project_word(
+    word: &str,
+    docs: &[Arc<Document>],
+    options: &Options,
+    current_dir: &Path,
+) -> io::Result<()> {
+    let mut file = fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(project_dictionary_path(docs, options, current_dir))
+        .await?;
+    file.write_all(format!("{}\n", word).as_bytes()).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        protocol::Uri,
+        tex::{Language, Resolver},
+        workspace::DocumentParams,
+    };
+    use indoc::indoc;
+
+    fn create_document(text: &str) -> Arc<Document> {
+        Arc::new(Document::open(DocumentParams {
+            uri: Uri::parse("http://www.example.com/foo.tex").unwrap(),
+            text: text.into(),
+            language: Language::Latex,
+            resolver: &Resolver::default(),
+            options: &Options::default(),
+            current_dir: &env::current_dir().unwrap(),
+        }))
+    }
+
+    fn words(text: &str) -> Vec<String> {
+        let doc = create_document(text);
+        find_words(doc.content.as_latex().unwrap())
+            .into_iter()
+            .map(|word| word.text)
+            .collect()
+    }
+
+    #[test]
+    fn find_words_prose() {
+        let actual = words(indoc!(
+            r#"
+                \section{Introduction}
+                We don't check \ref{foo} or $x + y$.
+                \begin{equation}
+                    foo
+                \end{equation}
+            "#
+        ));
+        assert_eq!(actual, vec!["Introduction", "We", "don't", "check", "or"]);
+    }
+
+    #[test]
+    fn find_words_skip_special() {
+        let actual = words("LaTeX foo2 PDF \\emph[bar]{baz}");
+        assert_eq!(actual, vec!["baz"]);
+    }
+
+    #[test]
+    fn find_words_verbatim() {
+        let actual = words("\\begin{verbatim}foo\\end{verbatim}");
+        assert!(actual.is_empty());
+    }
+
+    #[test]
+    fn detect_language_babel() {
+        let doc = create_document("\\usepackage[main=british,ngerman]{babel}");
+        assert_eq!(detect_language(&[doc]), Some("en_GB".into()));
+    }
+
+    #[test]
+    fn detect_language_babel_last() {
+        let doc = create_document("\\usepackage[english,ngerman]{babel}");
+        assert_eq!(detect_language(&[doc]), Some("de_DE".into()));
+    }
+
+    #[test]
+    fn detect_language_polyglossia() {
+        let doc = create_document("\\setdefaultlanguage[variant=british]{english}");
+        assert_eq!(detect_language(&[doc]), Some("en_GB".into()));
+    }
+
+    #[test]
+    fn project_dir_root_document() {
+        let dir = env::temp_dir();
+        let doc = Arc::new(Document::open(DocumentParams {
+            uri: Uri::from_file_path(dir.join("project/chapters/foo.tex")).unwrap(),
+            text: "Foo".into(),
+            language: Language::Latex,
+            resolver: &Resolver::default(),
+            options: &Options::default(),
+            current_dir: &dir,
+        }));
+        let root = Arc::new(Document::open(DocumentParams {
+            uri: Uri::from_file_path(dir.join("project/main.tex")).unwrap(),
+            text: "\\begin{document}\\end{document}".into(),
+            language: Language::Latex,
+            resolver: &Resolver::default(),
+            options: &Options::default(),
+            current_dir: &dir,
+        }));
+
+        let actual = project_dir(&[doc, root], &Options::default(), Path::new("/"));
+        assert_eq!(actual, dir.join("project"));
+    }
+
+    #[test]
+    fn detect_language_none() {
+        let doc = create_document("\\usepackage{amsmath}");
+        assert_eq!(detect_language(&[doc]), None);
+    }
+}
@@ -1,5 +1,6 @@
 use once_cell::sync::Lazy;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 
 #[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -81,6 +82,30 @@ impl LatexIncludeKind {
             LatexIncludeKind::Everything => None,
         }
     }
+
+    pub fn is_graphics(self) -> bool {
+        match self {
+            LatexIncludeKind::Image | LatexIncludeKind::Svg | LatexIncludeKind::Pdf => true,
+            LatexIncludeKind::Package
+            | LatexIncludeKind::Class
+            | LatexIncludeKind::Latex
+            | LatexIncludeKind::Bibliography
+            | LatexIncludeKind::Everything => false,
+        }
+    }
+
+    /// Returns the environment variable that contains the search paths for this kind of file.
+    pub fn search_path_variable(self) -> Option<&'static str> {
+        match self {
+            LatexIncludeKind::Package | LatexIncludeKind::Class => None,
+            LatexIncludeKind::Bibliography => Some("BIBINPUTS"),
+            LatexIncludeKind::Latex
+            | LatexIncludeKind::Image
+            | LatexIncludeKind::Svg
+            | LatexIncludeKind::Pdf
+            | LatexIncludeKind::Everything => Some("TEXINPUTS"),
+        }
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
@@ -109,6 +134,13 @@ pub struct LatexMathOperatorCommand {
     pub implementation_index: usize,
 }
 
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LatexEnvironmentDefinitionCommand {
+    pub name: String,
+    pub index: usize,
+}
+
 #[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct LatexTheoremDefinitionCommand {
@@ -178,6 +210,50 @@ pub struct BibtexEntryTypeDoc {
 pub struct BibtexFieldDoc {
     pub name: String,
     pub documentation: String,
+    #[serde(default)]
+    pub values: Vec<String>,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LatexKeyValueCommand {
+    pub name: String,
+    pub index: usize,
+    #[serde(default)]
+    pub options: bool,
+    pub key_set: String,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LatexKeySet {
+    pub name: String,
+    #[serde(default)]
+    pub strict: bool,
+    pub keys: Vec<LatexKey>,
+}
+
+impl LatexKeySet {
+    pub fn find(&self, name: &str) -> Option<&LatexKey> {
+        self.keys.iter().find(|key| key.name == name)
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LatexKey {
+    pub name: String,
+    #[serde(default)]
+    pub values: Vec<String>,
+    #[serde(default)]
+    pub is_length: bool,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LatexSymbolKeywords {
+    pub name: String,
+    pub keywords: Vec<String>,
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Default, Serialize, Deserialize)]
@@ -190,6 +266,8 @@ pub struct LanguageData {
     pub include_commands: Vec<LatexIncludeCommand>,
     pub command_definition_commands: Vec<LatexCommandDefinitionCommand>,
     pub math_operator_commands: Vec<LatexMathOperatorCommand>,
+    pub primitive_definition_commands: Vec<String>,
+    pub environment_definition_commands: Vec<LatexEnvironmentDefinitionCommand>,
     pub theorem_definition_commands: Vec<LatexTheoremDefinitionCommand>,
     pub colors: Vec<String>,
     pub color_commands: Vec<LatexColorCommand>,
@@ -202,9 +280,70 @@ pub struct LanguageData {
     pub tikz_libraries: Vec<String>,
     pub math_environments: Vec<String>,
     pub enum_environments: Vec<String>,
+    pub list_environments: Vec<String>,
+    pub verbatim_environments: Vec<String>,
+    pub math_commands: Vec<String>,
+    pub text_commands: Vec<String>,
+    pub prose_commands: Vec<String>,
+    pub incompatible_packages: Vec<(String, String)>,
+    pub hyperref_predecessors: Vec<String>,
+    pub key_value_commands: Vec<LatexKeyValueCommand>,
+    pub key_sets: Vec<LatexKeySet>,
+    pub length_units: Vec<String>,
+    pub symbol_keywords: Vec<LatexSymbolKeywords>,
+    #[serde(skip)]
+    symbol_keywords_by_name: HashMap<String, usize>,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum LatexCommandMode {
+    Math,
+    Text,
 }
 
 impl LanguageData {
+    /// Returns the mode that is required by the given command (including the backslash).
+    pub fn command_mode(&self, name: &str) -> Option<LatexCommandMode> {
+        if self.math_commands.iter().any(|cmd| cmd == name) {
+            Some(LatexCommandMode::Math)
+        } else if self.text_commands.iter().any(|cmd| cmd == name) {
+            Some(LatexCommandMode::Text)
+        } else {
+            None
+        }
+    }
+
+    /// Checks if the given command (including the backslash) defines a new command or environment.
+    pub fn is_definition_command(&self, name: &str) -> bool {
+        self.command_definition_commands
+            .iter()
+            .any(|cmd| cmd.name == name)
+            || self
+                .math_operator_commands
+                .iter()
+                .any(|cmd| cmd.name == name)
+            || self
+                .primitive_definition_commands
+                .iter()
+                .any(|cmd| cmd == name)
+            || self
+                .environment_definition_commands
+                .iter()
+                .any(|cmd| cmd.name == name)
+    }
+
+    /// Returns the words that describe the symbol of the given command (without the backslash).
+    pub fn symbol_keywords(&self, name: &str) -> &[String] {
+        self.symbol_keywords_by_name
+            .get(name)
+            .map(|index| self.symbol_keywords[*index].keywords.as_slice())
+            .unwrap_or_default()
+    }
+
+    pub fn key_set(&self, name: &str) -> Option<&LatexKeySet> {
+        self.key_sets.iter().find(|set| set.name == name)
+    }
+
     pub fn find_entry_type(&self, name: &str) -> Option<&BibtexEntryTypeDoc> {
         let name = name.to_lowercase();
         self.entry_types
@@ -227,5 +366,13 @@ impl LanguageData {
 
 pub static LANGUAGE_DATA: Lazy<LanguageData> = Lazy::new(|| {
     const JSON: &str = include_str!("../../data/lang_data.json");
-    serde_json::from_str(JSON).expect("Failed to deserialize language.json")
+    let mut data: LanguageData =
+        serde_json::from_str(JSON).expect("Failed to deserialize language.json");
+    data.symbol_keywords_by_name = data
+        .symbol_keywords
+        .iter()
+        .enumerate()
+        .map(|(index, symbol)| (symbol.name.trim_start_matches('\\').to_owned(), index))
+        .collect();
+    data
 });
@@ -24,13 +24,16 @@ pub struct SymbolTable {
     pub is_standalone: bool,
     pub includes: Vec<Include>,
     pub imports: Vec<Import>,
+    pub graphics_paths: Vec<GraphicsPath>,
     pub components: Vec<String>,
     pub citations: Vec<Citation>,
+    pub bib_items: Vec<BibItem>,
     pub command_definitions: Vec<CommandDefinition>,
     pub glossary_entries: Vec<GlossaryEntry>,
     pub equations: Vec<Equation>,
     pub inlines: Vec<Inline>,
     pub math_operators: Vec<MathOperator>,
+    pub environment_definitions: Vec<EnvironmentDefinition>,
     pub theorem_definitions: Vec<TheoremDefinition>,
     pub sections: Vec<Section>,
     pub labels: Vec<Label>,
@@ -63,11 +66,13 @@ impl SymbolTable {
         let mut includes = None;
         let mut imports = None;
         let mut citations = None;
+        let mut bib_items = None;
         let mut command_definitions = None;
         let mut glossary_entries = None;
         let mut equations = None;
         let mut inlines = None;
         let mut math_operators = None;
+        let mut environment_definitions = None;
         let mut theorem_definitions = None;
         let mut sections = None;
         let mut labels = None;
@@ -75,16 +80,19 @@ impl SymbolTable {
         let mut captions = None;
         let mut items = None;
 
+        let graphics_paths = GraphicsPath::parse(ctx);
         rayon::scope(|s| {
             s.spawn(|_| environments = Some(Environment::parse(ctx)));
-            s.spawn(|_| includes = Some(Include::parse(ctx)));
+            s.spawn(|_| includes = Some(Include::parse(ctx, &graphics_paths)));
             s.spawn(|_| imports = Some(Import::parse(ctx)));
             s.spawn(|_| citations = Some(Citation::parse(ctx)));
+            s.spawn(|_| bib_items = Some(BibItem::parse(ctx)));
             s.spawn(|_| command_definitions = Some(CommandDefinition::parse(ctx)));
             s.spawn(|_| glossary_entries = Some(GlossaryEntry::parse(ctx)));
             s.spawn(|_| equations = Some(Equation::parse(ctx)));
             s.spawn(|_| inlines = Some(Inline::parse(ctx)));
             s.spawn(|_| math_operators = Some(MathOperator::parse(ctx)));
+            s.spawn(|_| environment_definitions = Some(EnvironmentDefinition::parse(ctx)));
             s.spawn(|_| theorem_definitions = Some(TheoremDefinition::parse(ctx)));
             s.spawn(|_| sections = Some(Section::parse(ctx)));
             s.spawn(|_| labels = Some(Label::parse(ctx)));
@@ -113,13 +121,16 @@ impl SymbolTable {
             is_standalone,
             includes: includes.unwrap(),
             imports: imports.unwrap(),
+            graphics_paths,
             components,
             citations: citations.unwrap(),
+            bib_items: bib_items.unwrap(),
             command_definitions: command_definitions.unwrap(),
             glossary_entries: glossary_entries.unwrap(),
             equations: equations.unwrap(),
             inlines: inlines.unwrap(),
             math_operators: math_operators.unwrap(),
+            environment_definitions: environment_definitions.unwrap(),
             theorem_definitions: theorem_definitions.unwrap(),
             sections: sections.unwrap(),
             labels: labels.unwrap(),
@@ -170,6 +181,13 @@ impl SymbolTable {
             .filter(|label| label.names(&self.tree).len() == 1)
             .find(|label| self.is_direct_child(env, self.tree[label.parent].start()))
     }
+
+    pub fn find_bib_item(&self, key: &str) -> Option<BibItem> {
+        self.bib_items
+            .iter()
+            .copied()
+            .find(|item| item.key(&self.tree).text() == key)
+    }
 }
 
 impl Deref for SymbolTable {
@@ -208,6 +226,10 @@ impl EnvironmentDelimiter {
         self.is_special(tree, LANGUAGE_DATA.enum_environments.iter())
     }
 
+    pub fn is_verbatim(self, tree: &Tree) -> bool {
+        self.is_special(tree, LANGUAGE_DATA.verbatim_environments.iter())
+    }
+
     fn is_special<'a, I: Iterator<Item = &'a String>>(self, tree: &Tree, mut values: I) -> bool {
         match self.name(tree) {
             Some(name) => values.any(|env| env == name.text()),
@@ -304,14 +326,15 @@ impl Include {
             })
     }
 
-    fn parse(ctx: SymbolContext) -> Vec<Self> {
+    fn parse(ctx: SymbolContext, graphics_paths: &[GraphicsPath]) -> Vec<Self> {
         iproduct!(ctx.commands, LANGUAGE_DATA.include_commands.iter())
-            .filter_map(|(parent, desc)| Self::parse_single(ctx, *parent, desc))
+            .filter_map(|(parent, desc)| Self::parse_single(ctx, graphics_paths, *parent, desc))
             .collect()
     }
 
     fn parse_single(
         ctx: SymbolContext,
+        graphics_paths: &[GraphicsPath],
         parent: AstNodeIndex,
         desc: &LatexIncludeCommand,
     ) -> Option<Self> {
@@ -324,17 +347,12 @@ impl Include {
         let paths = ctx
             .tree
             .extract_comma_separated_words(parent, GroupKind::Group, desc.index)?;
+        let base_urls = Self::base_urls(ctx, graphics_paths, desc.kind)?;
         for path in paths {
-            let mut targets = Vec::new();
-            let base_url = base_url(ctx)?;
-            targets.push(base_url.join(path.text()).ok()?.into());
-
-            if let Some(extensions) = desc.kind.extensions() {
-                for extension in extensions {
-                    let path = format!("{}.{}", path.text(), extension);
-                    targets.push(base_url.join(&path).ok()?.into());
-                }
-            }
+            let mut targets: Vec<Uri> = base_urls
+                .iter()
+                .flat_map(|base_url| include_targets(base_url, desc.kind, path.text()))
+                .collect();
 
             if let Some(target) = Self::resolve_distro_file(ctx, desc, path.text()) {
                 targets.push(target);
@@ -352,6 +370,40 @@ impl Include {
         Some(include)
     }
 
+    /// Resolves the paths relative to another base like the directory of the root document.
+    pub fn targets_relative_to(&self, tree: &Tree, base_url: &Uri) -> Vec<Vec<Uri>> {
+        self.paths(tree)
+            .into_iter()
+            .map(|path| include_targets(base_url, self.kind, path.text()))
+            .collect()
+    }
+
+    fn base_urls(
+        ctx: SymbolContext,
+        graphics_paths: &[GraphicsPath],
+        kind: LatexIncludeKind,
+    ) -> Option<Vec<Uri>> {
+        let base_url = base_url(ctx)?;
+        let mut base_urls = vec![base_url.clone()];
+        if kind.is_graphics() {
+            for path in graphics_paths
+                .iter()
+                .flat_map(|graphics_path| graphics_path.paths(ctx.tree))
+            {
+                let path = if path.ends_with('/') {
+                    path
+                } else {
+                    format!("{}/", path)
+                };
+
+                if let Ok(url) = base_url.join(&path) {
+                    base_urls.push(url.into());
+                }
+            }
+        }
+        Some(base_urls)
+    }
+
     fn resolve_distro_file(
         ctx: SymbolContext,
         desc: &LatexIncludeCommand,
@@ -385,6 +437,22 @@ fn base_url(ctx: SymbolContext) -> Option<Uri> {
     }
 }
 
+fn include_targets(base_url: &Uri, kind: LatexIncludeKind, path: &str) -> Vec<Uri> {
+    let mut targets = Vec::new();
+    if let Ok(target) = base_url.join(path) {
+        targets.push(target.into());
+    }
+
+    if let Some(extensions) = kind.extensions() {
+        for extension in extensions {
+            if let Ok(target) = base_url.join(&format!("{}.{}", path, extension)) {
+                targets.push(target.into());
+            }
+        }
+    }
+    targets
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Import {
     pub parent: AstNodeIndex,
@@ -424,6 +492,42 @@ impl Import {
     }
 }
 
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct GraphicsPath {
+    pub parent: AstNodeIndex,
+}
+
+impl GraphicsPath {
+    pub fn paths(self, tree: &Tree) -> Vec<String> {
+        let mut paths = Vec::new();
+        if let Some(arg) = tree.extract_group(self.parent, GroupKind::Group, 0) {
+            let mut index = 0;
+            while let Some(path) = tree.print_group_content(arg, GroupKind::Group, index) {
+                paths.push(path);
+                index += 1;
+            }
+        }
+        paths
+    }
+
+    fn parse(ctx: SymbolContext) -> Vec<Self> {
+        ctx.commands
+            .iter()
+            .filter_map(|parent| Self::parse_single(ctx, *parent))
+            .collect()
+    }
+
+    fn parse_single(ctx: SymbolContext, parent: AstNodeIndex) -> Option<Self> {
+        let cmd = ctx.tree.as_command(parent)?;
+        if cmd.name.text() != "\\graphicspath" {
+            return None;
+        }
+
+        ctx.tree.extract_group(parent, GroupKind::Group, 0)?;
+        Some(Self { parent })
+    }
+}
+
 #[derive(Debug, Clone, Copy, Serialize, Deserialize)]
 pub struct Citation {
     parent: AstNodeIndex,
@@ -462,6 +566,64 @@ impl Citation {
     }
 }
 
+/// An entry of a `thebibliography` environment, e.g. `\bibitem[label]{key} text`.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct BibItem {
+    pub parent: AstNodeIndex,
+    /// The text of the entry up to the next `\bibitem` or the end of the bibliography.
+    pub body: Range,
+}
+
+impl BibItem {
+    pub fn key(self, tree: &Tree) -> &Token {
+        tree.extract_word(self.parent, GroupKind::Group, 0).unwrap()
+    }
+
+    fn parse(ctx: SymbolContext) -> Vec<Self> {
+        let mut bounds: Vec<_> = ctx
+            .commands
+            .iter()
+            .filter(|parent| {
+                Self::is_bib_item(ctx.tree, **parent)
+                    || Self::is_bibliography_end(ctx.tree, **parent)
+            })
+            .map(|parent| ctx.tree[*parent].start())
+            .collect();
+        bounds.sort();
+
+        let end = ctx.tree[ctx.tree.root].end();
+        ctx.commands
+            .iter()
+            .filter(|parent| Self::is_bib_item(ctx.tree, **parent))
+            .filter_map(|parent| {
+                ctx.tree.extract_word(*parent, GroupKind::Group, 0)?;
+                let start = ctx.tree[*parent].end();
+                let end = bounds
+                    .iter()
+                    .copied()
+                    .find(|pos| *pos >= start)
+                    .unwrap_or(end);
+                Some(Self {
+                    parent: *parent,
+                    body: Range::new(start, end),
+                })
+            })
+            .collect()
+    }
+
+    fn is_bib_item(tree: &Tree, parent: AstNodeIndex) -> bool {
+        tree.as_command(parent)
+            .map_or(false, |cmd| cmd.name.text() == "\\bibitem")
+    }
+
+    fn is_bibliography_end(tree: &Tree, parent: AstNodeIndex) -> bool {
+        tree.as_command(parent)
+            .filter(|cmd| cmd.name.text() == "\\end")
+            .and_then(|_| tree.extract_word(parent, GroupKind::Group, 0))
+            .map_or(false, |name| name.text() == "thebibliography")
+    }
+}
+
 #[derive(Debug, Clone, Copy, Serialize, Deserialize)]
 pub struct CommandDefinition {
     pub parent: AstNodeIndex,
@@ -477,6 +639,25 @@ impl CommandDefinition {
         tree.as_command(self.definition).unwrap().name.text()
     }
 
+    /// Returns the number of mandatory arguments of the defined command.
+    /// If the first argument has a default value, it is optional.
+    pub fn arg_count(self, tree: &Tree) -> usize {
+        let count = tree
+            .extract_word(self.parent, GroupKind::Options, self.arg_count_index)
+            .and_then(|count| count.text().parse().ok())
+            .unwrap_or(0);
+
+        let has_default = tree
+            .extract_group(self.parent, GroupKind::Options, self.arg_count_index + 1)
+            .is_some();
+
+        if has_default && count > 0 {
+            count - 1
+        } else {
+            count
+        }
+    }
+
     fn parse(ctx: SymbolContext) -> Vec<Self> {
         let def = LANGUAGE_DATA.command_definition_commands.iter();
         iproduct!(ctx.commands, def)
@@ -630,6 +811,69 @@ impl Inline {
     }
 }
 
+/// Describes which parts of a document are typeset in math mode.
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub struct MathMode {
+    regions: Vec<(Range, bool)>,
+}
+
+impl MathMode {
+    pub fn analyze(table: &SymbolTable) -> Self {
+        let tree = &table.tree;
+        let mut regions = Vec::new();
+        for equation in &table.equations {
+            regions.push((equation.range(tree), true));
+        }
+
+        for inline in &table.inlines {
+            regions.push((inline.range(tree), true));
+        }
+
+        for env in table
+            .environments
+            .iter()
+            .filter(|env| env.left.is_math(tree))
+        {
+            let range = Range::new(tree[env.left.parent].end(), tree[env.right.parent].start());
+            regions.push((range, true));
+        }
+
+        for node in &table.commands {
+            let cmd = tree.as_command(*node).unwrap();
+            let is_math = if cmd.name.text() == "\\ensuremath" {
+                true
+            } else if LANGUAGE_DATA
+                .prose_commands
+                .iter()
+                .any(|name| name == cmd.name.text())
+            {
+                false
+            } else {
+                continue;
+            };
+
+            for group in tree.children(*node).filter(|child| {
+                tree.as_group(*child)
+                    .map_or(false, |group| group.kind == GroupKind::Group)
+            }) {
+                regions.push((tree[group].range(), is_math));
+            }
+        }
+
+        Self { regions }
+    }
+
+    /// Checks whether the given position is in math mode
+    /// by looking at the innermost region that contains it.
+    pub fn contains(&self, pos: Position) -> bool {
+        self.regions
+            .iter()
+            .filter(|(range, _)| range.contains_exclusive(pos))
+            .max_by_key(|(range, _)| range.start)
+            .map_or(false, |(_, is_math)| *is_math)
+    }
+}
+
 #[derive(Debug, Clone, Copy, Serialize, Deserialize)]
 pub struct MathOperator {
     pub parent: AstNodeIndex,
@@ -680,6 +924,45 @@ impl MathOperator {
     }
 }
 
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct EnvironmentDefinition {
+    pub parent: AstNodeIndex,
+    pub arg_index: usize,
+}
+
+impl EnvironmentDefinition {
+    pub fn name(self, tree: &Tree) -> &Token {
+        tree.extract_word(self.parent, GroupKind::Group, self.arg_index)
+            .unwrap()
+    }
+
+    fn parse(ctx: SymbolContext) -> Vec<Self> {
+        let env = LANGUAGE_DATA.environment_definition_commands.iter();
+        iproduct!(ctx.commands, env)
+            .filter_map(|(parent, desc)| Self::parse_single(ctx, *parent, desc))
+            .collect()
+    }
+
+    fn parse_single(
+        ctx: SymbolContext,
+        parent: AstNodeIndex,
+        desc: &LatexEnvironmentDefinitionCommand,
+    ) -> Option<Self> {
+        let cmd = ctx.tree.as_command(parent)?;
+        if cmd.name.text() != desc.name {
+            return None;
+        }
+
+        let group_kind = GroupKind::Group;
+        ctx.tree.extract_word(parent, group_kind, desc.index)?;
+
+        Some(Self {
+            parent,
+            arg_index: desc.index,
+        })
+    }
+}
+
 #[derive(Debug, Clone, Copy, Serialize, Deserialize)]
 pub struct TheoremDefinition {
     pub parent: AstNodeIndex,
@@ -305,6 +305,43 @@ impl Tree {
         Some(words)
     }
 
+    /// Returns the keys of a key-value list like `[width=3cm, clip]` together with their ranges.
+    pub fn extract_keys(
+        &self,
+        parent: AstNodeIndex,
+        group_kind: GroupKind,
+        index: usize,
+    ) -> Option<Vec<(&str, Range)>> {
+        let group = self.extract_group(parent, group_kind, index)?;
+        let mut keys = Vec::new();
+        let mut expect_key = true;
+        for child in self.children(group) {
+            match &self[child] {
+                Node::Text(text) => {
+                    for word in &text.words {
+                        if expect_key {
+                            let key = word.text().split('=').next().unwrap();
+                            if !key.is_empty() {
+                                let start = word.start();
+                                let end = Position::new(
+                                    start.line,
+                                    start.character + key.chars().count() as u64,
+                                );
+                                keys.push((key, Range::new(start, end)));
+                            }
+                        }
+                        expect_key = false;
+                    }
+                }
+                Node::Comma(_) => expect_key = true,
+                Node::Root(_) | Node::Group(_) | Node::Command(_) | Node::Math(_) => {
+                    expect_key = false
+                }
+            }
+        }
+        Some(keys)
+    }
+
     pub fn print_group_content(
         &self,
         parent: AstNodeIndex,
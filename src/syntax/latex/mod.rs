@@ -48,7 +48,7 @@ pub fn open(params: OpenParams) -> SymbolTable {
 mod tests {
     use super::*;
     use crate::{
-        protocol::{Options, Range, RangeExt, Uri},
+        protocol::{Options, Position, Range, RangeExt, Uri},
         syntax::{generic_ast::AstNodeIndex, text::SyntaxNode},
         tex::Resolver,
     };
@@ -431,6 +431,20 @@ mod tests {
             );
         }
 
+        #[test]
+        fn graphics_path() {
+            verify(
+                vec![vec![
+                    "http://www.foo.com/dir1/dir2/bar",
+                    "http://www.foo.com/dir1/dir2/bar.svg",
+                    "http://www.foo.com/dir1/dir2/figures/bar",
+                    "http://www.foo.com/dir1/dir2/figures/bar.svg",
+                ]],
+                Resolver::default(),
+                indoc!(r#"\graphicspath{{figures}}\includesvg{bar}"#),
+            );
+        }
+
         #[test]
         fn distro_file() {
             let mut resolver = Resolver::default();
@@ -492,6 +506,32 @@ mod tests {
         assert_eq!(actual_keys, expected_keys);
     }
 
+    #[test]
+    fn bib_item() {
+        let table = open_simple(indoc!(
+            r#"
+                \begin{thebibliography}{9}
+                \bibitem{foo} Foo
+                \bibitem[Bar]{bar} Bar
+                \end{thebibliography}
+            "#
+        ));
+
+        let actual_items: Vec<_> = table
+            .bib_items
+            .iter()
+            .map(|item| (item.key(&table.tree).text(), item.body))
+            .collect();
+
+        assert_eq!(
+            actual_items,
+            vec![
+                ("foo", Range::new_simple(1, 13, 2, 0)),
+                ("bar", Range::new_simple(2, 18, 3, 0)),
+            ]
+        );
+    }
+
     #[test]
     fn command_definition() {
         let table = open_simple(indoc!(
@@ -514,6 +554,25 @@ mod tests {
         assert_eq!(actual_cmds, expected_cmds);
     }
 
+    #[test]
+    fn command_definition_arg_count() {
+        let table = open_simple(indoc!(
+            r#"
+                \newcommand{\foo}{Foo}
+                \newcommand{\bar}[2]{Bar}
+                \newcommand{\baz}[2][x]{Baz}
+            "#
+        ));
+
+        let actual_counts: Vec<usize> = table
+            .command_definitions
+            .iter()
+            .map(|def| def.arg_count(&table.tree))
+            .collect();
+
+        assert_eq!(actual_counts, vec![0, 2, 1]);
+    }
+
     #[test]
     fn glossary_entry() {
         let table = open_simple(indoc!(
@@ -575,6 +634,21 @@ mod tests {
         );
     }
 
+    #[test]
+    fn environment_definition() {
+        let table = open_simple(indoc!(
+            r#"
+                \newenvironment{foo}{}{}
+            "#
+        ));
+
+        assert_eq!(table.environment_definitions.len(), 1);
+        assert_eq!(
+            table.environment_definitions[0].name(&table.tree).text(),
+            "foo"
+        );
+    }
+
     #[test]
     fn theorem_definition() {
         let table = open_simple(indoc!(
@@ -632,6 +706,41 @@ mod tests {
         assert_eq!(actual_names, expected_names);
     }
 
+    #[test]
+    fn graphics_path() {
+        let table = open_simple(indoc!(
+            r#"
+                \graphicspath{{figures/}{images/}}
+            "#
+        ));
+
+        assert_eq!(table.graphics_paths.len(), 1);
+        assert_eq!(
+            table.graphics_paths[0].paths(&table.tree),
+            vec!["figures/", "images/"]
+        );
+    }
+
+    #[test]
+    fn math_mode() {
+        let table = open_simple(indoc!(
+            r#"
+                foo $x \text{bar} y$
+                \begin{align}
+                    z
+                \end{align}
+            "#
+        ));
+
+        let math_mode = MathMode::analyze(&table);
+        assert!(!math_mode.contains(Position::new(0, 2)));
+        assert!(math_mode.contains(Position::new(0, 6)));
+        assert!(!math_mode.contains(Position::new(0, 15)));
+        assert!(math_mode.contains(Position::new(0, 19)));
+        assert!(math_mode.contains(Position::new(2, 4)));
+        assert!(!math_mode.contains(Position::new(1, 8)));
+    }
+
     #[test]
     fn label_numbering() {
         let table = open_simple(indoc!(
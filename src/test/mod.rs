@@ -21,9 +21,13 @@ use futures::{
 };
 use jsonrpc::MessageHandler;
 use once_cell::sync::Lazy;
-use std::{path::PathBuf, sync::Arc};
+use std::{path::PathBuf, sync::Arc, time::Duration};
 use tempfile::{tempdir, TempDir};
-use tokio::fs;
+use tokio::{fs, time::delay_for};
+
+const DIAGNOSTICS_TIMEOUT: Duration = Duration::from_secs(5);
+
+const DIAGNOSTICS_POLL_INTERVAL: Duration = Duration::from_millis(100);
 
 struct GlobalDistribution {
     distro: Mutex<Option<Arc<dyn Distribution>>>,
@@ -71,6 +75,7 @@ pub struct TestBedBuilder {
     latex_build: Option<LatexBuildOptions>,
     latex_lint: Option<LatexLintOptions>,
     latex_forward_search: Option<LatexForwardSearchOptions>,
+    latex_spelling: Option<LatexSpellingOptions>,
     bibtex_formatting: Option<BibtexFormattingOptions>,
 }
 
@@ -109,6 +114,11 @@ impl TestBedBuilder {
         self
     }
 
+    pub fn latex_spelling(&mut self, options: LatexSpellingOptions) -> &mut Self {
+        self.latex_spelling = Some(options);
+        self
+    }
+
     pub fn bibtex_formatting(&mut self, options: BibtexFormattingOptions) -> &mut Self {
         self.bibtex_formatting = Some(options);
         self
@@ -178,9 +188,20 @@ impl TestBedBuilder {
                 build: self.latex_build.clone(),
                 forward_search: self.latex_forward_search.clone(),
                 lint: self.latex_lint.clone(),
+                spelling: self
+                    .latex_spelling
+                    .clone()
+                    .map(|options| LatexSpellingOptions {
+                        dictionary_directories: options.dictionary_directories.map(|dirs| {
+                            dirs.into_iter().map(|path| dir.path().join(path)).collect()
+                        }),
+                        ..options
+                    }),
+                ..LatexOptions::default()
             }),
             bibtex: Some(BibtexOptions {
                 formatting: self.bibtex_formatting.clone(),
+                ..BibtexOptions::default()
             }),
         };
 
@@ -364,6 +385,21 @@ impl TestBed {
             .map(|list| list.items)
     }
 
+    /// Waits until the server publishes the diagnostics of the given document.
+    pub async fn diagnostics(&self, relative_path: &str) -> Option<Vec<Diagnostic>> {
+        let uri = self.uri(relative_path);
+        let mut elapsed = Duration::from_secs(0);
+        while elapsed < DIAGNOSTICS_TIMEOUT {
+            if let Some(diagnostics) = self.server.diagnostics_by_uri.get(&uri) {
+                return Some(diagnostics.clone());
+            }
+
+            delay_for(DIAGNOSTICS_POLL_INTERVAL).await;
+            elapsed += DIAGNOSTICS_POLL_INTERVAL;
+        }
+        None
+    }
+
     pub async fn folding_range(&self, relative_path: &str) -> Option<Vec<FoldingRange>> {
         let params = FoldingRangeParams {
             text_document: self.identifier(relative_path),
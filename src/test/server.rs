@@ -14,6 +14,7 @@ pub struct TestLatexLspServer {
     pub progress_buf: Aovec<ProgressParams>,
     pub work_done_progress_create_buf: Aovec<WorkDoneProgressCreateParams>,
     pub log_message_buf: Aovec<LogMessageParams>,
+    pub apply_edit_buf: Aovec<ApplyWorkspaceEditParams>,
 }
 
 #[jsonrpc_server]
@@ -28,6 +29,7 @@ impl TestLatexLspServer {
             progress_buf: Aovec::new(base),
             work_done_progress_create_buf: Aovec::new(base),
             log_message_buf: Aovec::new(base),
+            apply_edit_buf: Aovec::new(base),
         }
     }
 
@@ -77,6 +79,15 @@ impl TestLatexLspServer {
     pub async fn log_message(&self, params: LogMessageParams) {
         self.log_message_buf.push(params);
     }
+
+    #[jsonrpc_method("workspace/applyEdit", kind = "request")]
+    pub async fn apply_edit(
+        &self,
+        params: ApplyWorkspaceEditParams,
+    ) -> Result<ApplyWorkspaceEditResponse> {
+        self.apply_edit_buf.push(params);
+        Ok(ApplyWorkspaceEditResponse { applied: true })
+    }
 }
 
 #[async_trait]
@@ -65,11 +65,16 @@ where
     Ok(Resolver::new(files_by_name))
 }
 
+/// The bibliography styles are not analyzed but they are needed by the completion.
+const STYLE_EXTENSIONS: &[&str] = &["bst", "bbx", "cbx"];
+
 fn is_tex_file(path: &Path) -> bool {
     path.extension()
         .and_then(OsStr::to_str)
-        .and_then(Language::by_extension)
-        .is_some()
+        .map_or(false, |ext| {
+            Language::by_extension(ext).is_some()
+                || STYLE_EXTENSIONS.contains(&ext.to_lowercase().as_str())
+        })
 }
 
 async fn make_absolute(root_directories: &[PathBuf], relative_path: &Path) -> Option<PathBuf> {
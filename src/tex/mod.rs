@@ -1,12 +1,14 @@
 mod compile;
 mod kpsewhich;
 mod miktex;
+mod search_path;
 mod tectonic;
 mod texlive;
 
 pub use self::{
     compile::{Artifacts, CompileError, CompileParams, Format},
     kpsewhich::{KpsewhichError, Resolver},
+    search_path::{clear_search_paths, search_paths},
 };
 
 use self::{compile::Compiler, miktex::Miktex, tectonic::Tectonic, texlive::Texlive};
@@ -72,7 +74,7 @@ pub enum Language {
 impl Language {
     pub fn by_extension(extension: &str) -> Option<Self> {
         match extension.to_lowercase().as_str() {
-            "tex" | "sty" | "cls" | "def" | "lco" | "aux" | "rnw" => Some(Language::Latex),
+            "tex" | "sty" | "cls" | "def" | "lco" | "aux" | "bbl" | "rnw" => Some(Language::Latex),
             "bib" | "bibtex" => Some(Language::Bibtex),
             _ => None,
         }
@@ -134,6 +136,14 @@ pub struct UnknownDistribution {
     resolver: Arc<Resolver>,
 }
 
+impl UnknownDistribution {
+    pub fn new(resolver: Resolver) -> Self {
+        Self {
+            resolver: Arc::new(resolver),
+        }
+    }
+}
+
 #[async_trait]
 impl Distribution for UnknownDistribution {
     fn kind(&self) -> DistributionKind {
@@ -0,0 +1,113 @@
+use crate::syntax::LatexIncludeKind;
+use once_cell::sync::Lazy;
+use std::{
+    collections::HashMap,
+    env,
+    ffi::{OsStr, OsString},
+    fs,
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+};
+
+/// The expanded search paths by the value of the environment variable and the base directory.
+/// Expanding recursive paths walks the file system, so the result is only computed once.
+static SEARCH_PATHS: Lazy<Mutex<HashMap<(OsString, PathBuf), Arc<Vec<PathBuf>>>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// Returns the directories of the environment variable (like `TEXINPUTS`)
+/// that TeX uses to find files of the given kind.
+/// Relative directories are resolved against `dir`.
+pub fn search_paths(kind: LatexIncludeKind, dir: &Path) -> Arc<Vec<PathBuf>> {
+    let paths = match kind.search_path_variable().and_then(env::var_os) {
+        Some(paths) => paths,
+        None => return Arc::default(),
+    };
+
+    let key = (paths, dir.to_owned());
+    let mut search_paths = SEARCH_PATHS.lock().unwrap();
+    if let Some(dirs) = search_paths.get(&key) {
+        return Arc::clone(dirs);
+    }
+
+    let dirs = Arc::new(expand_search_paths(&key.0, dir));
+    search_paths.insert(key, Arc::clone(&dirs));
+    dirs
+}
+
+/// Forgets the expanded search paths, e.g. after the distribution has been reloaded.
+pub fn clear_search_paths() {
+    SEARCH_PATHS.lock().unwrap().clear();
+}
+
+/// Expands a list of search paths like the value of `TEXINPUTS`.
+/// A path that ends with `//` also includes all of its subdirectories.
+fn expand_search_paths(paths: &OsStr, dir: &Path) -> Vec<PathBuf> {
+    let mut dirs = Vec::new();
+    for path in env::split_paths(paths) {
+        let path = match path.to_str() {
+            Some(path) => path,
+            None => continue,
+        };
+
+        let is_recursive = path.ends_with("//");
+        let path = path.trim_end_matches('/');
+        if path.is_empty() {
+            continue;
+        }
+
+        let path = dir.join(path);
+        if is_recursive {
+            collect_subdirectories(&path, &mut dirs);
+        } else {
+            dirs.push(path);
+        }
+    }
+    dirs
+}
+
+fn collect_subdirectories(dir: &Path, dirs: &mut Vec<PathBuf>) {
+    dirs.push(dir.to_owned());
+    if let Ok(entries) = fs::read_dir(dir) {
+        let mut children: Vec<_> = entries
+            .flatten()
+            .filter(|entry| entry.file_type().map_or(false, |ty| ty.is_dir()))
+            .map(|entry| entry.path())
+            .collect();
+        children.sort();
+        for child in children {
+            collect_subdirectories(&child, dirs);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn recursive() {
+        let dir = tempdir().unwrap();
+        fs::create_dir_all(dir.path().join("figures/plots")).unwrap();
+        fs::create_dir_all(dir.path().join("styles")).unwrap();
+
+        let paths = env::join_paths(&["figures//", "styles"]).unwrap();
+        let actual_dirs = expand_search_paths(&paths, dir.path());
+
+        assert_eq!(
+            actual_dirs,
+            vec![
+                dir.path().join("figures"),
+                dir.path().join("figures/plots"),
+                dir.path().join("styles"),
+            ]
+        );
+    }
+
+    #[test]
+    fn default_path() {
+        let paths = env::join_paths(&["", "styles/"]).unwrap();
+        let actual_dirs = expand_search_paths(&paths, Path::new("/foo"));
+        assert_eq!(actual_dirs, vec![Path::new("/foo/styles").to_owned()]);
+    }
+}
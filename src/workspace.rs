@@ -1,14 +1,19 @@
 use crate::{
+    bibliography,
     components::COMPONENT_DATABASE,
     protocol::{Options, TextDocumentItem, Uri},
     syntax::{bibtex, latex, LatexIncludeKind},
-    tex::{Distribution, Language, Resolver},
+    tex::{self, Distribution, Language, Resolver},
 };
 use futures::lock::Mutex;
 use log::{debug, error, warn};
-use petgraph::{graph::Graph, visit::Dfs};
+use petgraph::{
+    graph::{Graph, NodeIndex},
+    visit::Dfs,
+    Undirected,
+};
 use std::{
-    collections::HashMap,
+    collections::{HashMap, HashSet, VecDeque},
     ffi::OsStr,
     hash::{Hash, Hasher},
     io,
@@ -18,6 +23,11 @@ use std::{
 };
 use thiserror::Error;
 use tokio::fs;
+use url::Url;
+
+/// The extensions of the files that are generated next to a LaTeX document
+/// and are analyzed as part of it, e.g. `\bibitem`s of a `.bbl` file.
+const GENERATED_EXTENSIONS: &[&str] = &["aux", "bbl"];
 
 #[derive(Debug, PartialEq, Eq, Clone)]
 pub struct DocumentParams<'a> {
@@ -116,6 +126,8 @@ impl Hash for Document {
     }
 }
 
+type DocumentGraph = Graph<Arc<Document>, (), Undirected>;
+
 #[derive(Debug, PartialEq, Eq, Clone, Default)]
 pub struct Snapshot(pub Vec<Arc<Document>>);
 
@@ -138,23 +150,48 @@ impl Snapshot {
         options: &Options,
         current_dir: &Path,
     ) -> Vec<Arc<Document>> {
+        let (graph, indices_by_uri) = self.build_graph(options, current_dir);
+        match indices_by_uri.get(uri) {
+            Some(index) => Self::connected_documents(&graph, *index),
+            None => Vec::new(),
+        }
+    }
+
+    /// Returns the related documents of every document in the snapshot.
+    /// Unlike calling `relations` for every document, the include graph is only built once
+    /// and every connected component is only traversed once.
+    pub fn all_relations(&self, options: &Options, current_dir: &Path) -> Relations {
+        let (graph, _) = self.build_graph(options, current_dir);
+        let mut relations = Relations::default();
+        for index in graph.node_indices() {
+            if relations.components_by_uri.contains_key(&graph[index].uri) {
+                continue;
+            }
+
+            let component = Self::connected_documents(&graph, index);
+            for doc in &component {
+                relations
+                    .components_by_uri
+                    .insert(doc.uri.clone(), relations.components.len());
+            }
+            relations.components.push(component);
+        }
+        relations
+    }
+
+    fn build_graph(
+        &self,
+        options: &Options,
+        current_dir: &Path,
+    ) -> (DocumentGraph, HashMap<&Uri, NodeIndex>) {
         let mut graph = Graph::new_undirected();
         let mut indices_by_uri = HashMap::new();
         for document in &self.0 {
-            indices_by_uri.insert(&document.uri, graph.add_node(document));
+            indices_by_uri.insert(&document.uri, graph.add_node(Arc::clone(document)));
         }
 
         for parent in &self.0 {
             if let DocumentContent::Latex(table) = &parent.content {
-                table
-                    .includes
-                    .iter()
-                    .flat_map(|include| include.all_targets.iter())
-                    .filter_map(|targets| targets.iter().find_map(|target| self.find(target)))
-                    .for_each(|child| {
-                        graph.add_edge(indices_by_uri[&parent.uri], indices_by_uri[&child.uri], ());
-                    });
-
                 table
                     .imports
                     .iter()
@@ -165,24 +202,83 @@ impl Snapshot {
                         graph.add_edge(indices_by_uri[&parent.uri], indices_by_uri[&child.uri], ());
                     });
 
-                self.resolve_aux_targets(&parent.uri, options, current_dir, "aux")
-                    .into_iter()
-                    .flatten()
-                    .find_map(|target| self.find(&target))
-                    .into_iter()
-                    .for_each(|child| {
-                        graph.add_edge(indices_by_uri[&parent.uri], indices_by_uri[&child.uri], ());
-                    });
+                for extension in GENERATED_EXTENSIONS {
+                    self.resolve_aux_targets(&parent.uri, options, current_dir, extension)
+                        .into_iter()
+                        .flatten()
+                        .find_map(|target| self.find(&target))
+                        .into_iter()
+                        .for_each(|child| {
+                            graph.add_edge(
+                                indices_by_uri[&parent.uri],
+                                indices_by_uri[&child.uri],
+                                (),
+                            );
+                        });
+                }
             }
         }
 
-        let mut documents = Vec::new();
-        if self.find(uri).is_some() {
-            let mut dfs = Dfs::new(&graph, indices_by_uri[uri]);
-            while let Some(index) = dfs.next(&graph) {
-                documents.push(Arc::clone(&graph[index]));
+        for parent in &self.0 {
+            if let DocumentContent::Latex(table) = &parent.content {
+                let parent_index = indices_by_uri[&parent.uri];
+                for include in &table.includes {
+                    for targets in Self::include_targets(parent, table, include, &[]) {
+                        if let Some(child) = targets.iter().find_map(|target| self.find(target)) {
+                            let child_index = indices_by_uri[&child.uri];
+                            if graph.find_edge(parent_index, child_index).is_none() {
+                                graph.add_edge(parent_index, child_index, ());
+                            }
+                        }
+                    }
+                }
             }
         }
+
+        // The includes of a document may also be relative to its root documents,
+        // which are only known once the document is connected to them.
+        // Therefore, every root is traversed once and the includes of the documents
+        // that it reaches are resolved relative to it, following the new edges as well.
+        for root in self.0.iter().filter(|doc| is_standalone(doc)) {
+            let root_index = indices_by_uri[&root.uri];
+            let mut visited = HashSet::new();
+            let mut queue = VecDeque::new();
+            visited.insert(root_index);
+            queue.push_back(root_index);
+            while let Some(parent_index) = queue.pop_front() {
+                let parent = Arc::clone(&graph[parent_index]);
+                if let Some(table) = parent.content.as_latex().filter(|t| !t.is_standalone) {
+                    for include in &table.includes {
+                        for targets in Self::root_targets(table, include, root) {
+                            let child = match targets.iter().find_map(|target| self.find(target)) {
+                                Some(child) => child,
+                                None => continue,
+                            };
+
+                            let child_index = indices_by_uri[&child.uri];
+                            if graph.find_edge(parent_index, child_index).is_none() {
+                                graph.add_edge(parent_index, child_index, ());
+                            }
+                        }
+                    }
+                }
+
+                for child_index in graph.neighbors(parent_index).collect::<Vec<_>>() {
+                    if visited.insert(child_index) {
+                        queue.push_back(child_index);
+                    }
+                }
+            }
+        }
+        (graph, indices_by_uri)
+    }
+
+    fn connected_documents(graph: &DocumentGraph, start: NodeIndex) -> Vec<Arc<Document>> {
+        let mut documents = Vec::new();
+        let mut dfs = Dfs::new(graph, start);
+        while let Some(index) = dfs.next(graph) {
+            documents.push(Arc::clone(&graph[index]));
+        }
         documents
     }
 
@@ -221,17 +317,19 @@ impl Snapshot {
     }
 
     pub fn expand(&self, options: &Options, current_dir: &Path) -> Vec<Uri> {
+        let relations = self.all_relations(options, current_dir);
         let mut unknown_targets = Vec::new();
         for parent in &self.0 {
             if let DocumentContent::Latex(table) = &parent.content {
+                let roots = root_documents(parent, relations.get(&parent.uri));
                 table
                     .includes
                     .iter()
                     .filter(|include| Self::should_expand_include(&table, include))
-                    .flat_map(|include| include.all_targets.iter())
+                    .flat_map(|include| Self::include_targets(parent, table, include, &roots))
                     .filter(|targets| targets.iter().all(|target| self.find(target).is_none()))
                     .flatten()
-                    .for_each(|target| unknown_targets.push(target.clone()));
+                    .for_each(|target| unknown_targets.push(target));
 
                 table
                     .imports
@@ -245,16 +343,95 @@ impl Snapshot {
                     .flat_map(|import| import.targets.iter())
                     .for_each(|target| unknown_targets.push(target.clone()));
 
-                self.resolve_aux_targets(&parent.uri, options, current_dir, "aux")
-                    .into_iter()
-                    .filter(|targets| targets.iter().all(|target| self.find(target).is_none()))
-                    .flatten()
-                    .for_each(|target| unknown_targets.push(target));
+                for extension in GENERATED_EXTENSIONS {
+                    self.resolve_aux_targets(&parent.uri, options, current_dir, extension)
+                        .into_iter()
+                        .filter(|targets| targets.iter().all(|target| self.find(target).is_none()))
+                        .flatten()
+                        .for_each(|target| unknown_targets.push(target));
+                }
+            }
+        }
+
+        for uri in bibliography::library_uris(options, current_dir) {
+            if self.find(&uri).is_none() {
+                unknown_targets.push(uri);
             }
         }
         unknown_targets
     }
 
+    /// Returns the possible targets of an include command.
+    /// In addition to the targets of the document itself, the paths are resolved
+    /// relative to the TeX search paths and the given root documents
+    /// because TeX searches the directory of the main file.
+    fn include_targets(
+        parent: &Document,
+        table: &latex::SymbolTable,
+        include: &latex::Include,
+        roots: &[Arc<Document>],
+    ) -> Vec<Vec<Uri>> {
+        let mut all_targets = include.all_targets.clone();
+        let mut extend = |other_targets: Vec<Vec<Uri>>| {
+            for (targets, other_targets) in all_targets.iter_mut().zip(other_targets) {
+                targets.extend(other_targets);
+            }
+        };
+
+        extend(Self::search_path_targets(table, include, &parent.uri));
+        if !table.is_standalone {
+            for root in roots {
+                extend(Self::root_targets(table, include, root));
+            }
+        }
+        all_targets
+    }
+
+    /// Resolves the paths of an include command relative to a root document
+    /// and the TeX search paths of its directory.
+    fn root_targets(
+        table: &latex::SymbolTable,
+        include: &latex::Include,
+        root: &Document,
+    ) -> Vec<Vec<Uri>> {
+        let mut all_targets = include.targets_relative_to(&table.tree, &root.uri);
+        let search_path_targets = Self::search_path_targets(table, include, &root.uri);
+        for (targets, other_targets) in all_targets.iter_mut().zip(search_path_targets) {
+            targets.extend(other_targets);
+        }
+        all_targets
+    }
+
+    /// Resolves the paths of an include command relative to the TeX search paths
+    /// of the directory of the given document.
+    /// The search paths are expanded lazily because they may require walking the file system.
+    fn search_path_targets(
+        table: &latex::SymbolTable,
+        include: &latex::Include,
+        uri: &Uri,
+    ) -> Vec<Vec<Uri>> {
+        let mut all_targets = vec![Vec::new(); include.all_targets.len()];
+        let path = match uri.to_file_path() {
+            Ok(path) => path,
+            Err(()) => return all_targets,
+        };
+
+        if let Some(dir) = path.parent() {
+            for dir in tex::search_paths(include.kind, dir).iter() {
+                let base_url: Uri = match Url::from_directory_path(dir) {
+                    Ok(url) => url.into(),
+                    Err(()) => continue,
+                };
+
+                let dir_targets = include.targets_relative_to(&table.tree, &base_url);
+                for (targets, dir_targets) in all_targets.iter_mut().zip(dir_targets) {
+                    targets.extend(dir_targets);
+                }
+            }
+        }
+        all_targets
+    }
+
     fn should_expand_include(tree: &latex::Tree, include: &latex::Include) -> bool {
         match include.kind {
             LatexIncludeKind::Bibliography | LatexIncludeKind::Latex => true,
@@ -306,12 +483,132 @@ impl Snapshot {
     }
 }
 
+/// The related documents of every document in a snapshot.
+/// Related documents share one connected component of the include graph.
+#[derive(Debug, Clone, Default)]
+pub struct Relations {
+    components: Vec<Vec<Arc<Document>>>,
+    components_by_uri: HashMap<Uri, usize>,
+}
+
+impl Relations {
+    pub fn get(&self, uri: &Uri) -> &[Arc<Document>] {
+        self.components_by_uri
+            .get(uri)
+            .map(|index| self.components[*index].as_slice())
+            .unwrap_or_default()
+    }
+}
+
+fn is_standalone(doc: &Document) -> bool {
+    doc.content
+        .as_latex()
+        .map_or(false, |table| table.is_standalone)
+}
+
+/// Returns the root documents among the related documents of the given document.
+fn root_documents(doc: &Document, related: &[Arc<Document>]) -> Vec<Arc<Document>> {
+    related
+        .iter()
+        .filter(|root| root.uri != doc.uri && is_standalone(root))
+        .map(Arc::clone)
+        .collect()
+}
+
+/// Returns the directories that are searched for the files of an include command
+/// in addition to the directory of the document itself:
+/// the directory of the root document, the `\graphicspath` directories of images,
+/// the base directories of `\import` and the TeX search paths.
+pub fn include_dirs(
+    uri: &Uri,
+    related: &[Arc<Document>],
+    kind: LatexIncludeKind,
+    options: &Options,
+    current_dir: &Path,
+) -> Vec<Uri> {
+    let root_directory = options
+        .latex
+        .as_ref()
+        .and_then(|opts| opts.root_directory.as_ref());
+
+    let root_url: Uri = match root_directory {
+        Some(root_directory) => match Url::from_directory_path(current_dir.join(root_directory)) {
+            Ok(url) => url.into(),
+            Err(()) => return Vec::new(),
+        },
+        None => related
+            .iter()
+            .find(|doc| is_standalone(doc))
+            .map_or_else(|| uri.clone(), |doc| doc.uri.clone()),
+    };
+
+    let root_dir = match root_url.join("./") {
+        Ok(root_dir) => Uri::from(root_dir),
+        Err(_) => return Vec::new(),
+    };
+
+    let mut dirs = vec![root_dir.clone()];
+    for (doc, table) in related
+        .iter()
+        .filter_map(|doc| Some((doc, doc.content.as_latex()?)))
+    {
+        if kind.is_graphics() {
+            for path in table
+                .graphics_paths
+                .iter()
+                .flat_map(|graphics_path| graphics_path.paths(&table.tree))
+            {
+                if let Ok(dir) = root_dir.join(&directory_path(path)) {
+                    dirs.push(dir.into());
+                }
+            }
+        }
+
+        for import in table
+            .imports
+            .iter()
+            .filter(|import| import.targets.contains(uri))
+        {
+            let path = directory_path(import.dir(&table.tree).text().to_owned());
+            if let Ok(dir) = doc.uri.join(&path) {
+                dirs.push(dir.into());
+            }
+        }
+    }
+
+    if let Ok(path) = root_dir.to_file_path() {
+        for path in tex::search_paths(kind, &path).iter() {
+            if let Ok(dir) = Url::from_directory_path(path) {
+                dirs.push(dir.into());
+            }
+        }
+    }
+
+    let own_dir = uri.join("./").ok().map(Uri::from);
+    let mut unique_dirs = Vec::new();
+    for dir in dirs {
+        if Some(&dir) != own_dir.as_ref() && !unique_dirs.contains(&dir) {
+            unique_dirs.push(dir);
+        }
+    }
+    unique_dirs
+}
+
+fn directory_path(mut path: String) -> String {
+    if !path.ends_with('/') {
+        path.push('/');
+    }
+    path
+}
+
 #[derive(Debug, Error)]
 pub enum WorkspaceLoadError {
     #[error("invalid language id")]
     UnknownLanguage,
     #[error("invalid file path")]
     InvalidPath,
+    #[error("invalid CSL-JSON library")]
+    InvalidLibrary,
     #[error("an I/O error occurred: `{0}`")]
     IO(#[from] io::Error),
 }
@@ -362,18 +659,6 @@ impl Workspace {
     }
 
     pub async fn load(&self, path: &Path, options: &Options) -> Result<(), WorkspaceLoadError> {
-        let language = match path
-            .extension()
-            .and_then(OsStr::to_str)
-            .and_then(Language::by_extension)
-        {
-            Some(language) => language,
-            None => {
-                warn!("Could not determine language: {}", path.to_string_lossy());
-                return Err(WorkspaceLoadError::UnknownLanguage);
-            }
-        };
-
         let uri = match Uri::from_file_path(path) {
             Ok(uri) => uri,
             Err(_) => {
@@ -382,6 +667,20 @@ impl Workspace {
             }
         };
 
+        // Only the configured libraries are converted from CSL-JSON
+        // because other JSON files in the project are unrelated to the bibliography.
+        let extension = path.extension().and_then(OsStr::to_str);
+        let is_csl_json = extension.map_or(false, |ext| ext.eq_ignore_ascii_case("json"))
+            && bibliography::library_uris(options, &self.current_dir).contains(&uri);
+        let language = match extension.and_then(Language::by_extension) {
+            Some(language) => language,
+            None if is_csl_json => Language::Bibtex,
+            None => {
+                warn!("Could not determine language: {}", path.to_string_lossy());
+                return Err(WorkspaceLoadError::UnknownLanguage);
+            }
+        };
+
         let text = match fs::read_to_string(path).await {
             Ok(text) => text,
             Err(why) => {
@@ -390,6 +689,18 @@ impl Workspace {
             }
         };
 
+        let text = if is_csl_json {
+            match bibliography::convert_csl_json(&text) {
+                Some(text) => text,
+                None => {
+                    warn!("Could not parse CSL-JSON library: {}", uri);
+                    return Err(WorkspaceLoadError::InvalidLibrary);
+                }
+            }
+        } else {
+            text
+        };
+
         debug!("Loading document: {}", uri);
         let mut snapshot = self.snapshot.lock().await;
         *snapshot = self
@@ -828,6 +1139,66 @@ mod tests {
         assert_eq!(actual_uris, vec![uri1, uri2, uri3]);
     }
 
+    #[test]
+    fn relations_root_directory() {
+        let uri1 = Uri::parse("http://www.example.com/main.tex").unwrap();
+        let uri2 = Uri::parse("http://www.example.com/chapters/one.tex").unwrap();
+        let uri3 = Uri::parse("http://www.example.com/chapters/two.tex").unwrap();
+        let mut snapshot = Snapshot::new();
+        snapshot.0 = vec![
+            create_simple_document(
+                &uri1,
+                Language::Latex,
+                r#"\begin{document}\include{chapters/one}\end{document}"#,
+            ),
+            create_simple_document(&uri2, Language::Latex, r#"\input{chapters/two}"#),
+            create_simple_document(&uri3, Language::Latex, r#""#),
+        ];
+
+        let actual_uris: Vec<_> = snapshot
+            .relations(&uri2, &Options::default(), &env::current_dir().unwrap())
+            .into_iter()
+            .map(|doc| doc.uri.clone())
+            .collect();
+
+        assert_eq!(actual_uris.len(), 3);
+        assert!(actual_uris.contains(&uri3));
+    }
+
+    #[test]
+    fn relations_unrelated_root_directory() {
+        let uri1 = Uri::parse("http://www.example.com/main.tex").unwrap();
+        let uri2 = Uri::parse("http://www.example.com/chapters/one.tex").unwrap();
+        let uri3 = Uri::parse("http://www.example.com/other/main.tex").unwrap();
+        let uri4 = Uri::parse("http://www.example.com/other/chapters/two.tex").unwrap();
+        let mut snapshot = Snapshot::new();
+        snapshot.0 = vec![
+            create_simple_document(
+                &uri1,
+                Language::Latex,
+                r#"\begin{document}\include{chapters/one}\end{document}"#,
+            ),
+            create_simple_document(&uri2, Language::Latex, r#"\input{chapters/two}"#),
+            create_simple_document(&uri3, Language::Latex, r#"\begin{document}\end{document}"#),
+            create_simple_document(&uri4, Language::Latex, r#""#),
+        ];
+
+        let actual_uris: Vec<_> = snapshot
+            .relations(&uri2, &Options::default(), &env::current_dir().unwrap())
+            .into_iter()
+            .map(|doc| doc.uri.clone())
+            .collect();
+
+        assert_eq!(actual_uris.len(), 2);
+        assert!(actual_uris.contains(&uri1));
+        assert!(!actual_uris.contains(&uri4));
+
+        let expansion = snapshot.expand(&Options::default(), &env::current_dir().unwrap());
+        assert!(expansion
+            .iter()
+            .all(|uri| !uri.as_str().starts_with("http://www.example.com/other/")));
+    }
+
     #[test]
     fn parent() {
         let uri1 = Uri::parse("http://www.example.com/foo.tex").unwrap();
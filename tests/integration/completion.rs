@@ -338,7 +338,7 @@ async fn latex_citation_valid() {
     test_bed.open("main.tex").await;
     test_bed.open("main.bib").await;
 
-    let actual_item = run_item(&test_bed, "main.tex", 3, 6, "foo:2019").await;
+    let actual_item = run_item(&test_bed, "main.tex", 3, 6, "Bar (2019) Baz Qux").await;
 
     test_bed.shutdown().await;
 
@@ -539,6 +539,24 @@ async fn latex_component_kernel_command_glyph() {
     verify_text_edit(&actual_item, 7, 1, 7, 7, "varepsilon");
 }
 
+#[tokio::test]
+async fn latex_component_kernel_command_keyword() {
+    let mut test_bed = TestBedBuilder::new()
+        .file("main.tex", r#"$\infinity$"#)
+        .build()
+        .await;
+    test_bed.spawn();
+    test_bed.initialize(PULL_CAPABILITIES.clone()).await;
+    test_bed.open("main.tex").await;
+
+    let actual_item = run_item(&test_bed, "main.tex", 0, 10, "infty").await;
+
+    test_bed.shutdown().await;
+
+    verify_text_edit(&actual_item, 0, 2, 0, 10, "infty");
+    assert_eq!(actual_item.filter_text.as_deref(), Some("infty infinity"));
+}
+
 #[tokio::test]
 async fn latex_component_kernel_environment() {
     let mut test_bed = TestBedBuilder::new()
@@ -263,6 +263,70 @@ async fn latex_math_operator_link() {
     assert_eq!(link.target_selection_range, Range::new_simple(0, 0, 0, 31));
 }
 
+#[tokio::test]
+async fn latex_environment_link() {
+    let mut test_bed = TestBedBuilder::new()
+        .file(
+            "main.tex",
+            indoc!(
+                r#"
+                    \newenvironment{foo}{}{}
+                    \begin{foo}\end{foo}
+                "#
+            ),
+        )
+        .build()
+        .await;
+    test_bed.spawn();
+    test_bed
+        .initialize(LOCATION_LINK_CAPABILITIES.clone())
+        .await;
+    test_bed.open("main.tex").await;
+
+    let mut actual_links = test_bed.definition_link("main.tex", 1, 8).await.unwrap();
+
+    test_bed.shutdown().await;
+
+    assert_eq!(actual_links.len(), 1);
+    let link = actual_links.pop().unwrap();
+    verify_origin_selection_range(&link, 1, 7, 1, 10);
+    assert_eq!(link.target_uri, test_bed.uri("main.tex").into());
+    assert_eq!(link.target_range, Range::new_simple(0, 0, 0, 24));
+    assert_eq!(link.target_selection_range, Range::new_simple(0, 16, 0, 19));
+}
+
+#[tokio::test]
+async fn latex_glossary_entry_link() {
+    let mut test_bed = TestBedBuilder::new()
+        .file(
+            "main.tex",
+            indoc!(
+                r#"
+                    \newacronym{api}{API}{Foo}
+                    \gls{api}
+                "#
+            ),
+        )
+        .build()
+        .await;
+    test_bed.spawn();
+    test_bed
+        .initialize(LOCATION_LINK_CAPABILITIES.clone())
+        .await;
+    test_bed.open("main.tex").await;
+
+    let mut actual_links = test_bed.definition_link("main.tex", 1, 6).await.unwrap();
+
+    test_bed.shutdown().await;
+
+    assert_eq!(actual_links.len(), 1);
+    let link = actual_links.pop().unwrap();
+    verify_origin_selection_range(&link, 1, 5, 1, 8);
+    assert_eq!(link.target_uri, test_bed.uri("main.tex").into());
+    assert_eq!(link.target_range, Range::new_simple(0, 0, 0, 26));
+    assert_eq!(link.target_selection_range, Range::new_simple(0, 12, 0, 15));
+}
+
 #[tokio::test]
 async fn latex_label_unknown_link() {
     let mut test_bed = TestBedBuilder::new()
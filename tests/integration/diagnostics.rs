@@ -0,0 +1,50 @@
+use indoc::indoc;
+use std::path::PathBuf;
+use texlab::{
+    protocol::{Diagnostic, LatexSpellingOptions, Range, RangeExt},
+    test::{TestBedBuilder, PULL_CAPABILITIES},
+};
+
+#[tokio::test]
+async fn spelling() {
+    let mut test_bed = TestBedBuilder::new()
+        .file(
+            "main.tex",
+            indoc!(
+                r#"
+                    \documentclass{article}
+                    \begin{document}
+                    Hello wrold from texlab
+                    \end{document}
+                "#
+            ),
+        )
+        .file("dict/en_US.dic", "3\nhello\nworld\nfrom\n")
+        .file("dict/en_US.aff", "SET UTF-8\n")
+        .file(".texlab-dictionary", "texlab\n")
+        .latex_spelling(LatexSpellingOptions {
+            enabled: Some(true),
+            language: Some("en_US".into()),
+            dictionary_directories: Some(vec![PathBuf::from("dict")]),
+            project_dictionary: None,
+        })
+        .build()
+        .await;
+    test_bed.spawn();
+    test_bed.initialize(PULL_CAPABILITIES.clone()).await;
+    test_bed.open("main.tex").await;
+
+    let actual_diagnostics: Vec<Diagnostic> = test_bed
+        .diagnostics("main.tex")
+        .await
+        .unwrap()
+        .into_iter()
+        .filter(|diagnostic| diagnostic.source.as_deref() == Some("spelling"))
+        .collect();
+
+    test_bed.shutdown().await;
+
+    assert_eq!(actual_diagnostics.len(), 1);
+    assert_eq!(actual_diagnostics[0].range, Range::new_simple(2, 6, 2, 11));
+    assert_eq!(actual_diagnostics[0].message, "Unknown word: wrold");
+}
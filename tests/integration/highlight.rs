@@ -68,6 +68,42 @@ async fn label() {
     assert_eq!(actual_highlights, expected_highlights);
 }
 
+#[tokio::test]
+async fn environment() {
+    let mut test_bed = TestBedBuilder::new()
+        .file(
+            "main.tex",
+            indoc!(
+                r#"
+                    \begin{foo}
+                    \end{foo}
+                "#
+            ),
+        )
+        .build()
+        .await;
+    test_bed.spawn();
+    test_bed.initialize(PULL_CAPABILITIES.clone()).await;
+    test_bed.open("main.tex").await;
+
+    let actual_highlights = test_bed.document_highlight("main.tex", 0, 8).await.unwrap();
+
+    let expected_highlights = vec![
+        DocumentHighlight {
+            range: Range::new_simple(0, 7, 0, 10),
+            kind: Some(DocumentHighlightKind::Text),
+        },
+        DocumentHighlight {
+            range: Range::new_simple(1, 5, 1, 8),
+            kind: Some(DocumentHighlightKind::Text),
+        },
+    ];
+
+    test_bed.shutdown().await;
+
+    assert_eq!(actual_highlights, expected_highlights);
+}
+
 #[tokio::test]
 async fn unknown_file() {
     let mut test_bed = TestBedBuilder::new().build().await;
@@ -136,6 +136,36 @@ async fn latex_label() {
     assert_eq!(actual_refs, expected_refs);
 }
 
+#[tokio::test]
+async fn latex_command() {
+    let mut test_bed = TestBedBuilder::new()
+        .file(
+            "main.tex",
+            indoc!(
+                r#"
+                    \newcommand{\foo}{Foo}
+                    \foo
+                "#
+            ),
+        )
+        .build()
+        .await;
+    test_bed.spawn();
+    test_bed.initialize(PULL_CAPABILITIES.clone()).await;
+    test_bed.open("main.tex").await;
+
+    let actual_refs = test_bed.references("main.tex", 1, 2, false).await.unwrap();
+
+    test_bed.shutdown().await;
+
+    let expected_refs = vec![Location {
+        uri: test_bed.uri("main.tex").into(),
+        range: Range::new_simple(1, 0, 1, 4),
+    }];
+
+    assert_eq!(actual_refs, expected_refs);
+}
+
 #[tokio::test]
 async fn unknown_file() {
     let mut test_bed = TestBedBuilder::new().build().await;